@@ -0,0 +1,218 @@
+use crate::{objects::Skeleton, util::transform_yeti_matrix};
+
+use super::*;
+use glam::{Mat4, Quat, Vec3};
+use gltf_json as json;
+use json::validation::Checked::Valid;
+
+/// Walks a gao's references down through action banks and actions, collecting every animation as (clip name, ani key)
+pub fn find_action_clips(bf: &Bigfile, key: YKey) -> Vec<(String, YKey)> {
+    let mut clips = Vec::new();
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![key];
+
+    while let Some(key) = to_visit.pop() {
+        if !visited.insert(key) {
+            continue;
+        }
+
+        for subkey in &bf.object_table[&key].references {
+            if !bf.is_key_valid(*subkey) {
+                continue;
+            }
+
+            match bf.file_table[subkey].object_type {
+                ObjectType::lab | ObjectType::acb => to_visit.push(*subkey),
+                ObjectType::act => {
+                    // clips are named after the action, an action should only ever have one animation
+                    let name = bf.file_table[subkey].get_name().to_string();
+                    let act_refs = &bf.object_table[subkey].references;
+                    let parsed_ani = bf.object_table[subkey].archetype.as_action()
+                        .and_then(|act| act.animation)
+                        .and_then(|anim| anim.anim_ref)
+                        .and_then(|idx| act_refs.get(idx as usize));
+                    let anis = match parsed_ani {
+                        Some(ani) => std::slice::from_ref(ani),
+                        None => act_refs.as_slice()
+                    };
+                    for ani in anis.iter().filter(|ani| bf.is_key_valid(**ani)) {
+                        if bf.file_table[ani].object_type == ObjectType::ani && !clips.iter().any(|(_, k)| k == ani) {
+                            clips.push((name.clone(), *ani));
+                        }
+                    }
+                },
+                _ => { }
+            }
+        }
+    }
+
+    clips
+}
+
+fn write_floats(ct: &mut ExportContext, values: &[f32], type_: json::accessor::Type, count: usize, min_max: Option<(f32, f32)>) -> json::Index<json::Accessor> {
+    while !ct.cursor.position().is_multiple_of(4) {
+        ct.cursor.write_u8(0).unwrap();
+    }
+
+    let start = ct.cursor.position();
+    for v in values {
+        ct.cursor.write_f32::<ENDIAN>(*v).expect("write error");
+    }
+    let len = ct.cursor.position() - start;
+
+    let view = ct.root.push(json::buffer::View {
+        buffer: *ct.buffer_js,
+        byte_length: USize64(len),
+        byte_offset: Some(start.into()),
+        byte_stride: None,
+        name: None,
+        target: None, // animation data isn't a vertex attribute
+        extensions: None,
+        extras: None
+    });
+
+    ct.root.push(json::Accessor {
+        buffer_view: Some(view),
+        byte_offset: Some(USize64(0)),
+        count: USize64::from(count),
+        component_type: Valid(json::accessor::GenericComponentType(json::accessor::ComponentType::F32)),
+        extensions: Default::default(),
+        extras: Default::default(),
+        type_: Valid(type_),
+        min: min_max.map(|(min, _)| json::Value::from(vec![min])),
+        max: min_max.map(|(_, max)| json::Value::from(vec![max])),
+        name: None,
+        normalized: false,
+        sparse: None
+    })
+}
+
+/// bone transform relative to its parent, same as the node matrices written by gltf_ske (but still in yeti space)
+fn local_bind_matrix(skeleton: &Skeleton, bone: usize) -> Mat4 {
+    let bone_ref = &skeleton.bones[bone];
+    if let Some(parent_idx) = bone_ref.parent {
+        skeleton.bones[parent_idx as usize].mesh_space_matrix.inverse() * bone_ref.mesh_space_matrix
+    } else {
+        bone_ref.mesh_space_matrix
+    }
+}
+
+/// Exports each clip as a glTF animation targeting the joints of the skin that `gltf_ske` built for `ske_key`
+pub fn gltf_ani_clips(ct: &mut ExportContext, ske_key: YKey, clips: &[(String, YKey)]) {
+    let Some(skeleton) = ct.bf.object_table[&ske_key].archetype.as_skeleton() else { return; };
+
+    let joints = match ct.index_cache.get(&ske_key)
+        .and_then(|nodes| nodes.first())
+        .and_then(|node| ct.root.nodes[*node as usize].skin)
+    {
+        Some(skin) => ct.root.skins[skin.value()].joints.clone(),
+        None => {
+            log::warn!("skeleton {:#010X} wasn't exported, can't export animations", ske_key);
+            return;
+        }
+    };
+
+    // animated nodes can't use a matrix, so switch the joints over to TRS
+    for joint in joints.iter() {
+        let node = &mut ct.root.nodes[joint.value()];
+        if let Some(matrix) = node.matrix.take() {
+            let (scl, rot, pos) = Mat4::from_cols_array(&matrix).to_scale_rotation_translation();
+            node.translation = Some(pos.to_array());
+            node.rotation = Some(json::scene::UnitQuaternion(rot.to_array()));
+            node.scale = Some(scl.to_array());
+        }
+    }
+
+    for (name, ani_key) in clips {
+        if !ct.exported_clips.insert((ske_key, *ani_key)) {
+            continue;
+        }
+
+        let Some(ani) = ct.bf.object_table[ani_key].archetype.as_animation() else { continue; };
+        if let Some(e) = &ani.decode_error {
+            log::warn!("skipping animation {:#010X} {}, it didn't decode: {}", ani_key, name, e);
+            continue;
+        }
+        if ani.num_frames == 0 || ani.frame_rate <= 0.0 {
+            log::warn!("skipping animation {:#010X} {}, no frames", ani_key, name);
+            continue;
+        }
+
+        // glTF requires sampler inputs to strictly increase
+        let times: Vec<f32> = (0..ani.num_frames).map(|f| f as f32 / ani.frame_rate).collect();
+        if !times.iter().all(|t| t.is_finite()) || times.windows(2).any(|w| w[0] >= w[1]) {
+            log::warn!("skipping animation {:#010X} {}, frame rate {} doesn't give increasing key times", ani_key, name, ani.frame_rate);
+            continue;
+        }
+        let time_acc = write_floats(ct, &times, json::accessor::Type::Scalar, times.len(), Some((0.0, *times.last().unwrap())));
+
+        let mut channels = Vec::new();
+        let mut samplers = Vec::new();
+
+        for track in ani.tracks.iter() {
+            let bone = track.bone as usize;
+            if bone >= skeleton.bones.len() || bone >= joints.len() {
+                log::warn!("animation {:#010X} has a track for bone {} but the skeleton only has {}", ani_key, bone, skeleton.bones.len());
+                continue;
+            }
+
+            // resample every frame so the handedness change can be applied to the full transform
+            let (bind_scl, bind_rot, bind_pos) = local_bind_matrix(skeleton, bone).to_scale_rotation_translation();
+            let mut pos_out = Vec::with_capacity(times.len() * 3);
+            let mut rot_out = Vec::with_capacity(times.len() * 4);
+            let mut scl_out = Vec::with_capacity(times.len() * 3);
+            for frame in 0..ani.num_frames {
+                let pose = track.sample(frame as f32);
+                let matrix = Mat4::from_scale_rotation_translation(
+                    pose.scale.unwrap_or(bind_scl),
+                    pose.rotation.unwrap_or(bind_rot),
+                    pose.translation.unwrap_or(bind_pos)
+                );
+                let (scl, rot, pos): (Vec3, Quat, Vec3) = transform_yeti_matrix(&matrix).to_scale_rotation_translation();
+                pos_out.extend_from_slice(&pos.to_array());
+                rot_out.extend_from_slice(&rot.to_array());
+                scl_out.extend_from_slice(&scl.to_array());
+            }
+
+            let outputs = [
+                (!track.translation.is_empty(), json::animation::Property::Translation, pos_out, json::accessor::Type::Vec3),
+                (!track.rotation.is_empty(), json::animation::Property::Rotation, rot_out, json::accessor::Type::Vec4),
+                (!track.scale.is_empty(), json::animation::Property::Scale, scl_out, json::accessor::Type::Vec3),
+            ];
+
+            for (_, property, values, type_) in outputs.into_iter().filter(|o| o.0) {
+                let output = write_floats(ct, &values, type_, times.len(), None);
+                samplers.push(json::animation::Sampler {
+                    extensions: None,
+                    extras: Default::default(),
+                    input: time_acc,
+                    interpolation: Valid(json::animation::Interpolation::Linear),
+                    output
+                });
+                channels.push(json::animation::Channel {
+                    sampler: json::Index::new(samplers.len() as u32 - 1),
+                    target: json::animation::Target {
+                        extensions: None,
+                        extras: Default::default(),
+                        node: joints[bone],
+                        path: Valid(property)
+                    },
+                    extensions: None,
+                    extras: Default::default()
+                });
+            }
+        }
+
+        if channels.is_empty() {
+            continue;
+        }
+
+        ct.root.push(json::Animation {
+            extensions: None,
+            extras: Default::default(),
+            channels,
+            name: Some(name.clone()),
+            samplers
+        });
+    }
+}
@@ -1,49 +1,135 @@
-use crate::objects::ObjectArchetype;
+use crate::objects::{surface_color, CollisionObjectTable, GameMaterialList, ObjectArchetype, PhysicsBody};
 
 use super::*;
-use glam::Vec3;
+use glam::{Vec3, Vec4};
 use serde_json::json;
 use json::validation::Checked::Valid;
 
+fn physics_extras(body: &PhysicsBody) -> serde_json::Value {
+    json!({
+        "body_type": format!("{:?}", body.body_type),
+        "mass": body.mass,
+        "linear_damping": body.linear_damping,
+        "angular_damping": body.angular_damping,
+        "friction": body.friction,
+        "restitution": body.restitution,
+        "center_of_mass": [-body.center_of_mass.x, body.center_of_mass.z, body.center_of_mass.y],
+        "flags": body.flags,
+    })
+}
+
 pub fn gltf_cot<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     gltf_export_init!(ct);
 
+    let physics = CollisionObjectTable::find_references(ct.bf, ct.key, ObjectType::phs).into_iter()
+        .find_map(|key| ct.bf.object_table[&key].archetype.as_physics_structure().and_then(|phs| phs.body))
+        .map(|body| physics_extras(&body));
+
     let mut nodes = Vec::new();
     for key in ct.bf.object_table[&ct.key].references.iter() {
         if ct.bf.is_key_valid(*key) {
-            if ct.bf.file_table[key].object_type.is_col() {
+            let object_type = ct.bf.file_table[key].object_type;
+            if object_type.is_col() || object_type.is_ccm() {
                 do_sub_ct!(ct, *key, {
-                    nodes.append(&mut gltf_col(ct));
+                    match object_type {
+                        ObjectType::col => nodes.append(&mut gltf_col(ct)),
+                        _ => nodes.append(&mut gltf_ccm(ct)),
+                    }
                 });
             }
         }
     }
 
-    nodes
+    // collision objects can be shared between tables, so the table's physics go on a node of its own
+    let Some(physics) = physics else { return nodes; };
+    let node = ct.root.push(json::Node {
+        name: Some(format!("{:#010X} {}", ct.key, ct.bf.file_table[&ct.key].get_name_ext())),
+        children: (!nodes.is_empty()).then_some(nodes),
+        extras: serde_json::value::to_raw_value(&json!({ "physics": physics })).ok(),
+        ..Default::default()
+    });
+
+    vec![node]
 }
 
-pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
-    if !ct.options.export_collision {
-        return vec![];
+fn collision_material(name: String, color: Vec4, extras: serde_json::Value) -> json::Material {
+    json::Material {
+        name: Some(name),
+        pbr_metallic_roughness: json::material::PbrMetallicRoughness {
+            base_color_factor: json::material::PbrBaseColorFactor(color.to_array()),
+            metallic_factor: json::material::StrengthFactor(0.0),
+            roughness_factor: json::material::StrengthFactor(1.0),
+            ..Default::default()
+        },
+        extras: serde_json::value::to_raw_value(&extras).ok(),
+        ..Default::default()
     }
+}
 
+/// Debug material colored by the game material's surface type
+pub fn gltf_gmt(ct: &mut ExportContext) -> Vec<json::Index<json::Material>> {
     gltf_export_init!(ct);
 
+    let name = format!("{:#010X} {}", ct.key, ct.bf.file_table[&ct.key].get_name_ext());
+    let props = ct.bf.object_table[&ct.key].archetype.as_game_material().and_then(|gmt| gmt.props);
+    let (color, extras) = match props {
+        Some(props) => (props.color(), json!({
+            "type": "game_material",
+            "surface_type": props.surface_type,
+            "friction": props.friction,
+            "restitution": props.restitution,
+            "sound_id": props.sound_id,
+            "fx_id": props.fx_id,
+        })),
+        None => (surface_color(u32::from(ct.key) as u64), json!({ "type": "game_material" })),
+    };
+
+    let material = ct.root.push(collision_material(name, color, extras));
+
+    insert_cache!(ct, &ct.key, material);
+
+    vec![material]
+}
+
+/// Materials for a collision's face material indices, from its game material list when one is found
+fn gltf_collision_materials(ct: &mut ExportContext, materials: &[u16]) -> BTreeMap<u16, json::Index<json::Material>> {
+    let mut map = BTreeMap::new();
+    if !ct.options.color_collision_by_material {
+        return map;
+    }
+
+    let bf = ct.bf;
+    let referenced_by = ct.referenced_by.get_or_insert_with(|| GameMaterialList::referenced_by(bf));
+    let gml = GameMaterialList::find_for_collision(bf, referenced_by, ct.key);
+    for material in materials {
+        let gmt = gml.and_then(|gml| GameMaterialList::resolve(ct.bf, gml, *material)).map(|(key, _)| key);
+        let index = match gmt {
+            Some(gmt) => do_sub_ct!(ct, gmt, { gltf_gmt(ct) }).first().copied(),
+            None => Some(*ct.collision_materials.entry(*material).or_insert_with(|| ct.root.push(collision_material(
+                format!("collision material {}", material),
+                surface_color(*material as u64),
+                json!({ "type": "game_material", "collision_material": material }),
+            )))),
+        };
+        if let Some(index) = index {
+            map.insert(*material, index);
+        }
+    }
+
+    map
+}
+
+/// Writes a collision mesh with a primitive per material, faces that index past the positions are dropped
+fn gltf_collision_mesh(ct: &mut ExportContext, name: String, positions: &[Vec3], faces: &[(u16, [u16; 3])], materials: &BTreeMap<u16, json::Index<json::Material>>, mut extras: serde_json::Value) -> json::Node {
     while ct.cursor.position() % 4 != 0 {
         ct.cursor.write_u8(0).unwrap();
     }
 
-    let col_name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
-    let col = match &ct.bf.object_table[&ct.key].archetype {
-        ObjectArchetype::CollisionObject(col) => col,
-        _ => panic!("wrong object type!")
-    };
-
     let mut min = Vec3::splat(f32::INFINITY);
     let mut max = Vec3::splat(-f32::INFINITY);
 
     let vtx_start = ct.cursor.position();
-    for pos in &col.positions {
+    for pos in positions {
         let pos = Vec3::new(-pos.x, pos.z, pos.y);
 
         min = min.min(pos);
@@ -55,15 +141,6 @@ pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     }
     let vbuf_len = ct.cursor.position() - vtx_start;
 
-    let ind_start = ct.cursor.position();
-    for ind in &col.indices {
-        //ct.cursor.write_u16::<ENDIAN>(ind[0]).unwrap(); // no fscking clue what this does
-        ct.cursor.write_u16::<ENDIAN>(ind[1]).unwrap();
-        ct.cursor.write_u16::<ENDIAN>(ind[2]).unwrap();
-        ct.cursor.write_u16::<ENDIAN>(ind[3]).unwrap();
-    }
-    let ind_len = ct.cursor.position() - ind_start;
-
     let vtx_view = ct.root.push(json::buffer::View {
         buffer: *ct.buffer_js,
         byte_length: USize64::from(vbuf_len),
@@ -77,23 +154,10 @@ pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
 
     check_buffer_view!(ct, "vtx_view");
 
-    let ind_view = ct.root.push(json::buffer::View {
-        buffer: *ct.buffer_js,
-        byte_length: USize64::from(ind_len),
-        byte_offset: Some(USize64::from(ind_start)),
-        byte_stride: None, // index buffers are tightly packed, no stride is needed.
-        extensions: Default::default(),
-        extras: Default::default(),
-        name: None,
-        target: Some(Valid(json::buffer::Target::ElementArrayBuffer))
-    });
-
-    check_buffer_view!(ct, "ind_view");
-
     let pos_acc = ct.root.push(json::Accessor {
         buffer_view: Some(vtx_view),
         byte_offset: Some(USize64(0)),
-        count: USize64::from(col.positions.len()),
+        count: USize64::from(positions.len()),
         component_type: Valid(json::accessor::GenericComponentType(json::accessor::ComponentType::F32)),
         extensions: Default::default(),
         extras: Default::default(),
@@ -105,44 +169,98 @@ pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
         sparse: None
     });
     check_buffer_accessor!(ct, "pos_acc for col");
-    
-    let ind_acc = ct.root.push(json::Accessor {
-        buffer_view: Some(ind_view),
-        byte_offset: Some(USize64(0)),
-        count: USize64::from(col.indices.len() * 3),
-        component_type: Valid(json::accessor::GenericComponentType(json::accessor::ComponentType::U16)),
-        extensions: Default::default(),
-        extras: Default::default(),
-        type_: Valid(json::accessor::Type::Scalar),
-        min: None,
-        max: None,
-        name: None,
-        normalized: false,
-        sparse: None
-    });
-        
-    let primitive = json::mesh::Primitive {
-        attributes: {
-            let mut map = BTreeMap::new();
-            map.insert(Valid(json::mesh::Semantic::Positions), pos_acc);
-            map
-        },
-        extensions: Default::default(),
-        extras: Default::default(),
-        indices: Some(ind_acc),
-        material: None,
-        mode: Valid(json::mesh::Mode::Triangles),
-        targets: None
-    };
-    
+
+    let mut by_material: BTreeMap<u16, Vec<[u16; 3]>> = BTreeMap::new();
+    for (material, indices) in faces {
+        if indices.iter().all(|idx| (*idx as usize) < positions.len()) {
+            by_material.entry(*material).or_default().push(*indices);
+        }
+    }
+
+    let mut primitives = Vec::new();
+    for (material, faces) in &by_material {
+        let ind_start = ct.cursor.position();
+        for face in faces {
+            for idx in face {
+                ct.cursor.write_u16::<ENDIAN>(*idx).unwrap();
+            }
+        }
+        let ind_len = ct.cursor.position() - ind_start;
+
+        let ind_view = ct.root.push(json::buffer::View {
+            buffer: *ct.buffer_js,
+            byte_length: USize64::from(ind_len),
+            byte_offset: Some(USize64::from(ind_start)),
+            byte_stride: None, // index buffers are tightly packed, no stride is needed.
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: None,
+            target: Some(Valid(json::buffer::Target::ElementArrayBuffer))
+        });
+
+        check_buffer_view!(ct, "ind_view");
+
+        let ind_acc = ct.root.push(json::Accessor {
+            buffer_view: Some(ind_view),
+            byte_offset: Some(USize64(0)),
+            count: USize64::from(faces.len() * 3),
+            component_type: Valid(json::accessor::GenericComponentType(json::accessor::ComponentType::U16)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Valid(json::accessor::Type::Scalar),
+            min: None,
+            max: None,
+            name: None,
+            normalized: false,
+            sparse: None
+        });
+
+        primitives.push(json::mesh::Primitive {
+            attributes: {
+                let mut map = BTreeMap::new();
+                map.insert(Valid(json::mesh::Semantic::Positions), pos_acc);
+                map
+            },
+            extensions: Default::default(),
+            extras: serde_json::value::to_raw_value(&json!({ "collision_material": material })).ok(),
+            indices: Some(ind_acc),
+            material: materials.get(material).copied(),
+            mode: Valid(json::mesh::Mode::Triangles),
+            targets: None
+        });
+    }
+
     let mesh = ct.root.push(json::Mesh {
         extensions: Default::default(),
         extras: Default::default(),
-        name: Some(format!("{:#010X} {}", ct.key, col_name)),
-        primitives: vec![primitive],
+        name: Some(name.clone()),
+        primitives,
         weights: None
     });
 
+    extras["collision_materials"] = json!(by_material.keys().collect::<Vec<_>>());
+
+    json::Node {
+        mesh: Some(mesh),
+        name: Some(name),
+        extras: serde_json::value::to_raw_value(&extras).ok(),
+        ..Default::default()
+    }
+}
+
+pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
+    if !ct.options.export_collision {
+        return vec![];
+    }
+
+    gltf_export_init!(ct);
+
+    let col_name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
+    let col = match &ct.bf.object_table[&ct.key].archetype {
+        ObjectArchetype::CollisionObject(col) => col,
+        _ => panic!("wrong object type!")
+    };
+
     let name = format!("{:#010X} {}", ct.key, col_name);
 
     // TODO there has to be a flag somewhere in the file itself to determine this right??
@@ -156,19 +274,45 @@ pub fn gltf_col<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
         }
     };
 
-    let extras = Some(json!({
+    let extras = json!({
         "type": "collision",
         "collision_type": col_type
-    }));
-
-    let node = ct.root.push(json::Node {
-        mesh: Some(mesh),
-        name: Some(name),
-        extras: extras.map(|v| serde_json::value::to_raw_value(&v).unwrap()),
-        ..Default::default()
     });
 
+    let faces: Vec<_> = col.faces.iter().map(|face| (face.material, face.indices)).collect();
+    let materials = gltf_collision_materials(ct, &col.materials());
+    let node = gltf_collision_mesh(ct, name, &col.positions, &faces, &materials, extras);
+    let node = ct.root.push(node);
+
     //insert_cache!(ct, &ct.key, node);
 
     vec![node]
-}
\ No newline at end of file
+}
+
+pub fn gltf_ccm(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    if !ct.options.export_collision {
+        return vec![];
+    }
+
+    // a node can only have one parent, so each use gets its own node for the shared mesh
+    if let Some(node) = ct.shared_nodes.get(&ct.key).cloned() {
+        return vec![ct.root.push(node)];
+    }
+
+    let ccm_name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
+    let Some(hull) = ct.bf.object_table[&ct.key].archetype.as_cooked_collision_mesh().and_then(|ccm| ccm.hull.as_ref()) else {
+        log::warn!("no convex hull decoded for {:#010X} {}, skipping", ct.key, ccm_name);
+        return vec![];
+    };
+
+    let extras = json!({
+        "type": "collision",
+        "collision_type": "convex"
+    });
+
+    let faces: Vec<_> = hull.triangles().into_iter().map(|indices| (0, indices)).collect();
+    let node = gltf_collision_mesh(ct, format!("{:#010X} {}", ct.key, ccm_name), &hull.vertices, &faces, &BTreeMap::new(), extras);
+    ct.shared_nodes.insert(ct.key, node.clone());
+
+    vec![ct.root.push(node)]
+}
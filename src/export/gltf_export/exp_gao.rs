@@ -1,10 +1,11 @@
-use crate::{objects::{Light, ObjectArchetype}, util::transform_yeti_matrix};
+use crate::{objects::{Light, LightFlags, ObjectArchetype}, util::transform_yeti_matrix};
 
 use super::*;
 use glam::Mat4;
 use gltf_json as json;
 use json::validation::Checked::Valid;
 use log::warn;
+use serde_json::json;
 
 pub fn gltf_got<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     let (map, skeleton_key) = {
@@ -69,7 +70,19 @@ pub fn gltf_got<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
         nodes.push(skin_node);
     }
 
+    let (got_levels, lod_distances): (HashMap<YKey, u8>, Vec<f32>) = match ct.bf.object_table[&ct.key].archetype.as_graphic_object_table() {
+        Some(got) => (got.mesh_lods(ct.bf, ct.key).into_iter().collect(), got.lod_distances.clone()),
+        None => Default::default()
+    };
+    let got_name = ct.bf.file_table[&ct.key].get_name().to_string();
+    let mut mesh_nodes = Vec::new();
+
     for (&mesh_key, mat_keys) in &map {
+        let got_level = got_levels.get(&mesh_key).copied().unwrap_or(0);
+        if got_level > 0 && ct.options.lod_export_strategy.is_highest_only() {
+            continue;
+        }
+
         do_sub_ct!(ct, mesh_key, {
             ct.sub_context.num_skeleton_bones = skeleton_key.and_then(|key| {
                     ct.bf.object_table[&key].archetype.as_skeleton().map(|ske| ske.num_bones)
@@ -100,7 +113,13 @@ pub fn gltf_got<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
                 if !ct.meshes_processed.contains(&(mesh.value() as u32)) && !mats.is_empty() {
                     for prim in ct.root.meshes[mesh_idx].primitives.iter_mut(){
                         let mat_idx = prim.material.map_or(0, |index| index.value());
-                        prim.material = Some(mats[mat_idx]);
+                        prim.material = Some(match mats.get(mat_idx) {
+                            Some(mat) => *mat,
+                            None => {
+                                log::warn!("mesh {:#010X} uses material {} but only {} are exported, using the first", mesh_key, mat_idx, mats.len());
+                                mats[0]
+                            }
+                        });
                     }
                     ct.meshes_processed.insert(mesh.value() as u32);
                 }
@@ -108,20 +127,57 @@ pub fn gltf_got<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
                 let extras_raw = extras.as_ref().map(|v| serde_json::value::to_raw_value(v).unwrap());
 
                 let name = ct.root.meshes[mesh_idx].name.clone();
-                nodes.push(ct.root.push(json::Node {
+                let level = match got_level {
+                    0 => ct.mesh_lod_levels.get(&mesh_idx).copied().unwrap_or(0),
+                    level => level
+                };
+                mesh_nodes.push((level, ct.root.push(json::Node {
                     mesh: Some(*mesh),
                     name,
                     extras: extras_raw,
                     skin: skin_node.and_then(|node| ct.root.nodes[node.value()].skin),
                     ..Default::default()
-                }));
+                })));
             }
         });
     }
 
+    nodes.append(&mut gltf_lod_nodes(ct, mesh_nodes, &got_name, &lod_distances));
+
     nodes
 }
 
+/// light parameters that have no place in KHR_lights_punctual, kept so maps can be relit in a dcc
+fn light_extras(light: &Light) -> json::Extras {
+    let settings = light.settings()?;
+    let mut extras = json!({
+        "yeti_light_flags": settings.flags.bits(),
+        "yeti_light_mask": settings.mask,
+        "cast_shadows": settings.flags.contains(LightFlags::CAST_SHADOWS),
+        "specular": settings.flags.contains(LightFlags::SPECULAR),
+    });
+    match light {
+        Light::Ambient(ambient) => {
+            extras["yeti_light_type"] = json!("ambient");
+            extras["color"] = json!([ambient.color.x, ambient.color.y, ambient.color.z]);
+            extras["intensity"] = json!(ambient.intensity);
+        },
+        Light::Point(point) => {
+            extras["inner_range"] = json!(point.inner_range);
+        },
+        Light::Spot(spot) => {
+            extras["inner_range"] = json!(spot.inner_range);
+            extras["attenuation"] = json!(spot.attenuation);
+            extras["cone_falloff"] = json!(spot.cone_falloff);
+        },
+        Light::Unknown(light_type, _) => {
+            extras["yeti_light_type"] = json!(light_type);
+        },
+        _ => { }
+    }
+    serde_json::value::to_raw_value(&extras).ok()
+}
+
 pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool) -> Vec<json::Index<json::Node>> {
     gltf_export_init!(ct);
 
@@ -188,7 +244,7 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
                     ct.sub_context = SubContext {
                         _vertex_colors: colors.clone(),
                         capture_visual_for: capture_visual_for.clone(),
-                        ..ct.sub_context
+                        ..ct.sub_context.clone()
                     };
                     
                     match ct.bf.file_table[key].object_type {
@@ -200,9 +256,24 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
             }
         }
         ct.key = old_key;
+        nodes.append(&mut gltf_gao_zones(ct));
         nodes
     };
 
+    if ct.options.export_animations {
+        let ske_key = ct.bf.object_table[&ct.key].references.iter()
+            .filter(|key| ct.bf.is_key_valid(**key) && ct.bf.file_table[key].object_type == ObjectType::got)
+            .flat_map(|got| ct.bf.object_table[got].references.iter())
+            .find(|key| ct.bf.is_key_valid(**key) && ct.bf.file_table[key].object_type == ObjectType::ske)
+            .copied();
+
+        if let Some(ske_key) = ske_key {
+            let clips = find_action_clips(ct.bf, ct.key);
+            log::debug!("exporting {} animations for {}", clips.len(), name);
+            gltf_ani_clips(ct, ske_key, &clips);
+        }
+    }
+
     let light = {
         if let Some(ref mut ext) = ct.root.extensions {
             if let Some(ref mut lights) = ext.khr_lights_punctual {
@@ -211,7 +282,7 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
                         lights.lights.push(json::extensions::scene::khr_lights_punctual::Light {
                             color: [point.color.x, point.color.y, point.color.z],
                             extensions: None,
-                            extras: Default::default(),
+                            extras: light_extras(&gao.light),
                             intensity: point.intensity * ct.options.point_light_intensity_multiplier,
                             name: Some(name.clone()),
                             range: Some(point.range * ct.options.point_light_range_multiplier),
@@ -224,7 +295,7 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
                         lights.lights.push(json::extensions::scene::khr_lights_punctual::Light {
                             color: [spot.color.x, spot.color.y, spot.color.z],
                             extensions: None,
-                            extras: Default::default(),
+                            extras: light_extras(&gao.light),
                             intensity: spot.intensity * ct.options.spot_light_intensity_multiplier,
                             name: Some(name.clone()),
                             range: Some(spot.range * ct.options.spot_light_range_multiplier),
@@ -240,7 +311,7 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
                         lights.lights.push(json::extensions::scene::khr_lights_punctual::Light {
                             color: [directional.color.x, directional.color.y, directional.color.z],
                             extensions: None,
-                            extras: Default::default(),
+                            extras: light_extras(&gao.light),
                             intensity: directional.intensity * ct.options.directional_light_intensity_multiplier,
                             name: Some(name.clone()),
                             range: None,
@@ -249,7 +320,8 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
                         });
                         Some(lights.lights.len() as u32 - 1)
                     },
-                    Light::None => None
+                    // not representable as a punctual light, these only end up in the node's extras
+                    Light::Ambient(_) | Light::Unknown(..) | Light::None => None
                 }
             } else {
                 log::warn!("no khr_lights_punctual struct!");
@@ -262,7 +334,7 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
     };
 
 
-    if nodes.len() == 0 && light == None && !ct.options.export_empty_gaos && skip_empty_gaos_if_possible { // skip exporting empty/childless/implementationless gaos
+    if nodes.is_empty() && light.is_none() && matches!(gao.light, Light::None) && !ct.options.export_empty_gaos && skip_empty_gaos_if_possible { // skip exporting empty/childless/implementationless gaos
         log::debug!("skipping {} due to no data", name);
         return Vec::new();
     }
@@ -271,12 +343,14 @@ pub fn gltf_gao<'a>(ct: &'a mut ExportContext, skip_empty_gaos_if_possible: bool
         matrix: final_matrix.and_then(|m| Some(m.to_cols_array())),
         children: Some(nodes),
         name: Some(name),
+        extras: if light.is_none() { light_extras(&gao.light) } else { None },
         extensions: {
             if let Some(light) = light {
                 Some(json::extensions::scene::Node {
                     khr_lights_punctual: Some(json::extensions::scene::khr_lights_punctual::KhrLightsPunctual {
                         light: json::Index::new(light)
-                    })
+                    }),
+                    ..Default::default()
                 })
             } else {
                 Default::default()
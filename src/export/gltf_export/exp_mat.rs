@@ -1,4 +1,5 @@
 use crate::bigfile::util::unwrap_tga_key;
+use crate::objects::{BlendMode, MaterialFlags, MaterialProperties, ShaderAlphaMode, ShaderClassification, ShaderTextureUse, TextureSlotType};
 
 use super::*;
 use gltf_json as json;
@@ -32,7 +33,17 @@ pub fn gltf_mat<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Material
         extras: Default::default()
     };
 
-    // we want to set texture modes per material, and if not, per shader
+    let props = ct.bf.object_table[&ct.key].archetype.as_material()
+        .and_then(|mat| mat.data.as_decoded())
+        .cloned();
+
+    let classification = if ct.bf.is_key_valid(*shd_key) {
+        ct.bf.object_table[shd_key].archetype.as_shader_graph().and_then(|shd| shd.classify())
+    } else {
+        None
+    };
+
+    // the config lists override everything, per material and if not, per shader
     let mat_key = &ct.key;
 
     if ct.export_config.material_shader_type_ids.has_standard(mat_key, shd_key) {
@@ -51,6 +62,13 @@ pub fn gltf_mat<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Material
         transform_skybox_shader(&mut material, ct);
     } else if ct.export_config.material_shader_type_ids.has_submarine(mat_key, shd_key) {
         transform_submarine_material(&mut material, ct);
+    } else if let Some(props) = props {
+        transform_from_properties(&mut material, ct, &props);
+        if let Some(classification) = &classification {
+            refine_from_shader(&mut material, classification);
+        }
+    } else if let Some(classification) = &classification {
+        transform_from_shader(&mut material, ct, classification);
     } else {
         transform_standard_shader(&mut material, ct);
     }
@@ -62,6 +80,170 @@ pub fn gltf_mat<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Material
     vec![material]
 }
 
+fn export_slot_texture(ct: &mut ExportContext, props: &MaterialProperties, slot: TextureSlotType, hint: TextureTransformHint) -> Option<json::texture::Info> {
+    let tex = props.texture(slot)?;
+    let key = *ct.bf.object_table[&ct.key].references.get(tex.ref_index as usize)?;
+    if !ct.bf.is_key_valid(key) || !ct.bf.file_table[&key].object_type.is_tga() {
+        log::warn!("material {:#010X} {} slot points to {:#010X}, which isn't a texture", ct.key, slot, key);
+        return None;
+    }
+
+    do_sub_ct!(ct, key, {
+        gltf_tga(ct, hint).first().map(|index| json::texture::Info {
+            index: *index,
+            tex_coord: tex.uv_set as u32,
+            extensions: Default::default(),
+            extras: Default::default()
+        })
+    })
+}
+
+fn transform_from_properties(material: &mut json::Material, ct: &mut ExportContext, props: &MaterialProperties) {
+    let base_hint = match props.blend_mode {
+        BlendMode::InvColorAlpha | BlendMode::ColorAlpha => TextureTransformHint::ChannelToAlphaInvertAndClear(0),
+        _ => TextureTransformHint::None
+    };
+
+    material.pbr_metallic_roughness.base_color_factor = json::material::PbrBaseColorFactor(props.diffuse.to_array());
+    material.pbr_metallic_roughness.base_color_texture = export_slot_texture(ct, props, TextureSlotType::Diffuse, base_hint);
+    material.double_sided = props.flags.contains(MaterialFlags::TWO_SIDED);
+
+    if let Some(normal) = export_slot_texture(ct, props, TextureSlotType::Normal, TextureTransformHint::NormalMap) {
+        material.normal_texture = Some(json::material::NormalTexture {
+            index: normal.index,
+            tex_coord: normal.tex_coord,
+            scale: 1.0,
+            extensions: Default::default(),
+            extras: Default::default()
+        });
+    }
+
+    if let Some(specular) = export_slot_texture(ct, props, TextureSlotType::Specular, TextureTransformHint::ChannelToAlpha(0)) {
+        material.extensions = Some(json::extensions::material::Material {
+            specular: Some(json::extensions::material::Specular {
+                specular_color_factor: json::extensions::material::SpecularColorFactor(props.specular.truncate().to_array()),
+                specular_factor: json::extensions::material::SpecularFactor(1.0),
+                specular_texture: Some(specular),
+                specular_color_texture: None,
+                extras: Default::default()
+            }),
+            emissive_strength: None,
+            ..Default::default()
+        });
+    }
+
+    material.emissive_factor = json::material::EmissiveFactor(props.emissive.truncate().to_array());
+    material.emissive_texture = export_slot_texture(ct, props, TextureSlotType::Emissive, TextureTransformHint::None);
+    if material.emissive_texture.is_none() && props.flags.contains(MaterialFlags::EMISSIVE) {
+        material.emissive_texture = material.pbr_metallic_roughness.base_color_texture.clone();
+    }
+
+    if props.blend_mode != BlendMode::Opaque || props.flags.contains(MaterialFlags::ALPHA_BLEND) {
+        material.alpha_mode = Valid(json::material::AlphaMode::Blend);
+    } else if props.flags.contains(MaterialFlags::ALPHA_TEST) {
+        material.alpha_mode = Valid(json::material::AlphaMode::Mask);
+        material.alpha_cutoff = Some(json::material::AlphaCutoff(props.alpha_cutoff()));
+    }
+}
+
+fn export_shader_texture(ct: &mut ExportContext, tex: &ShaderTextureUse, hint: TextureTransformHint) -> Option<json::texture::Info> {
+    // texture slots index the material's texture references in order
+    let textures: Vec<YKey> = ct.bf.object_table[&ct.key].references.iter()
+        .filter(|key| ct.bf.is_key_valid(**key) && ct.bf.file_table[key].object_type.is_tga())
+        .copied()
+        .collect();
+    let Some(key) = textures.get(tex.texture_slot as usize).copied() else {
+        log::warn!("shader samples texture slot {} but material {:#010X} only has {} textures", tex.texture_slot, ct.key, textures.len());
+        return None;
+    };
+
+    do_sub_ct!(ct, key, {
+        gltf_tga(ct, hint).first().map(|index| json::texture::Info {
+            index: *index,
+            // uv sets live in the undecoded node params
+            tex_coord: 0,
+            extensions: Default::default(),
+            extras: Default::default()
+        })
+    })
+}
+
+/// Alpha and emissive settings from the shader, where the material properties didn't already set them
+fn refine_from_shader(material: &mut json::Material, classification: &ShaderClassification) {
+    if material.alpha_mode == Valid(json::material::AlphaMode::Opaque) {
+        match classification.alpha {
+            ShaderAlphaMode::Opaque => { },
+            ShaderAlphaMode::Test(cutoff) => {
+                material.alpha_mode = Valid(json::material::AlphaMode::Mask);
+                material.alpha_cutoff = Some(json::material::AlphaCutoff(cutoff));
+            },
+            ShaderAlphaMode::ColorAlpha { .. } => {
+                material.alpha_mode = Valid(json::material::AlphaMode::Blend);
+            }
+        }
+    }
+
+    if classification.emissive && material.emissive_texture.is_none() && material.emissive_factor.0 == [0.0; 3] {
+        material.emissive_factor = json::material::EmissiveFactor([1.0, 1.0, 1.0]);
+        material.emissive_texture = material.pbr_metallic_roughness.base_color_texture.clone();
+    }
+}
+
+fn transform_from_shader(material: &mut json::Material, ct: &mut ExportContext, classification: &ShaderClassification) {
+    let base_hint = match classification.alpha {
+        ShaderAlphaMode::ColorAlpha { .. } => TextureTransformHint::ChannelToAlphaInvertAndClear(0),
+        _ => TextureTransformHint::None
+    };
+
+    if classification.textures.is_empty() {
+        // constant or vertex colored shaders still get the textures the material references
+        load_standard_shader(material, ct, TextureTransformHint::ChannelToAlpha(0));
+    } else {
+        if let Some(tex) = classification.diffuse_texture().or(classification.first_texture(|tex| tex.alpha)).copied() {
+            material.pbr_metallic_roughness.base_color_texture = export_shader_texture(ct, &tex, base_hint);
+        }
+
+        if let Some(tex) = classification.normal_texture().copied() {
+            material.normal_texture = export_shader_texture(ct, &tex, TextureTransformHint::NormalMap).map(|normal| json::material::NormalTexture {
+                index: normal.index,
+                tex_coord: normal.tex_coord,
+                scale: 1.0,
+                extensions: Default::default(),
+                extras: Default::default()
+            });
+        }
+
+        if let Some(tex) = classification.specular_texture().copied() {
+            if let Some(specular) = export_shader_texture(ct, &tex, TextureTransformHint::ChannelToAlpha(0)) {
+                material.extensions = Some(json::extensions::material::Material {
+                    specular: Some(json::extensions::material::Specular {
+                        specular_color_factor: json::extensions::material::SpecularColorFactor([1.0, 1.0, 1.0]),
+                        specular_factor: json::extensions::material::SpecularFactor(1.0),
+                        specular_texture: Some(specular),
+                        specular_color_texture: None,
+                        extras: Default::default()
+                    }),
+                    emissive_strength: None,
+                    ..Default::default()
+                });
+            }
+        }
+
+        if let Some(tex) = classification.emissive_texture().filter(|tex| !tex.diffuse).copied() {
+            material.emissive_texture = export_shader_texture(ct, &tex, TextureTransformHint::None);
+            material.emissive_factor = json::material::EmissiveFactor([1.0, 1.0, 1.0]);
+        }
+    }
+
+    refine_from_shader(material, classification);
+
+    if classification.unlit {
+        material.extensions.get_or_insert_with(Default::default).emissive_strength = Some(json::extensions::material::EmissiveStrength {
+            emissive_strength: json::extensions::material::EmissiveStrengthFactor(ct.options.unlit_emissive_multiplier)
+        });
+    }
+}
+
 fn load_standard_shader<'a>(material: &mut json::Material, ct: &'a mut ExportContext, spec_transform_hint: TextureTransformHint) {
     let mut textures = ct.bf.object_table[&ct.key].references.iter()
         .filter(|key| ct.bf.is_key_valid(**key) && ct.bf.file_table[key].object_type.is_tga())
@@ -135,7 +317,8 @@ fn load_standard_shader<'a>(material: &mut json::Material, ct: &'a mut ExportCon
                     specular_color_texture: None,
                     extras: Default::default()
                 }),
-                emissive_strength: None
+                emissive_strength: None,
+                ..Default::default()
             })
         });
     }
@@ -191,7 +374,8 @@ fn transform_skybox_shader<'a>(material: &mut json::Material, ct: &'a mut Export
         specular: None,
         emissive_strength: Some(json::extensions::material::EmissiveStrength {
             emissive_strength: json::extensions::material::EmissiveStrengthFactor(ct.options.skybox_emissive_multiplier)
-        })
+        }),
+        ..Default::default()
     });
 }
 
@@ -25,21 +25,38 @@ pub fn gltf_msh<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Mesh>> {
     //     Some(&sc.vertex_colors)
     // });
 
-    let mut prims = Vec::new();
+    let mut prims: BTreeMap<u8, Vec<_>> = BTreeMap::new();
     let mut meshes = Vec::new();
 
-    for idx in 0..msh.submeshes.len() {
-        let submesh = &msh.submeshes[idx];
+    let submeshes = msd.resolve_submeshes(Some(msh));
+    let lod_levels = match ct.options.lod_export_strategy {
+        LodExportStrategy::All => vec![0; submeshes.len()],
+        _ => msd.submesh_lod_levels(&submeshes)
+    };
+
+    for (idx, submesh) in submeshes.iter().enumerate() {
+        let level = lod_levels[idx];
+        if level > 0 && ct.options.lod_export_strategy.is_highest_only() {
+            continue;
+        }
 
         let vertex_range = (submesh.vtx_start as usize)..(submesh.vtx_start as usize + submesh.vtx_num as usize);
+        let face_range = (submesh.face_start / 3) as usize..(submesh.face_start / 3) as usize + submesh.face_num as usize;
+        if vertex_range.end > msd.vertex_data.pos.len() || face_range.end > msd.faces.len() {
+            log::warn!("skipping submesh {} of {:#010X} {}, it's out of range of the mesh data", idx, ct.key, msh_name);
+            continue;
+        }
 
         let vertex_start_u32 = submesh.vtx_start as u32;
+        let vertex_end_u32 = vertex_start_u32 + submesh.vtx_num as u32;
         
         let build = GltfPrimitiveBuild {
             //pos_pre_transformed: Box::new(msd.vertex_data.pos.iter().cloned()),
             pos: Box::new(msd.vertex_data.pos[vertex_range.clone()].iter().map(|v| Vec3::new(-v.x, v.z, v.y))),
-            indices: Box::new(msd.faces[(submesh.face_start / 3) as usize..(submesh.face_start / 3) as usize + submesh.face_num as usize]
-                .iter().flat_map(|face| [face.f0 - vertex_start_u32, face.f1 - vertex_start_u32, face.f2 - vertex_start_u32])),
+            indices: Box::new(msd.faces[face_range]
+                .iter()
+                .filter(move |face| [face.f0, face.f1, face.f2].iter().all(|i| (vertex_start_u32..vertex_end_u32).contains(i)))
+                .flat_map(move |face| [face.f0, face.f1, face.f2].map(|i| i.saturating_sub(vertex_start_u32)))),
             uv0: match msd.vertex_data.uv0.len() {
                 0 => None,
                 _ => Some(Box::new(msd.vertex_data.uv0[vertex_range.clone()].iter().cloned()))
@@ -63,9 +80,10 @@ pub fn gltf_msh<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Mesh>> {
                 _ => Some(Box::new(msd.vertex_data.weights[vertex_range.clone()].iter().map(|weights| { 
                     weights.map(|w| {
                         // if the weight is zero we shouldn't try to index into bone palette
-                        if submesh.bone_palette.len() > 0 && w.weight > 0.0 {
-                            (submesh.bone_palette[w.bone as usize], w.weight)
-                        } else { (0, 0.0) }
+                        match submesh.bone_palette.get(w.bone as usize) {
+                            Some(bone) if w.weight > 0.0 => (*bone, w.weight),
+                            _ => (0, 0.0)
+                        }
                     }) 
                 })))
             },
@@ -83,9 +101,12 @@ pub fn gltf_msh<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Mesh>> {
                 weights: None
             });
 
+            if level > 0 {
+                ct.mesh_lod_levels.insert(mesh.value(), level);
+            }
             meshes.push(mesh);
         } else {
-            prims.push(primitive);
+            prims.entry(level).or_default().push(primitive);
         }
     };
     
@@ -96,16 +117,63 @@ pub fn gltf_msh<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Mesh>> {
 
         meshes
     } else {
-        let mesh = ct.root.push(json::Mesh {
-            extensions: Default::default(),
-            extras: Default::default(),
-            name: Some(format!("{:#010X} {}", ct.key, msh_name)),
-            primitives: prims,
-            weights: None
-        });
+        let multiple_levels = prims.len() > 1;
+        for (level, primitives) in prims {
+            let mesh = ct.root.push(json::Mesh {
+                extensions: Default::default(),
+                extras: Default::default(),
+                name: Some(match multiple_levels {
+                    true => format!("{:#010X} {} lod{}", ct.key, msh_name, level),
+                    false => format!("{:#010X} {}", ct.key, msh_name)
+                }),
+                primitives,
+                weights: None
+            });
 
-        insert_cache!(ct, &ct.key, mesh);
+            if level > 0 {
+                ct.mesh_lod_levels.insert(mesh.value(), level);
+            }
+            insert_cache!(ct, &ct.key, mesh);
+            meshes.push(mesh);
+        }
 
-        vec![mesh]
+        meshes
+    }
+}
+
+/// Groups nodes by LOD level under an `MSFT_lod` node when exporting that way, otherwise returns them as they are.
+/// Lower levels are only referenced by the extension, so they aren't added to the scene.
+pub fn gltf_lod_nodes(ct: &mut ExportContext, nodes: Vec<(u8, json::Index<json::Node>)>, name: &str, distances: &[f32]) -> Vec<json::Index<json::Node>> {
+    if !ct.options.lod_export_strategy.is_msft_lod() || nodes.iter().all(|(level, _)| *level == 0) {
+        return nodes.into_iter().map(|(_, node)| node).collect();
     }
+
+    let mut levels: BTreeMap<u8, Vec<_>> = BTreeMap::new();
+    for (level, node) in nodes {
+        levels.entry(level).or_default().push(node);
+    }
+
+    let mut groups: Vec<_> = levels.into_iter().map(|(level, children)| ct.root.push(json::Node {
+        name: Some(format!("{} lod{}", name, level)),
+        children: Some(children),
+        ..Default::default()
+    })).collect();
+    let highest = groups.remove(0);
+
+    let mut others = serde_json::Map::new();
+    others.insert("MSFT_lod".into(), json!({ "ids": groups.iter().map(|node| node.value()).collect::<Vec<_>>() }));
+    let node = &mut ct.root.nodes[highest.value()];
+    node.extensions = Some(json::extensions::scene::Node {
+        others,
+        ..Default::default()
+    });
+    if !distances.is_empty() {
+        node.extras = serde_json::value::to_raw_value(&json!({ "yeti_lod_distances": distances })).ok();
+    }
+
+    if !ct.root.extensions_used.iter().any(|ext| ext == "MSFT_lod") {
+        ct.root.extensions_used.push("MSFT_lod".into());
+    }
+
+    vec![highest]
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use super::*;
+use glam::Vec3;
+use serde_json::json;
+
+/// Exports a world's navmesh as its own mesh node, with a primitive per area flag combination
+pub fn gltf_nav(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    if !ct.options.export_navmesh {
+        return vec![];
+    }
+
+    // worlds can reach the same navmesh through their references and their nav link, each use gets its own node
+    if let Some(node) = ct.shared_nodes.get(&ct.key).cloned() {
+        return vec![ct.root.push(node)];
+    }
+
+    let nav_name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
+    let Some(navmesh) = ct.bf.object_table[&ct.key].archetype.as_nav_data().and_then(|nav| nav.navmesh.as_ref()) else {
+        log::warn!("no navmesh decoded for {:#010X} {}, skipping", ct.key, nav_name);
+        return vec![];
+    };
+
+    let triangles = navmesh.triangles();
+    if triangles.is_empty() {
+        log::warn!("navmesh {:#010X} {} has no triangles, skipping", ct.key, nav_name);
+        return vec![];
+    }
+
+    // the first area writes the positions, the others only write their indices and share them
+    let mut primitives: Vec<json::mesh::Primitive> = Vec::new();
+    for area in navmesh.areas() {
+        let indices: Vec<u32> = triangles.iter()
+            .filter(|(poly, _)| navmesh.polygons[*poly].area_flags == area)
+            .flat_map(|(_, face)| face.map(|idx| idx as u32))
+            .collect();
+        if indices.is_empty() {
+            continue;
+        }
+
+        let mut primitive = match primitives.first() {
+            Some(first) => json::mesh::Primitive {
+                indices: Some(write_indices(ct, indices.into_iter())),
+                ..first.clone()
+            },
+            None => write_primitive(ct, GltfPrimitiveBuild {
+                pos: Box::new(navmesh.vertices.iter().map(|v| Vec3::new(-v.x, v.z, v.y))),
+                indices: Box::new(indices.into_iter()),
+                uv0: None,
+                uv1: None,
+                tangents: None,
+                normals: None,
+                colors: None,
+                weights: None,
+                material: None,
+            })
+        };
+        primitive.extras = serde_json::value::to_raw_value(&json!({ "area_flags": area.bits() })).ok();
+        primitives.push(primitive);
+    }
+
+    let name = format!("{:#010X} {}", ct.key, nav_name);
+    let mesh = ct.root.push(json::Mesh {
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: Some(name.clone()),
+        primitives,
+        weights: None
+    });
+
+    // polygons are kept as they are in the file so tools don't have to rebuild them from the triangles
+    let extras = json!({
+        "type": "navmesh",
+        "version": navmesh.version,
+        "polygons": navmesh.polygons.iter().map(|poly| json!({
+            "area_flags": poly.area_flags.bits(),
+            "vertices": poly.indices,
+            "neighbours": poly.neighbours,
+        })).collect::<Vec<_>>(),
+    });
+
+    let node = json::Node {
+        mesh: Some(mesh),
+        name: Some(name),
+        extras: serde_json::value::to_raw_value(&extras).ok(),
+        ..Default::default()
+    };
+    ct.shared_nodes.insert(ct.key, node.clone());
+
+    vec![ct.root.push(node)]
+}
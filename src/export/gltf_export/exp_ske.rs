@@ -1,8 +1,37 @@
-use crate::{objects::{ObjectArchetype}, util::transform_yeti_matrix};
+use crate::{objects::{Bone, BoneConstraintType, ObjectArchetype, ProceduralBone, ProceduralBoneKind, Skeleton}, util::transform_yeti_matrix};
 
 use super::*;
 use gltf_json as json;
 use json::validation::Checked::Valid;
+use serde_json::json;
+
+fn bone_extras(bone: &Bone) -> json::Extras {
+    let constraint = &bone.constraint;
+    let constraint = (constraint.constraint_type != BoneConstraintType::None).then(|| json!({
+        "type": format!("{:?}", constraint.constraint_type),
+        "target": constraint.target,
+        "weight": constraint.weight,
+        "limits": constraint.limits,
+    }));
+    serde_json::value::to_raw_value(&json!({
+        "flags": bone.flags.bits(),
+        "lod_mask": bone.lod_mask,
+        "constraint": constraint,
+    })).ok()
+}
+
+fn procedural_extras(bone: &ProceduralBone) -> json::Extras {
+    let params = match bone.kind {
+        ProceduralBoneKind::Twist { source, axis, factor } => json!({ "source": source, "axis": axis.to_array(), "factor": factor }),
+        ProceduralBoneKind::Jiggle { stiffness, damping, gravity } => json!({ "stiffness": stiffness, "damping": damping, "gravity": gravity }),
+        ProceduralBoneKind::LookAt { target } => json!({ "target": target }),
+        ProceduralBoneKind::CopyTransform { source, weight } => json!({ "source": source, "weight": weight }),
+    };
+    serde_json::value::to_raw_value(&json!({
+        "procedural": bone.kind.name(),
+        "params": params,
+    })).ok()
+}
 
 pub fn gltf_ske<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     gltf_export_init!(ct);
@@ -26,6 +55,7 @@ pub fn gltf_ske<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
         index_list.push(ct.root.push(json::Node {
             name: Some(bone.get_name().to_string()),
             matrix: Some(gltf_matrix.to_cols_array()),
+            extras: bone_extras(bone),
             ..Default::default()
         }));
     };
@@ -36,14 +66,42 @@ pub fn gltf_ske<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
         node.children = Some(bone.children.iter().map(|&child_idx| index_list[child_idx as usize]).collect());
     }
 
+    // procedural bones go after the skeleton's own, so skin weights still line up with the first joints
+    let procedural_bones = Skeleton::find_procedural(ct.bf, ct.key)
+        .and_then(|key| ct.bf.object_table[&key].archetype.as_skeleton_procedural())
+        .map(|skp| skp.bones.as_slice())
+        .unwrap_or(&[]);
+    let mut inv_bind_matrices: Vec<_> = skeleton.bones.iter().map(|bone| bone.inv_bind_matrix).collect();
+    for bone in procedural_bones {
+        // bones whose parent doesn't resolve hang off the skeleton root instead of becoming extra roots in the skin
+        let resolved = bone.parent.filter(|parent| (*parent as usize) < skeleton.bones.len());
+        let matrix = match resolved {
+            Some(_) => bone.local_matrix(),
+            None => {
+                log::warn!("procedural bone {} has no parent in skeleton {:#010X}, parenting it to the root", bone.get_name(), ct.key);
+                skeleton.bones[0].mesh_space_matrix.inverse() * bone.mesh_space_matrix(skeleton)
+            }
+        };
+        let node = ct.root.push(json::Node {
+            name: Some(bone.get_name().to_string()),
+            matrix: Some(transform_yeti_matrix(&matrix).to_cols_array()),
+            extras: procedural_extras(bone),
+            ..Default::default()
+        });
+        let parent = index_list[resolved.unwrap_or(0) as usize];
+        ct.root.nodes[parent.value()].children.get_or_insert_with(Vec::new).push(node);
+        inv_bind_matrices.push(bone.mesh_space_matrix(skeleton).inverse());
+        index_list.push(node);
+    }
+
     while ct.cursor.position() % 4 != 0 {
         ct.cursor.write_u8(0).unwrap();
     }
 
     let matrix_start = ct.cursor.position();
     
-    for bone in &skeleton.bones {
-        let yeti_matrix = transform_yeti_matrix(&bone.inv_bind_matrix);
+    for inv_bind_matrix in &inv_bind_matrices {
+        let yeti_matrix = transform_yeti_matrix(inv_bind_matrix);
         
         for val in yeti_matrix.to_cols_array() {
             ct.cursor.write_f32::<ENDIAN>(val).unwrap();
@@ -66,7 +124,7 @@ pub fn gltf_ske<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     let matrix_acc = ct.root.push(json::Accessor {
         buffer_view: Some(matrix_view),
         byte_offset: Some(USize64(0)),
-        count: USize64::from(skeleton.bones.len()),
+        count: USize64::from(inv_bind_matrices.len()),
         component_type: Valid(json::accessor::GenericComponentType(json::accessor::ComponentType::F32)),
         extensions: Default::default(),
         extras: Default::default(),
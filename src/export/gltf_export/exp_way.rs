@@ -1,4 +1,4 @@
-use crate::{util::transform_yeti_matrix};
+use crate::{objects::WayNetwork, util::transform_yeti_matrix};
 
 use super::*;
 use glam::{Mat4, Vec3};
@@ -25,7 +25,83 @@ fn center(min: Vec3, max: Vec3) -> Vec3 {
     (min + max) * 0.5
 }
 
+/// Exports a way list's network as line meshes, one line per link between way points
+fn gltf_way_network(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    let name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
+    let network = WayNetwork::from_way_list(ct.bf, ct.key);
+    if network.edges.is_empty() {
+        return vec![];
+    }
+
+    // internal and external links get a primitive each, sharing the positions the first one writes
+    let mut primitives: Vec<json::mesh::Primitive> = Vec::new();
+    for external in [false, true] {
+        let indices: Vec<u32> = network.edges.iter()
+            .filter(|edge| edge.external == external)
+            .flat_map(|edge| [edge.from as u32, edge.to as u32])
+            .collect();
+        if indices.is_empty() {
+            continue;
+        }
+
+        let mut primitive = match primitives.first() {
+            Some(first) => json::mesh::Primitive {
+                indices: Some(write_indices(ct, indices.into_iter())),
+                ..first.clone()
+            },
+            None => write_primitive(ct, GltfPrimitiveBuild {
+                pos: Box::new(network.nodes.iter().map(|node| Vec3::new(-node.position.x, node.position.z, node.position.y))),
+                indices: Box::new(indices.into_iter()),
+                uv0: None,
+                uv1: None,
+                tangents: None,
+                normals: None,
+                colors: None,
+                weights: None,
+                material: None,
+            })
+        };
+        primitive.mode = json::validation::Checked::Valid(json::mesh::Mode::Lines);
+        primitives.push(primitive);
+    }
+
+    let mesh = ct.root.push(json::Mesh {
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: Some(format!("{:#010X} {}", ct.key, name)),
+        primitives,
+        weights: None
+    });
+
+    let extras = json!({
+        "type": "way_network",
+        "points": network.nodes.iter().map(|node| json!({
+            "way": node.way.to_string(),
+            "point": node.point,
+            "gao": node.gao.to_string(),
+            "width": node.width,
+            "flags": node.flags.bits(),
+        })).collect::<Vec<_>>(),
+        "edges": network.edges.iter().map(|edge| json!([edge.from, edge.to, edge.external])).collect::<Vec<_>>(),
+    });
+
+    let node = ct.root.push(json::Node {
+        name: Some(name),
+        mesh: Some(mesh),
+        extras: serde_json::value::to_raw_value(&extras).ok(),
+        ..Default::default()
+    });
+
+    insert_cache!(ct, &ct.key, node);
+
+    vec![node]
+}
+
 pub fn gltf_wal<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
+    if ct.options.way_export_strategy.should_export_graph() {
+        return gltf_way_network(ct);
+    }
+
     let mut nodes = Vec::new();
     for key in &ct.bf.object_table[&ct.key].references {
         do_sub_ct!(ct, *key, {
@@ -39,7 +115,7 @@ pub fn gltf_wal<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
 pub fn gltf_way<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
     let name = ct.bf.file_table[&ct.key].get_name_ext().to_string();
 
-    if ct.options.way_export_strategy.is_none() {
+    if !ct.options.way_export_strategy.should_export_triangles() && !ct.options.way_export_strategy.should_export_extrude() {
         return vec![];
     }
 
@@ -1,14 +1,22 @@
 use super::*;
 use gltf_json as json;
 
+const TEMPLATE_WORLD_KEY: u32 = 0xB00214E8;
+
 pub fn gltf_wor<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
-    if ct.key == 0xB00214E8.into() {
+    // template worlds are only pulled into other worlds, they don't get a node of their own. The known template
+    // key stays as a fallback for when the world data doesn't decode.
+    if ct.key == TEMPLATE_WORLD_KEY.into() || ct.bf.object_table[&ct.key].archetype.as_world().is_some_and(|wor| !wor.is_instanced()) {
         return vec![];
     }
 
     gltf_export_init!(ct);
 
     let refs = &ct.bf.object_table[&ct.key].references;
+    let nav = ct.bf.object_table[&ct.key].archetype.as_world()
+        .and_then(|wor| wor.data.as_decoded())
+        .map(|data| data.links.nav)
+        .filter(|nav| ct.bf.is_key_valid(*nav) && !refs.contains(nav));
     let name = ct.bf.file_table[&ct.key].get_name().to_string();
 
     let node = ct.root.push(json::Node {
@@ -46,12 +54,24 @@ pub fn gltf_wor<'a>(ct: &'a mut ExportContext) -> Vec<json::Index<json::Node>> {
                     do_sub_ct!(ct, *key, {
                         nodes.append(&mut gltf_wal(ct));
                     });
+                },
+                ObjectType::nav => {
+                    do_sub_ct!(ct, *key, {
+                        nodes.append(&mut gltf_nav(ct));
+                    });
                 }
                 _ => { }
             };
         }
     }
 
+    // the nav link isn't always among the world's references
+    if let Some(nav) = nav {
+        do_sub_ct!(ct, nav, {
+            nodes.append(&mut gltf_nav(ct));
+        });
+    }
+
     ct.root.nodes[node.value()].children = Some(nodes);
 
     vec![node]
@@ -0,0 +1,201 @@
+use crate::{objects::{find_gao_zones, Zone, ZoneModifier, ZoneType}, util::transform_yeti_matrix};
+
+use super::*;
+use glam::{Mat4, Vec3};
+use gltf_json as json;
+use json::validation::Checked::Valid;
+
+const CIRCLE_SEGMENTS: u32 = 24;
+
+fn modifier_json(modifier: &ZoneModifier) -> serde_json::Value {
+    match modifier {
+        ZoneModifier::Ambient { color, intensity } => json!({ "type": "ambient", "color": color.to_array(), "intensity": intensity }),
+        ZoneModifier::Fog { color, start, end, density } => json!({ "type": "fog", "color": color.to_array(), "start": start, "end": end, "density": density }),
+        ZoneModifier::PostFx { post_fx } => json!({ "type": "post_fx", "post_fx": post_fx.to_string() }),
+        ZoneModifier::SunLight { direction, color, intensity, unk_01 } => json!({ "type": "sun_light", "direction": direction.to_array(), "color": color.to_array(), "intensity": intensity, "unk_01": unk_01 }),
+        ZoneModifier::CharacterDiffuseMultiplier { multiplier } => json!({ "type": "character_diffuse_multiplier", "multiplier": multiplier }),
+    }
+}
+
+fn zone_extras(zone: &Zone) -> json::Extras {
+    let mut extras = match &zone.zone_type {
+        ZoneType::Point => json!({ "zone_shape": "point" }),
+        ZoneType::Ray(ray) => json!({ "zone_shape": "ray", "length": ray.length }),
+        ZoneType::Sphere(sphere) => json!({ "zone_shape": "sphere", "radius": sphere.radius }),
+        ZoneType::AABox(aabox) => json!({ "zone_shape": "aabox", "size": (aabox.max - aabox.min).to_array() }),
+        ZoneType::OBox(obox) => json!({ "zone_shape": "obox", "extents": obox.extents.to_array() }),
+        ZoneType::Capsule(capsule) => json!({ "zone_shape": "capsule", "radius": capsule.radius, "length": capsule.start.distance(capsule.end) }),
+    };
+    if let Some(modifiers) = &zone.modifiers {
+        extras["zone_modifiers"] = modifiers.iter().map(modifier_json).collect();
+    }
+    serde_json::value::to_raw_value(&extras).ok()
+}
+
+/// half size of the shape in its local space, None for shapes without a volume
+fn zone_half_extents(zone_type: &ZoneType) -> Option<Vec3> {
+    match zone_type {
+        ZoneType::Point | ZoneType::Ray(_) => None,
+        ZoneType::Sphere(sphere) => Some(Vec3::splat(sphere.radius)),
+        ZoneType::AABox(aabox) => Some((aabox.max - aabox.min) / 2.0),
+        ZoneType::OBox(obox) => Some(obox.extents),
+        ZoneType::Capsule(capsule) => Some(Vec3::new(capsule.radius, capsule.radius, capsule.start.distance(capsule.end) / 2.0 + capsule.radius)),
+    }
+}
+
+fn box_corners(half: Vec3) -> Vec<Vec3> {
+    (0..8).map(|i| Vec3::new(
+        if i & 1 == 0 { -half.x } else { half.x },
+        if i & 2 == 0 { -half.y } else { half.y },
+        if i & 4 == 0 { -half.z } else { half.z },
+    )).collect()
+}
+
+/// a closed box around the shape, as triangles
+fn zone_box_mesh(zone_type: &ZoneType) -> Option<(Vec<Vec3>, Vec<u32>)> {
+    let half = zone_half_extents(zone_type)?;
+    // wound counter clockwise seen from outside, the axis swap into gltf space is a rotation so this holds there too
+    let indices = vec![
+        0, 2, 1, 1, 2, 3, // -z
+        4, 5, 6, 5, 7, 6, // +z
+        0, 1, 4, 1, 5, 4, // -y
+        2, 6, 3, 3, 6, 7, // +y
+        0, 4, 2, 2, 4, 6, // -x
+        1, 3, 5, 3, 7, 5, // +x
+    ];
+    Some((box_corners(half), indices))
+}
+
+/// circle of line segments around the z axis
+fn push_circle(pos: &mut Vec<Vec3>, indices: &mut Vec<u32>, center: Vec3, radius: f32, rotation: Mat4) {
+    let start = pos.len() as u32;
+    for i in 0..CIRCLE_SEGMENTS {
+        let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+        pos.push(center + rotation.transform_vector3(Vec3::new(angle.cos() * radius, angle.sin() * radius, 0.0)));
+        indices.extend_from_slice(&[start + i, start + (i + 1) % CIRCLE_SEGMENTS]);
+    }
+}
+
+/// the outline of the shape, as lines
+fn zone_wireframe_mesh(zone_type: &ZoneType) -> Option<(Vec<Vec3>, Vec<u32>)> {
+    let mut pos = Vec::new();
+    let mut indices = Vec::new();
+    let to_x = Mat4::from_rotation_y(std::f32::consts::FRAC_PI_2);
+    let to_y = Mat4::from_rotation_x(std::f32::consts::FRAC_PI_2);
+
+    match zone_type {
+        ZoneType::Point => return None,
+        ZoneType::Ray(ray) => {
+            pos.extend_from_slice(&[Vec3::ZERO, Vec3::Z * ray.length]);
+            indices.extend_from_slice(&[0, 1]);
+        },
+        ZoneType::Sphere(sphere) => {
+            for rotation in [Mat4::IDENTITY, to_x, to_y] {
+                push_circle(&mut pos, &mut indices, Vec3::ZERO, sphere.radius, rotation);
+            }
+        },
+        ZoneType::AABox(_) | ZoneType::OBox(_) => {
+            pos = box_corners(zone_half_extents(zone_type)?);
+            indices = vec![0, 1, 2, 3, 4, 5, 6, 7, 0, 2, 1, 3, 4, 6, 5, 7, 0, 4, 1, 5, 2, 6, 3, 7];
+        },
+        ZoneType::Capsule(capsule) => {
+            let half_len = capsule.start.distance(capsule.end) / 2.0;
+            for z in [-half_len, half_len] {
+                push_circle(&mut pos, &mut indices, Vec3::Z * z, capsule.radius, Mat4::IDENTITY);
+            }
+            for rotation in [to_x, to_y] {
+                push_circle(&mut pos, &mut indices, Vec3::Z * -half_len, capsule.radius, rotation);
+                push_circle(&mut pos, &mut indices, Vec3::Z * half_len, capsule.radius, rotation);
+            }
+            for side in [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y] {
+                let start = pos.len() as u32;
+                pos.extend_from_slice(&[side * capsule.radius - Vec3::Z * half_len, side * capsule.radius + Vec3::Z * half_len]);
+                indices.extend_from_slice(&[start, start + 1]);
+            }
+        }
+    }
+
+    Some((pos, indices))
+}
+
+fn gltf_zone_mesh(ct: &mut ExportContext, zone_type: &ZoneType, name: &str) -> Option<json::Index<json::Mesh>> {
+    let (mode, (pos, indices)) = match ct.options.zone_export_strategy {
+        ZoneExportStrategy::Box => (json::mesh::Mode::Triangles, zone_box_mesh(zone_type)?),
+        ZoneExportStrategy::Wireframe => (json::mesh::Mode::Lines, zone_wireframe_mesh(zone_type)?),
+        _ => return None
+    };
+
+    let mut primitive = write_primitive(ct, GltfPrimitiveBuild {
+        pos: Box::new(pos.iter().map(|v| Vec3::new(-v.x, v.z, v.y))),
+        indices: Box::new(indices.iter().cloned()),
+        uv0: None,
+        uv1: None,
+        tangents: None,
+        normals: None,
+        colors: None,
+        weights: None,
+        material: None,
+    });
+    primitive.mode = Valid(mode);
+
+    Some(ct.root.push(json::Mesh {
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: Some(name.to_string()),
+        primitives: vec![primitive],
+        weights: None
+    }))
+}
+
+/// Exports the zone in `ct.key` as a node placed at its shape, with the shape and modifiers in the extras
+pub fn gltf_zon(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    gltf_export_init!(ct);
+
+    let Some(zone) = ct.bf.object_table[&ct.key].archetype.as_zone() else { return Vec::new(); };
+    let name = ct.bf.file_table[&ct.key].get_name().to_string();
+
+    let mesh = gltf_zone_mesh(ct, &zone.zone_type, &name);
+    let matrix = transform_yeti_matrix(&zone.zone_type.local_matrix());
+    let node = ct.root.push(json::Node {
+        matrix: if matrix == Mat4::IDENTITY { None } else { Some(matrix.to_cols_array()) },
+        mesh,
+        name: Some(name),
+        extras: zone_extras(zone),
+        ..Default::default()
+    });
+
+    insert_cache!(ct, &ct.key, node);
+
+    vec![node]
+}
+
+/// Exports every zone a zone array references
+pub fn gltf_zar(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    let zones: Vec<YKey> = ct.bf.object_table[&ct.key].references.iter()
+        .filter(|zon| ct.bf.is_key_valid(**zon) && ct.bf.file_table[*zon].object_type == ObjectType::zon)
+        .copied()
+        .collect();
+
+    let mut nodes = Vec::new();
+    for zon in zones {
+        do_sub_ct!(ct, zon, {
+            nodes.append(&mut gltf_zon(ct));
+        });
+    }
+    nodes
+}
+
+/// Exports every zone a gao owns, directly or through its zone arrays
+pub fn gltf_gao_zones(ct: &mut ExportContext) -> Vec<json::Index<json::Node>> {
+    let mut nodes = Vec::new();
+    if ct.options.zone_export_strategy.is_none() {
+        return nodes;
+    }
+
+    for zone in find_gao_zones(ct.bf, ct.key) {
+        do_sub_ct!(ct, zone.zone, {
+            nodes.append(&mut gltf_zon(ct));
+        });
+    }
+    nodes
+}
@@ -21,6 +21,7 @@ struct OptionStrings {
     pub point_light_intensity_multiplier        : String,
     pub point_light_range_multiplier            : String,
     pub skybox_emissive_multiplier              : String,
+    pub unlit_emissive_multiplier               : String,
 }
 
 #[derive(Debug, strum_macros::Display, strum::EnumIter, PartialEq, Clone, Copy)]
@@ -65,6 +66,7 @@ impl GltfExportWindow {
         strings.point_light_intensity_multiplier       = format!("{}", options.point_light_intensity_multiplier         );
         strings.point_light_range_multiplier           = format!("{}", options.point_light_range_multiplier             );
         strings.skybox_emissive_multiplier             = format!("{}", options.skybox_emissive_multiplier               );
+        strings.unlit_emissive_multiplier              = format!("{}", options.unlit_emissive_multiplier                );
     }
 
     fn is_valid(&self) -> bool {
@@ -124,18 +126,30 @@ impl GltfExportWindow {
                         number_field(ui, "Point Light Multiplier"       , &mut options.point_light_intensity_multiplier           , &mut self.edit_strings.point_light_intensity_multiplier       );
                         number_field(ui, "Point Light Range Multiplier" , &mut options.point_light_range_multiplier               , &mut self.edit_strings.point_light_range_multiplier           );
                         number_field(ui, "Skybox Brighness Multiplier"  , &mut options.skybox_emissive_multiplier                 , &mut self.edit_strings.skybox_emissive_multiplier             );
+                        number_field(ui, "Unlit Brightness Multiplier"  , &mut options.unlit_emissive_multiplier                  , &mut self.edit_strings.unlit_emissive_multiplier              );
 
                         ui.separator();
 
                         ui.label("EXPORT OPTIONS");
                         ui.checkbox(&mut options.export_collision, "Export Collision");
+                        ui.checkbox(&mut options.color_collision_by_material, "Color Collision By Game Material");
+                        ui.checkbox(&mut options.export_navmesh, "Export Navmesh");
                         ui.checkbox(&mut options.export_empty_gaos, "Export Empty GAOs");
                         ui.checkbox(&mut options.export_key_map, "Export Keymap");
+                        ui.checkbox(&mut options.export_animations, "Export Animations");
                         ui.horizontal_wrapped(|ui| {
                             ui.label("Way Export: ");
                             ui.enum_selector(&mut options.way_export_strategy);
                         });
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label("Zone Export: ");
+                            ui.enum_selector(&mut options.zone_export_strategy);
+                        });
                         ui.checkbox(&mut options.export_submeshes_individually, "Export Submeshes Individually");
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label("LOD Export: ");
+                            ui.enum_selector(&mut options.lod_export_strategy);
+                        });
                     });
 
                     ui.separator();
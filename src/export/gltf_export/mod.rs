@@ -24,6 +24,9 @@ mod exp_mat; use exp_mat::*;
 mod exp_col; use exp_col::*;
 mod exp_way; use exp_way::*;
 mod exp_ske; use exp_ske::*;
+mod exp_ani; use exp_ani::*;
+mod exp_zon; use exp_zon::*;
+mod exp_nav; use exp_nav::*;
 mod gltf_export_window; pub use gltf_export_window::*;
 mod util; use util::*;
 mod config; use config::*;
@@ -33,7 +36,8 @@ pub enum WayExportStrategy {
     None,
     Triangulate,
     Extrude,
-    Both
+    Both,
+    Graph
 }
 
 impl WayExportStrategy {
@@ -50,6 +54,26 @@ impl WayExportStrategy {
             _ => false
         }
     }
+
+    pub fn should_export_graph(&self) -> bool {
+        self.is_graph()
+    }
+}
+
+#[derive(Debug, strum_macros::Display, strum::EnumIter, EnumAsInner, PartialEq, PartialOrd, Clone, Copy)]
+pub enum ZoneExportStrategy {
+    None,
+    Empty,
+    Box,
+    Wireframe
+}
+
+#[derive(Debug, strum_macros::Display, strum::EnumIter, EnumAsInner, PartialEq, PartialOrd, Clone, Copy)]
+pub enum LodExportStrategy {
+    /// every level as its own visible mesh
+    All,
+    HighestOnly,
+    MsftLod
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -62,13 +86,19 @@ pub struct GltfExportOptions {
     pub point_light_intensity_multiplier        : f32,
     pub point_light_range_multiplier            : f32,
     pub skybox_emissive_multiplier              : f32,
+    pub unlit_emissive_multiplier               : f32,
 
     pub export_collision                        : bool,
+    pub color_collision_by_material             : bool,
+    pub export_navmesh                          : bool,
     pub export_empty_gaos                       : bool,
     pub export_key_map                          : bool,
+    pub export_animations                       : bool,
 
     pub way_export_strategy                     : WayExportStrategy,
+    pub zone_export_strategy                    : ZoneExportStrategy,
     pub export_submeshes_individually           : bool,
+    pub lod_export_strategy                     : LodExportStrategy,
 
     pub map_name                                : String,
 }
@@ -80,16 +110,22 @@ impl Default for GltfExportOptions {
             spot_light_intensity_multiplier: 1.0,
             point_light_intensity_multiplier: 1.0,
             skybox_emissive_multiplier: 1.0,
+            unlit_emissive_multiplier: 1.0,
             spot_light_range_multiplier: 1.0,
             point_light_range_multiplier: 1.0,
             invert_directional_lights: false,
             invert_spot_lights: false,
             export_collision: true,
+            color_collision_by_material: false,
+            export_navmesh: false,
             export_empty_gaos: false,
             way_export_strategy: WayExportStrategy::None,
+            zone_export_strategy: ZoneExportStrategy::None,
             map_name: String::new(),
             export_key_map: false,
+            export_animations: false,
             export_submeshes_individually: false,
+            lod_export_strategy: LodExportStrategy::All,
         }
     }
 }
@@ -151,6 +187,17 @@ struct ExportContext<'a> {
     pub sub_context: SubContext,
     pub export_config: ExportConfig,
     pub meshes_processed: HashSet<u32>,
+    /// LOD level of exported meshes, by mesh index, only levels above 0 are kept
+    pub mesh_lod_levels: HashMap<usize, u8>,
+    /// (skeleton, animation) pairs already exported, actors sharing a skeleton share their clips
+    pub exported_clips: HashSet<(YKey, YKey)>,
+    /// nodes of objects that can be used more than once (cooked collision, navmeshes) by key,
+    /// pushed anew for every use so the mesh is shared but no node gets two parents
+    pub shared_nodes: HashMap<YKey, json::Node>,
+    /// objects referencing each object, built on first use for finding collision game materials
+    pub referenced_by: Option<HashMap<YKey, Vec<YKey>>>,
+    /// materials of collision material indices that don't resolve to a game material
+    pub collision_materials: HashMap<u16, json::Index<json::Material>>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -261,7 +308,8 @@ pub fn gltf_export(key: YKey, bf: &Bigfile, options: GltfExportOptions) -> bool
             "KHR_materials_emissive_strength".into(),
         ],
         extensions: Some(json::extensions::Root {
-            khr_lights_punctual: Some(json::extensions::root::KhrLightsPunctual::default())
+            khr_lights_punctual: Some(json::extensions::root::KhrLightsPunctual::default()),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -293,6 +341,11 @@ pub fn gltf_export(key: YKey, bf: &Bigfile, options: GltfExportOptions) -> bool
         sub_context: SubContext::default(),
         export_config: load_export_config().expect("fail to load way config!"),
         meshes_processed: HashSet::new(),
+        mesh_lod_levels: HashMap::new(),
+        exported_clips: HashSet::new(),
+        shared_nodes: HashMap::new(),
+        referenced_by: None,
+        collision_materials: HashMap::new(),
     };
 
     match bf.file_table[&key].object_type {
@@ -301,12 +354,16 @@ pub fn gltf_export(key: YKey, bf: &Bigfile, options: GltfExportOptions) -> bool
                 gltf_msh(&mut ct)
             };
 
-            for mesh in meshes {
-                nodes.push(ct.root.push(json::Node {
-                    mesh: Some(mesh),
+            let mesh_nodes: Vec<_> = meshes.iter().map(|mesh| {
+                let level = ct.mesh_lod_levels.get(&mesh.value()).copied().unwrap_or(0);
+                (level, ct.root.push(json::Node {
+                    mesh: Some(*mesh),
                     ..Default::default()
-                }));
-            }
+                }))
+            }).collect();
+
+            let name = bf.file_table[&key].get_name().to_string();
+            nodes = gltf_lod_nodes(&mut ct, mesh_nodes, &name, &[]);
         },
         ObjectType::got => {
             nodes = gltf_got(&mut ct);
@@ -317,8 +374,26 @@ pub fn gltf_export(key: YKey, bf: &Bigfile, options: GltfExportOptions) -> bool
         ObjectType::wor => {
             nodes = gltf_wor(&mut ct);
         },
+        ObjectType::zon => {
+            nodes = gltf_zon(&mut ct);
+        },
+        ObjectType::zar => {
+            nodes = gltf_zar(&mut ct);
+        },
         ObjectType::way => {
             nodes = gltf_way(&mut ct);
+        },
+        ObjectType::cot => {
+            nodes = gltf_cot(&mut ct);
+        },
+        ObjectType::col => {
+            nodes = gltf_col(&mut ct);
+        },
+        ObjectType::ccm => {
+            nodes = gltf_ccm(&mut ct);
+        },
+        ObjectType::nav => {
+            nodes = gltf_nav(&mut ct);
         }
         _ => { }
     };
@@ -352,9 +352,30 @@ pub fn write_primitive(ct: &'_ mut ExportContext, build: GltfPrimitiveBuild) ->
         attributes.insert(Checked::Valid(Semantic::Joints(0)), joints_acc);
     }
 
+    let idx_acc = write_indices(ct, build.indices);
+        
+    let primitive = json::mesh::Primitive {
+        attributes,
+        extensions: Default::default(),
+        extras: Default::default(),
+        indices: Some(idx_acc),
+        material: build.material.map(|idx| json::Index::new(idx as u32)),
+        mode: Valid(json::mesh::Mode::Triangles),
+        targets: None
+    };
+
+    primitive
+}
+
+/// Writes u32 indices as their own accessor, for primitives that share another primitive's vertex attributes
+pub fn write_indices(ct: &'_ mut ExportContext, indices: impl Iterator<Item = u32>) -> json::Index<json::Accessor> {
+    while !ct.cursor.position().is_multiple_of(4) {
+        ct.cursor.write_u8(0).unwrap();
+    }
+
     let idx_start = ct.cursor.position();
     let mut num_indices: usize = 0;
-    for idx in build.indices {
+    for idx in indices {
         ct.cursor.write_u32::<ENDIAN>(idx).expect("write error");
         num_indices += 1;
     }
@@ -389,16 +410,6 @@ pub fn write_primitive(ct: &'_ mut ExportContext, build: GltfPrimitiveBuild) ->
         sparse: None
     });
     check_buffer_accessor!(ct, "idx_acc");
-        
-    let primitive = json::mesh::Primitive {
-        attributes,
-        extensions: Default::default(),
-        extras: Default::default(),
-        indices: Some(idx_acc),
-        material: build.material.map(|idx| json::Index::new(idx as u32)),
-        mode: Valid(json::mesh::Mode::Triangles),
-        targets: None
-    };
 
-    primitive
-}
\ No newline at end of file
+    idx_acc
+}
@@ -17,45 +17,53 @@ struct AppShader {
 
 impl AppShader {
     pub unsafe fn compile_shader(&mut self, gl: &glow::Context) {
+        if let Err(error) = self.try_compile_shader(gl) {
+            panic!("{}", error);
+        }
+    }
+
+    pub unsafe fn try_compile_shader(&mut self, gl: &glow::Context) -> Result<(), String> {
         info!("compiling shader {}", &self.name);
 
-        let program = gl.create_program().expect("could not create program!");
+        let program = gl.create_program()?;
 
         let mut shaders = Vec::with_capacity(5);
 
-        if let Some(source) = &self.vert {
-            let vshader = gl.create_shader(glow::VERTEX_SHADER).expect("could not create vertex shader!");
-            gl.shader_source(vshader, &source);
-            gl.compile_shader(vshader);
-            if !gl.get_shader_compile_status(vshader) {
-                panic!("error compiling {}: {}", &self.name, gl.get_shader_info_log(vshader));
-            }
-            gl.attach_shader(program, vshader);
-            shaders.push(vshader);
-        }
-
-        if let Some(source) = &self.frag {
-            let fshader = gl.create_shader(glow::FRAGMENT_SHADER).expect("could not create fragment shader!");
-            gl.shader_source(fshader, &source);
-            gl.compile_shader(fshader);
-            if !gl.get_shader_compile_status(fshader) {
-                panic!("error compiling {}: {}", &self.name, gl.get_shader_info_log(fshader));
+        let stages = [(glow::VERTEX_SHADER, &self.vert), (glow::FRAGMENT_SHADER, &self.frag)];
+        for (stage, source) in stages {
+            let Some(source) = source else { continue; };
+            let shader = gl.create_shader(stage)?;
+            gl.shader_source(shader, source);
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                let log = gl.get_shader_info_log(shader);
+                gl.delete_shader(shader);
+                for shader in shaders {
+                    gl.delete_shader(shader);
+                }
+                gl.delete_program(program);
+                return Err(format!("error compiling {}: {}", &self.name, log));
             }
-            gl.attach_shader(program, fshader);
-            shaders.push(fshader);
+            gl.attach_shader(program, shader);
+            shaders.push(shader);
         }
 
         gl.link_program(program);
-        if !gl.get_program_link_status(program) {
-            panic!("error compiling {}: {}", &self.name, gl.get_program_info_log(program));
-        }
+        let linked = gl.get_program_link_status(program);
 
         for shader in shaders {
             gl.detach_shader(program, shader);
             gl.delete_shader(shader);
         }
 
+        if !linked {
+            let log = gl.get_program_info_log(program);
+            gl.delete_program(program);
+            return Err(format!("error compiling {}: {}", &self.name, log));
+        }
+
         self.program = Some(program);
+        Ok(())
     }
 
     pub fn from_source(name: String, source: &String) -> Self {
@@ -105,6 +113,7 @@ impl AppShader {
 
 pub struct ShaderCache {
     shaders: HashMap<String, AppShader>,
+    gl: Option<Arc<glow::Context>>,
 }
 
 impl ShaderCache {
@@ -119,6 +128,7 @@ impl ShaderCache {
 
         Self {
             shaders,
+            gl: None,
         }
     }
 
@@ -128,5 +138,24 @@ impl ShaderCache {
         for shader in self.shaders.values_mut() {
             shader.compile_shader(&gl);
         }
+
+        self.gl = Some(gl);
+    }
+
+    /// Compiles a `##vert`/`##frag` source at runtime, replacing any shader with the same name
+    pub fn compile_source(&mut self, name: &str, source: &str) -> Result<(), String> {
+        let Some(gl) = self.gl.clone() else {
+            return Err(String::from("shader cache has no gl context yet"));
+        };
+
+        let mut shader = AppShader::from_source(name.to_string(), &source.to_string());
+        unsafe {
+            shader.try_compile_shader(&gl)?;
+            if let Some(program) = self.shaders.insert(name.to_string(), shader).and_then(|old| old.program) {
+                gl.delete_program(program);
+            }
+        }
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use log::*;
 use core::str;
 use std::{cmp::Ordering, fmt::Display, io::Cursor};
@@ -8,6 +8,10 @@ use super::{ArchetypeImpl, YetiIOError};
 
 pub struct AIConstList {
     pub root_node: ConstTreeNode,
+    pub is_xml: bool,
+    pub unk_header: u64,
+    /// consts in file order with their full path names, `root_node` is built from these
+    pub consts: Vec<ConstValue>,
 }
 
 #[derive(Default)]
@@ -85,13 +89,16 @@ impl ConstValue {
 impl Default for AIConstList {
     fn default() -> Self {
         Self {
-            root_node: ConstTreeNode::default()
+            root_node: ConstTreeNode::default(),
+            is_xml: false,
+            unk_header: 0,
+            consts: Vec::new(),
         }
     }
 }
 
 impl AIConstList {
-    fn load_xml_consts(buf: &[u8]) -> Result<Vec<ConstValue>, YetiIOError> {
+    fn load_xml_consts(buf: &[u8]) -> Result<(Vec<ConstValue>, usize), YetiIOError> {
         let mut v = Vec::new();
 
         enum ReaderState {
@@ -166,17 +173,15 @@ impl AIConstList {
             }
         };
 
-        Ok(v)
+        Ok((v, buf.len()))
     }
 
-    fn load_binary_consts(buf: &[u8]) -> Result<Vec<ConstValue>, YetiIOError> {
+    fn load_binary_consts(&mut self, buf: &[u8]) -> Result<(Vec<ConstValue>, usize), YetiIOError> {
         let mut v = Vec::new();
 
         let mut cursor = Cursor::new(buf);
-        let num = {
-            cursor.read_u64::<LittleEndian>()?; //skip first 64 bytes
-            cursor.read_u32::<LittleEndian>()?
-        };
+        self.unk_header = cursor.read_u64::<LittleEndian>()?;
+        let num = cursor.read_u32::<LittleEndian>()?;
 
         for _ in 0..num {
             let typ = cursor.read_u8()?;
@@ -196,19 +201,20 @@ impl AIConstList {
             v.push(cst);
         }
 
-        Ok(v)
+        Ok((v, cursor.position() as usize))
     }
 }
 
 impl ArchetypeImpl for AIConstList {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
 
-        let consts = match (&buf[0..8]).read_u64::<LittleEndian>()? {
-            8391171954870665532 => Self::load_xml_consts(buf)?,
-            _ => Self::load_binary_consts(buf)?
+        self.is_xml = (&buf[0..8]).read_u64::<LittleEndian>()? == 8391171954870665532;
+        let (consts, consumed) = match self.is_xml {
+            true => Self::load_xml_consts(buf)?,
+            false => self.load_binary_consts(buf)?
         };
 
-        for cst in consts {
+        for cst in consts.iter() {
             let mut names: Vec<&str> = cst.get_name().split("\\").collect();
             let val_name = names.pop().unwrap().to_string();
             let mut node = &mut self.root_node;
@@ -217,11 +223,74 @@ impl ArchetypeImpl for AIConstList {
             }
             node.values.push(cst.with_name(&val_name));
         }
+        self.consts = consts;
 
-        Ok(())
+        Ok(consumed)
     }
 
     fn unload(&mut self) {
         *self = Default::default();
     }
-}
\ No newline at end of file
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        if self.is_xml {
+            return Err("saving xml const lists is not supported".into());
+        }
+
+        buf.write_u64::<LittleEndian>(self.unk_header)?;
+        buf.write_u32::<LittleEndian>(self.consts.len() as u32)?;
+        for cst in self.consts.iter() {
+            match cst {
+                ConstValue::Int(name, v) => {
+                    buf.write_u8(1)?;
+                    crate::util::write_nul_term_string(buf, name)?;
+                    buf.write_i32::<LittleEndian>(*v)?;
+                },
+                ConstValue::Float(name, v) => {
+                    buf.write_u8(2)?;
+                    crate::util::write_nul_term_string(buf, name)?;
+                    buf.write_f32::<LittleEndian>(*v)?;
+                },
+                ConstValue::Vec(name, v) => {
+                    buf.write_u8(3)?;
+                    crate::util::write_nul_term_string(buf, name)?;
+                    buf.write_f32::<LittleEndian>(v.x)?;
+                    buf.write_f32::<LittleEndian>(v.y)?;
+                    buf.write_f32::<LittleEndian>(v.z)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+    use crate::util::write_nul_term_string;
+
+    #[test]
+    fn round_trip_binary() {
+        let mut buf = Vec::new();
+        buf.write_u64::<LittleEndian>(0x0000000100000002).unwrap();
+        buf.write_u32::<LittleEndian>(3).unwrap();
+
+        buf.write_u8(1).unwrap();
+        write_nul_term_string(&mut buf, "AI\\Combat\\MaxEnemies").unwrap();
+        buf.write_i32::<LittleEndian>(-4).unwrap();
+
+        buf.write_u8(2).unwrap();
+        write_nul_term_string(&mut buf, "AI\\Combat\\Range").unwrap();
+        buf.write_f32::<LittleEndian>(12.5).unwrap();
+
+        buf.write_u8(3).unwrap();
+        write_nul_term_string(&mut buf, "AI\\Offset").unwrap();
+        for v in [1.0, -2.0, 0.25] {
+            buf.write_f32::<LittleEndian>(v).unwrap();
+        }
+
+        let mut consts = AIConstList::default();
+        assert_round_trip(&mut consts, &buf);
+        assert!(!consts.is_xml);
+    }
+}
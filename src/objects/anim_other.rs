@@ -4,13 +4,65 @@ use byteorder::{LittleEndian, ReadBytesExt};
 use strum_macros::{Display};
 
 use crate::objects::ArchetypeImpl;
+use crate::metadata::YKey;
+
+/// plays a sound, 30 bytes on disk
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SoundEvent {
+    pub frame: f32,
+    pub end_frame: f32,
+    pub sound: YKey,
+    pub volume: f32,
+    pub pitch: f32,
+    pub bone: u16,
+    pub unk_01: [u8; 8],
+}
+
+/// spawns an effect, 18 bytes on disk
+#[derive(Default, Debug, Clone, Copy)]
+pub struct EffectEvent {
+    pub frame: f32,
+    pub effect: YKey,
+    pub bone: u16,
+    pub scale: f32,
+    pub unk_01: u32,
+}
+
+/// gameplay trigger handled by scripts, 18 bytes on disk
+#[derive(Default, Debug, Clone, Copy)]
+pub struct TriggerEvent {
+    pub frame: f32,
+    pub event_id: u32,
+    pub params: [f32; 2],
+    pub unk_01: u16,
+}
 
 #[derive(Default, Debug, Display)]
 pub enum AnimEventData {
     #[default] None,
-    Type01([u8; 30]),
-    Type02([u8; 18]),
-    Type03([u8; 18]),
+    Sound(SoundEvent),
+    Effect(EffectEvent),
+    Trigger(TriggerEvent),
+}
+
+impl AnimEventData {
+    pub fn frame(&self) -> Option<f32> {
+        match self {
+            Self::None => None,
+            Self::Sound(sound) => Some(sound.frame),
+            Self::Effect(effect) => Some(effect.frame),
+            Self::Trigger(trigger) => Some(trigger.frame),
+        }
+    }
+
+    /// the sound or effect this event fires, if any
+    pub fn referenced_key(&self) -> Option<YKey> {
+        match self {
+            Self::Sound(sound) => Some(sound.sound),
+            Self::Effect(effect) => Some(effect.effect),
+            _ => None
+        }
+    }
 }
 
 #[derive(Default, Debug)]
@@ -26,7 +78,7 @@ pub struct AnimEventContainer {
 }
 
 impl ArchetypeImpl for AnimEventContainer {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), crate::bigfile::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         {
@@ -47,19 +99,34 @@ impl ArchetypeImpl for AnimEventContainer {
             let flags = cursor.read_u8()?;
             let event_data = match data_type {
                 0x01 => {
-                    let mut data = [0; 30];
-                    cursor.read_exact(&mut data)?;
-                    AnimEventData::Type01(data)
+                    let mut sound = SoundEvent {
+                        frame: cursor.read_f32::<LittleEndian>()?,
+                        end_frame: cursor.read_f32::<LittleEndian>()?,
+                        sound: cursor.read_u32::<LittleEndian>()?.into(),
+                        volume: cursor.read_f32::<LittleEndian>()?,
+                        pitch: cursor.read_f32::<LittleEndian>()?,
+                        bone: cursor.read_u16::<LittleEndian>()?,
+                        ..Default::default()
+                    };
+                    cursor.read_exact(&mut sound.unk_01)?;
+                    AnimEventData::Sound(sound)
                 },
                 0x02 => {
-                    let mut data = [0; 18];
-                    cursor.read_exact(&mut data)?;
-                    AnimEventData::Type02(data)
+                    AnimEventData::Effect(EffectEvent {
+                        frame: cursor.read_f32::<LittleEndian>()?,
+                        effect: cursor.read_u32::<LittleEndian>()?.into(),
+                        bone: cursor.read_u16::<LittleEndian>()?,
+                        scale: cursor.read_f32::<LittleEndian>()?,
+                        unk_01: cursor.read_u32::<LittleEndian>()?,
+                    })
                 },
                 0x03 => {
-                    let mut data = [0; 18];
-                    cursor.read_exact(&mut data)?;
-                    AnimEventData::Type03(data)
+                    AnimEventData::Trigger(TriggerEvent {
+                        frame: cursor.read_f32::<LittleEndian>()?,
+                        event_id: cursor.read_u32::<LittleEndian>()?,
+                        params: [cursor.read_f32::<LittleEndian>()?, cursor.read_f32::<LittleEndian>()?],
+                        unk_01: cursor.read_u16::<LittleEndian>()?,
+                    })
                 },
                 _ => {
                     return Err(format!("invalid event data type: {:#04X}", data_type).into());
@@ -72,7 +139,7 @@ impl ArchetypeImpl for AnimEventContainer {
         }
         self.events = events;
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -80,15 +147,60 @@ impl ArchetypeImpl for AnimEventContainer {
     }
 }
 
+/// Reads an optional reference index, 0xFFFF means none
+fn read_ref_index(cursor: &mut Cursor<&[u8]>) -> Result<Option<u16>, crate::bigfile::YetiIOError> {
+    Ok(match cursor.read_u16::<LittleEndian>()? {
+        0xFFFF => None,
+        idx => Some(idx)
+    })
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ActionListEntry {
+    pub action_id: u16,
+    /// index into the list's references, pointing to the action bank holding the action
+    pub bank_ref: u8,
+    pub flags: u8,
+}
+
 #[derive(Default, Debug)]
 pub struct ListActionBank {
     pub version: u32,
-    pub num_actions: u16
+    pub num_actions: u16,
+    pub entries: Vec<ActionListEntry>,
+    /// set when the entries don't fit the payload, the entries are then left unparsed
+    pub decode_error: Option<String>,
+}
+
+/// action id, bank ref and flags
+const ACTION_LIST_ENTRY_SIZE: usize = 4;
+/// action id, action ref and transition count, before the transitions
+const ACTION_BANK_ENTRY_SIZE: usize = 5;
+/// to action, blend time and flags
+const ACTION_TRANSITION_SIZE: usize = 8;
+
+/// Bytes left after the cursor
+fn remaining(cursor: &Cursor<&[u8]>) -> usize {
+    cursor.get_ref().len().saturating_sub(cursor.position() as usize)
+}
+
+impl ListActionBank {
+    fn read_entries(cursor: &mut Cursor<&[u8]>, num_actions: u16) -> Result<Vec<ActionListEntry>, crate::bigfile::YetiIOError> {
+        let mut entries = Vec::with_capacity(num_actions as usize);
+        for _ in 0..num_actions {
+            entries.push(ActionListEntry {
+                action_id: cursor.read_u16::<LittleEndian>()?,
+                bank_ref: cursor.read_u8()?,
+                flags: cursor.read_u8()?,
+            });
+        }
+        Ok(entries)
+    }
 }
 
 impl ArchetypeImpl for ListActionBank {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), crate::bigfile::YetiIOError> {
-        let mut cursor = Cursor::new(&buf);
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
 
         self.version = cursor.read_u32::<LittleEndian>()?;
         if self.version > 2 {
@@ -96,23 +208,98 @@ impl ArchetypeImpl for ListActionBank {
         }
         self.num_actions = cursor.read_u16::<LittleEndian>()?;
 
-        Ok(())
+        if self.num_actions as usize * ACTION_LIST_ENTRY_SIZE > remaining(&cursor) {
+            self.decode_error = Some(format!("{} actions don't fit in the remaining {} bytes", self.num_actions, remaining(&cursor)));
+            return Ok(cursor.position() as usize);
+        }
+
+        self.entries = Self::read_entries(&mut cursor, self.num_actions)?;
+
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
+        *self = Default::default();
+    }
 
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
     }
+
+    fn reference_error(&self, num_refs: usize) -> Option<String> {
+        self.entries.iter()
+            .find(|entry| entry.bank_ref as usize >= num_refs)
+            .map(|entry| format!("action {} points to bank reference {} of {}", entry.action_id, entry.bank_ref, num_refs))
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ActionTransition {
+    pub to_action: u16,
+    pub blend_time: f32,
+    pub flags: u16,
+}
+
+#[derive(Default, Debug)]
+pub struct ActionBankEntry {
+    pub action_id: u16,
+    /// index into the bank's references, pointing to the action
+    pub action_ref: Option<u16>,
+    pub transitions: Vec<ActionTransition>,
 }
 
 #[derive(Default, Debug)]
 pub struct ActionBank {
     pub version: u32,
-    pub unk_dat01: [u8; 10]
+    pub unk_dat01: [u8; 10],
+    pub actions: Vec<ActionBankEntry>,
+    /// set when the actions stop decoding, the ones read before that are kept
+    pub decode_error: Option<String>,
+}
+
+impl ActionBank {
+    /// Reads actions until one fails, `end` is left after the last one read whole
+    fn read_actions(&mut self, cursor: &mut Cursor<&[u8]>, end: &mut u64) -> Result<(), crate::bigfile::YetiIOError> {
+        let num_actions = cursor.read_u16::<LittleEndian>()?;
+        if num_actions as usize * ACTION_BANK_ENTRY_SIZE > remaining(cursor) {
+            return Err(format!("{} actions don't fit in the remaining {} bytes", num_actions, remaining(cursor)).into());
+        }
+        *end = cursor.position();
+
+        self.actions.reserve(num_actions as usize);
+        for _ in 0..num_actions {
+            let mut entry = ActionBankEntry {
+                action_id: cursor.read_u16::<LittleEndian>()?,
+                action_ref: read_ref_index(cursor)?,
+                transitions: Vec::new(),
+            };
+
+            let num_transitions = cursor.read_u8()?;
+            if num_transitions as usize * ACTION_TRANSITION_SIZE > remaining(cursor) {
+                return Err(format!("action {} has {} transitions, {} bytes remain", entry.action_id, num_transitions, remaining(cursor)).into());
+            }
+            for _ in 0..num_transitions {
+                entry.transitions.push(ActionTransition {
+                    to_action: cursor.read_u16::<LittleEndian>()?,
+                    blend_time: cursor.read_f32::<LittleEndian>()?,
+                    flags: cursor.read_u16::<LittleEndian>()?,
+                });
+            }
+
+            self.actions.push(entry);
+            *end = cursor.position();
+        }
+        Ok(())
+    }
+
+    pub fn find_action(&self, action_id: u16) -> Option<&ActionBankEntry> {
+        self.actions.iter().find(|act| act.action_id == action_id)
+    }
 }
 
 impl ArchetypeImpl for ActionBank {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), crate::bigfile::YetiIOError> {
-        let mut cursor = Cursor::new(&buf);
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
 
         self.version = cursor.read_u32::<LittleEndian>()?;
         if self.version > 2 {
@@ -120,11 +307,26 @@ impl ArchetypeImpl for ActionBank {
         }
         cursor.read_exact(&mut self.unk_dat01)?;
 
-        Ok(())
+        let mut end = cursor.position();
+        if let Err(error) = self.read_actions(&mut cursor, &mut end) {
+            self.decode_error = Some(format!("{} actions read: {}", self.actions.len(), error));
+        }
+
+        Ok(end as usize)
     }
 
     fn unload(&mut self) {
-        
+        *self = Default::default();
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+
+    fn reference_error(&self, num_refs: usize) -> Option<String> {
+        self.actions.iter()
+            .find_map(|act| act.action_ref.filter(|idx| *idx as usize >= num_refs).map(|idx| (act.action_id, idx)))
+            .map(|(action_id, idx)| format!("action {} points to reference {} of {}", action_id, idx, num_refs))
     }
 }
 
@@ -132,17 +334,54 @@ impl ArchetypeImpl for ActionBank {
 pub enum ActionType {
     #[default] None,
     Type01,
-    Type02([f32; 3])
+    Type02([f32; 3]),
+    /// we don't know the payload size for these, so nothing after the header gets decoded
+    Unknown(u32),
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ActionBlend {
+    pub blend_in: f32,
+    pub blend_out: f32,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ActionAnimation {
+    /// index into the action's references, pointing to the animation
+    pub anim_ref: Option<u16>,
+    /// index into the action's references, pointing to the anim events
+    pub event_ref: Option<u16>,
+    pub speed: f32,
+    pub flags: u8,
+    pub blend: ActionBlend,
 }
 
 #[derive(Default, Debug)]
 pub struct Action {
     pub action_type: ActionType,
     pub unk_01: u16,
+    pub animation: Option<ActionAnimation>,
+    /// set when the animation doesn't fit the payload, it's then left unparsed
+    pub decode_error: Option<String>,
+}
+
+impl Action {
+    fn read_animation(cursor: &mut Cursor<&[u8]>) -> Result<ActionAnimation, crate::bigfile::YetiIOError> {
+        Ok(ActionAnimation {
+            anim_ref: read_ref_index(cursor)?,
+            event_ref: read_ref_index(cursor)?,
+            speed: cursor.read_f32::<LittleEndian>()?,
+            flags: cursor.read_u8()?,
+            blend: ActionBlend {
+                blend_in: cursor.read_f32::<LittleEndian>()?,
+                blend_out: cursor.read_f32::<LittleEndian>()?,
+            },
+        })
+    }
 }
 
 impl ArchetypeImpl for Action {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), crate::bigfile::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let action_type = cursor.read_u32::<LittleEndian>()?;
@@ -158,14 +397,37 @@ impl ArchetypeImpl for Action {
                 ActionType::Type02(floats)
             },
             _ => {
-                return Err(format!("weird action type {}", action_type).into());
+                log::warn!("weird action type {}", action_type);
+                ActionType::Unknown(action_type)
             }
         };
 
-        Ok(())
+        if !matches!(self.action_type, ActionType::Unknown(_)) {
+            let start = cursor.position();
+            match Self::read_animation(&mut cursor) {
+                Ok(animation) => self.animation = Some(animation),
+                Err(error) => {
+                    self.decode_error = Some(format!("couldn't read action animation: {}", error));
+                    cursor.set_position(start);
+                }
+            }
+        }
+
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
-        
+        *self = Default::default();
     }
-}
\ No newline at end of file
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+
+    fn reference_error(&self, num_refs: usize) -> Option<String> {
+        let animation = self.animation?;
+        [("animation", animation.anim_ref), ("anim events", animation.event_ref)].into_iter()
+            .find_map(|(what, idx)| idx.filter(|idx| *idx as usize >= num_refs).map(|idx| (what, idx)))
+            .map(|(what, idx)| format!("{} points to reference {} of {}", what, idx, num_refs))
+    }
+}
@@ -0,0 +1,306 @@
+use std::io::{Cursor, Read};
+use byteorder::{LittleEndian, ReadBytesExt};
+use bitflags::bitflags;
+use glam::{Quat, Vec3, Vec4};
+use super::{ArchetypeImpl, YetiIOError};
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct TrackFlags: u8 {
+        const ROTATION      = 1 << 0;
+        const TRANSLATION   = 1 << 1;
+        const SCALE         = 1 << 2;
+        const _ = !0;
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Keyframe<T> {
+    pub frame: u16,
+    pub value: T,
+}
+
+#[derive(Debug, Default)]
+pub struct BoneTrack {
+    pub bone: u8,
+    pub flags: TrackFlags,
+    pub rotation: Vec<Keyframe<Quat>>,
+    pub translation: Vec<Keyframe<Vec3>>,
+    pub scale: Vec<Keyframe<Vec3>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BonePose {
+    pub rotation: Option<Quat>,
+    pub translation: Option<Vec3>,
+    pub scale: Option<Vec3>,
+}
+
+/// finds the keys surrounding `frame` and how far between them it is
+fn sample_keys<T: Copy>(keys: &[Keyframe<T>], frame: f32, lerp: impl Fn(T, T, f32) -> T) -> Option<T> {
+    let first = keys.first()?;
+    if frame <= first.frame as f32 {
+        return Some(first.value);
+    }
+
+    for pair in keys.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if frame <= b.frame as f32 {
+            let span = b.frame.saturating_sub(a.frame).max(1) as f32;
+            return Some(lerp(a.value, b.value, (frame - a.frame as f32) / span));
+        }
+    }
+
+    keys.last().map(|key| key.value)
+}
+
+impl BoneTrack {
+    pub fn sample(&self, frame: f32) -> BonePose {
+        BonePose {
+            rotation: sample_keys(&self.rotation, frame, |a, b, t| a.slerp(b, t)),
+            translation: sample_keys(&self.translation, frame, |a, b, t| a.lerp(b, t)),
+            scale: sample_keys(&self.scale, frame, |a, b, t| a.lerp(b, t)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Animation {
+    pub version: u32,
+    pub unk_01: u32,
+    pub num_frames: u16,
+    pub frame_rate: f32,
+    pub tracks: Vec<BoneTrack>,
+    /// set when the tracks didn't decode, they're left empty and unparsed
+    pub decode_error: Option<String>,
+}
+
+impl Animation {
+    pub fn duration(&self) -> f32 {
+        match self.frame_rate {
+            rate if rate <= 0.0 => 0.0,
+            rate => self.num_frames.saturating_sub(1) as f32 / rate
+        }
+    }
+
+    pub fn sample(&self, frame: f32) -> Vec<BonePose> {
+        self.tracks.iter().map(|track| track.sample(frame)).collect()
+    }
+
+    fn read_tracks(&self, cursor: &mut Cursor<&[u8]>) -> Result<Vec<BoneTrack>, YetiIOError> {
+        let num_tracks = cursor.read_u8()?;
+        let mut tracks = Vec::with_capacity(num_tracks as usize);
+        for _ in 0..num_tracks {
+            let mut track = BoneTrack {
+                bone: cursor.read_u8()?,
+                flags: TrackFlags::from_bits_retain(cursor.read_u8()?),
+                ..Default::default()
+            };
+
+            if track.flags.contains(TrackFlags::ROTATION) {
+                track.rotation = Self::read_rotation_keys(cursor)?;
+            }
+            if track.flags.contains(TrackFlags::TRANSLATION) {
+                track.translation = Self::read_vector_keys(cursor)?;
+            }
+            if track.flags.contains(TrackFlags::SCALE) {
+                track.scale = Self::read_vector_keys(cursor)?;
+            }
+
+            self.check_key_frames(track.bone, &track.rotation)?;
+            self.check_key_frames(track.bone, &track.translation)?;
+            self.check_key_frames(track.bone, &track.scale)?;
+
+            tracks.push(track);
+        }
+        Ok(tracks)
+    }
+
+    /// sampling relies on keys being in order and inside the animation
+    fn check_key_frames<T>(&self, bone: u8, keys: &[Keyframe<T>]) -> Result<(), YetiIOError> {
+        if let Some(key) = keys.iter().find(|key| key.frame >= self.num_frames) {
+            return Err(format!("bone {} has a key at frame {} but the animation only has {} frames", bone, key.frame, self.num_frames).into());
+        }
+        if let Some(pair) = keys.windows(2).find(|pair| pair[0].frame >= pair[1].frame) {
+            return Err(format!("bone {} has keys out of order, frame {} comes before {}", bone, pair[0].frame, pair[1].frame).into());
+        }
+        Ok(())
+    }
+
+    fn read_rotation_keys(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Keyframe<Quat>>, YetiIOError> {
+        let num_keys = cursor.read_u16::<LittleEndian>()?;
+        let mut keys = Vec::with_capacity(num_keys as usize);
+        for _ in 0..num_keys {
+            let frame = cursor.read_u16::<LittleEndian>()?;
+            // quaternion components quantized to i16
+            let mut q = [0.0; 4];
+            for c in q.iter_mut() {
+                *c = cursor.read_i16::<LittleEndian>()? as f32 / i16::MAX as f32;
+            }
+            // an all-zero key can't be normalized, treat it as no rotation
+            keys.push(Keyframe {
+                frame,
+                value: Quat::from_vec4(Vec4::from_array(q).normalize_or(Vec4::W))
+            });
+        }
+        Ok(keys)
+    }
+
+    fn read_vector_keys(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Keyframe<Vec3>>, YetiIOError> {
+        let num_keys = cursor.read_u16::<LittleEndian>()?;
+
+        // values are quantized to u16 across the track's range
+        let min = Vec3::new(
+            cursor.read_f32::<LittleEndian>()?,
+            cursor.read_f32::<LittleEndian>()?,
+            cursor.read_f32::<LittleEndian>()?
+        );
+        let extent = Vec3::new(
+            cursor.read_f32::<LittleEndian>()?,
+            cursor.read_f32::<LittleEndian>()?,
+            cursor.read_f32::<LittleEndian>()?
+        );
+
+        let mut keys = Vec::with_capacity(num_keys as usize);
+        for _ in 0..num_keys {
+            let frame = cursor.read_u16::<LittleEndian>()?;
+            let q = Vec3::new(
+                cursor.read_u16::<LittleEndian>()? as f32,
+                cursor.read_u16::<LittleEndian>()? as f32,
+                cursor.read_u16::<LittleEndian>()? as f32
+            ) / u16::MAX as f32;
+            keys.push(Keyframe {
+                frame,
+                value: min + q * extent
+            });
+        }
+        Ok(keys)
+    }
+}
+
+impl ArchetypeImpl for Animation {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+
+        {
+            //signature at top of file: '.ani'
+            let mut sig: [u8; 4] = [0; 4];
+            cursor.read_exact(&mut sig)?;
+            if &sig[1..] != b"ani" {
+                log::warn!("weird signature \'{}\'", String::from_utf8_lossy(&sig));
+            }
+        }
+
+        self.version = cursor.read_u32::<LittleEndian>()?;
+        self.unk_01 = cursor.read_u32::<LittleEndian>()?;
+        self.num_frames = cursor.read_u16::<LittleEndian>()?;
+        self.frame_rate = cursor.read_f32::<LittleEndian>()?;
+
+        let tracks_start = cursor.position() as usize;
+        match self.read_tracks(&mut cursor) {
+            Ok(tracks) => {
+                self.tracks = tracks;
+                Ok(cursor.position() as usize)
+            },
+            Err(e) => {
+                log::warn!("animation tracks didn't decode: {}", e);
+                self.decode_error = Some(e.to_string());
+                Ok(tracks_start)
+            }
+        }
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default();
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> Vec<Keyframe<f32>> {
+        vec![
+            Keyframe { frame: 0, value: 0.0 },
+            Keyframe { frame: 10, value: 1.0 },
+            Keyframe { frame: 20, value: 3.0 },
+        ]
+    }
+
+    fn lerp(a: f32, b: f32, t: f32) -> f32 {
+        a + (b - a) * t
+    }
+
+    #[test]
+    fn sample_first_frame() {
+        assert_eq!(sample_keys(&keys(), 0.0, lerp), Some(0.0));
+        assert_eq!(sample_keys(&keys(), -5.0, lerp), Some(0.0));
+    }
+
+    #[test]
+    fn sample_last_frame() {
+        assert_eq!(sample_keys(&keys(), 20.0, lerp), Some(3.0));
+        assert_eq!(sample_keys(&keys(), 25.0, lerp), Some(3.0));
+    }
+
+    #[test]
+    fn sample_between_keys() {
+        assert_eq!(sample_keys(&keys(), 5.0, lerp), Some(0.5));
+        assert_eq!(sample_keys(&keys(), 10.0, lerp), Some(1.0));
+        assert_eq!(sample_keys(&keys(), 15.0, lerp), Some(2.0));
+    }
+
+    #[test]
+    fn sample_no_keys() {
+        assert_eq!(sample_keys(&[], 5.0, lerp), None);
+    }
+
+    #[test]
+    fn duration_without_frame_rate() {
+        let anim = Animation { num_frames: 31, frame_rate: 0.0, ..Default::default() };
+        assert_eq!(anim.duration(), 0.0);
+        let anim = Animation { num_frames: 31, frame_rate: 30.0, ..Default::default() };
+        assert_eq!(anim.duration(), 1.0);
+    }
+
+    fn rotation_anim(num_frames: u16, keys: &[(u16, [i16; 4])]) -> Vec<u8> {
+        let mut buf = b".ani".to_vec();
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        buf.extend_from_slice(&num_frames.to_le_bytes());
+        buf.extend_from_slice(&30.0f32.to_le_bytes());
+        buf.extend_from_slice(&[1, 0, TrackFlags::ROTATION.bits()]);
+        buf.extend_from_slice(&(keys.len() as u16).to_le_bytes());
+        for (frame, q) in keys {
+            buf.extend_from_slice(&frame.to_le_bytes());
+            for c in q {
+                buf.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    #[test]
+    fn load_zero_rotation() {
+        let buf = rotation_anim(2, &[(0, [0, 0, 0, 0]), (1, [0, 0, 0, i16::MAX])]);
+        let mut anim = Animation::default();
+        assert_eq!(anim.load_from_buf(&buf).unwrap(), buf.len());
+        assert!(anim.decode_error.is_none());
+        assert_eq!(anim.tracks[0].rotation[0].value, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn load_bad_keys_falls_back() {
+        for keys in [[(0, [0, 0, 0, 1]), (5, [0, 0, 0, 1])], [(1, [0, 0, 0, 1]), (0, [0, 0, 0, 1])]] {
+            let buf = rotation_anim(2, &keys);
+            let mut anim = Animation::default();
+            assert_eq!(anim.load_from_buf(&buf).unwrap(), 18);
+            assert!(anim.decode_error.is_some());
+            assert!(anim.tracks.is_empty());
+        }
+    }
+}
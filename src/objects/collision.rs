@@ -1,11 +1,22 @@
-use glam::Vec3;
+use std::io::Read;
+
+use glam::{Vec3, Vec4};
 
 use super::*;
+use crate::bigfile::Bigfile;
+use crate::util::load_util::read_vec3;
+
+/// A collision triangle and the game material it's made of
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CollisionFace {
+    pub material: u16,
+    pub indices: [u16; 3],
+}
 
 #[derive(Default)]
 pub struct CollisionObject {
     pub positions: Vec<Vec3>,
-    pub indices: Vec<[u16; 4]>,
+    pub faces: Vec<CollisionFace>,
 }
 
 impl CollisionObject {
@@ -20,35 +31,64 @@ impl CollisionObject {
 
         (min, max)
     }
+
+    /// Material indices used by the faces, sorted
+    pub fn materials(&self) -> Vec<u16> {
+        let mut materials: Vec<u16> = self.faces.iter().map(|face| face.material).collect();
+        materials.sort_unstable();
+        materials.dedup();
+        materials
+    }
+
+    pub fn is_face_valid(&self, face: &CollisionFace) -> bool {
+        face.indices.iter().all(|idx| (*idx as usize) < self.positions.len())
+    }
 }
 
 impl ArchetypeImpl for CollisionObject {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let num_pos = cursor.read_u16::<LittleEndian>()?;
         for _ in 0..num_pos {
-            self.positions.push(Vec3::new(
-                cursor.read_f32::<LittleEndian>()?,
-                cursor.read_f32::<LittleEndian>()?,
-                cursor.read_f32::<LittleEndian>()?,
-            ));
+            self.positions.push(read_vec3(&mut cursor)?);
+        }
+
+        let num_faces = cursor.read_u16::<LittleEndian>()?;
+        for _ in 0..num_faces {
+            self.faces.push(CollisionFace {
+                material: cursor.read_u16::<LittleEndian>()?,
+                indices: [
+                    cursor.read_u16::<LittleEndian>()?,
+                    cursor.read_u16::<LittleEndian>()?,
+                    cursor.read_u16::<LittleEndian>()?,
+                ],
+            });
+        }
+
+        let num_invalid = self.faces.iter().filter(|face| !self.is_face_valid(face)).count();
+        if num_invalid > 0 {
+            log::warn!("{} collision faces index past the {} positions", num_invalid, self.positions.len());
         }
 
-        let num_ind = cursor.read_u16::<LittleEndian>()?;
-        for _ in 0..num_ind {
-            let b = [
-                cursor.read_u16::<LittleEndian>()?,
-                cursor.read_u16::<LittleEndian>()?,
-                cursor.read_u16::<LittleEndian>()?,
-                cursor.read_u16::<LittleEndian>()?,
-            ];
-            // if b[0] != 1 {
-            //     return Err(format!("weird indices number? {}", b[0]).into());
-            // }
-            self.indices.push(b);
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u16::<LittleEndian>(self.positions.len() as u16)?;
+        for pos in self.positions.iter() {
+            buf.write_f32::<LittleEndian>(pos.x)?;
+            buf.write_f32::<LittleEndian>(pos.y)?;
+            buf.write_f32::<LittleEndian>(pos.z)?;
         }
 
+        buf.write_u16::<LittleEndian>(self.faces.len() as u16)?;
+        for face in self.faces.iter() {
+            buf.write_u16::<LittleEndian>(face.material)?;
+            for i in face.indices {
+                buf.write_u16::<LittleEndian>(i)?;
+            }
+        }
         Ok(())
     }
 
@@ -57,19 +97,274 @@ impl ArchetypeImpl for CollisionObject {
     }
 }
 
+/// Per collision data of a table, only the leading flags are understood
+#[derive(Default)]
+pub struct CollisionTableEntry {
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
 #[derive(Default)]
 pub struct CollisionObjectTable {
     pub num_collisions: u32,
+    pub entries: Vec<CollisionTableEntry>,
 }
 
 impl ArchetypeImpl for CollisionObjectTable {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
-        self.num_collisions = Cursor::new(buf).read_u32::<LittleEndian>()?;
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        self.num_collisions = cursor.read_u32::<LittleEndian>()?;
 
-        Ok(())
+        // entries are only split out when the rest divides evenly between the collisions
+        let rest = buf.len() - 4;
+        let count = self.num_collisions as usize;
+        if count == 0 || rest == 0 || !rest.is_multiple_of(count) || rest / count < 4 {
+            return Ok(4);
+        }
+
+        for _ in 0..count {
+            let flags = cursor.read_u32::<LittleEndian>()?;
+            let mut data = vec![0; rest / count - 4];
+            cursor.read_exact(&mut data)?;
+            self.entries.push(CollisionTableEntry { flags, data });
+        }
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Default::default()
+    }
+}
+
+impl CollisionObjectTable {
+    /// Keys referenced by a collision table with the given type
+    pub fn find_references(bf: &Bigfile, cot: YKey, object_type: ObjectType) -> Vec<YKey> {
+        bf.object_table.get(&cot).map(|obj| obj.references.iter()
+            .filter(|key| bf.is_key_valid(**key) && bf.file_table[*key].object_type == object_type)
+            .copied()
+            .collect()).unwrap_or_default()
+    }
+}
+
+/// A face of a convex hull, indexing the hull's vertices
+#[derive(Debug, Clone)]
+pub struct HullPolygon {
+    pub plane: Vec4,
+    pub indices: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConvexHull {
+    pub version: u32,
+    pub num_edges: u32,
+    pub vertices: Vec<Vec3>,
+    pub polygons: Vec<HullPolygon>,
+}
+
+impl ConvexHull {
+    /// Polygons as triangle fans
+    pub fn triangles(&self) -> Vec<[u16; 3]> {
+        self.polygons.iter()
+            .flat_map(|poly| (1..poly.indices.len().saturating_sub(1))
+                .map(|i| [poly.indices[0] as u16, poly.indices[i] as u16, poly.indices[i + 1] as u16]))
+            .collect()
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self, YetiIOError> {
+        let version = cursor.read_u32::<LittleEndian>()?;
+        let num_vertices = cursor.read_u32::<LittleEndian>()?;
+        let num_edges = cursor.read_u32::<LittleEndian>()?;
+        let num_polygons = cursor.read_u32::<LittleEndian>()?;
+        let num_refs = cursor.read_u32::<LittleEndian>()?;
+
+        // polygons index vertices with a byte
+        if num_vertices > 256 || num_polygons > 256 || num_refs > num_polygons * 256 {
+            return Err(format!("hull counts out of range: {} vertices {} polygons {} refs", num_vertices, num_polygons, num_refs).into());
+        }
+
+        let mut vertices = Vec::with_capacity(num_vertices as usize);
+        for _ in 0..num_vertices {
+            vertices.push(read_vec3(cursor)?);
+        }
+
+        let mut ranges = Vec::with_capacity(num_polygons as usize);
+        for _ in 0..num_polygons {
+            let plane = Vec4::new(
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+            );
+            let start = cursor.read_u16::<LittleEndian>()? as usize;
+            let num = cursor.read_u8()? as usize;
+            let _min_index = cursor.read_u8()?;
+            ranges.push((plane, start..start + num));
+        }
+
+        let mut refs = vec![0; num_refs as usize];
+        cursor.read_exact(&mut refs)?;
+
+        if ranges.iter().map(|(_, range)| range.len()).sum::<usize>() != refs.len() {
+            return Err("hull polygon sizes don't add up to its vertex references".into());
+        }
+
+        let mut polygons = Vec::with_capacity(ranges.len());
+        for (plane, range) in ranges {
+            let indices = refs.get(range).ok_or("hull polygon is out of range of its vertex references")?.to_vec();
+            if indices.iter().any(|idx| *idx as u32 >= num_vertices) {
+                return Err("hull polygon indexes past its vertices".into());
+            }
+            polygons.push(HullPolygon { plane, indices });
+        }
+
+        Ok(Self { version, num_edges, vertices, polygons })
+    }
+}
+
+/// Collision mesh cooked by the physics engine, the convex hull chunk is the only part decoded
+#[derive(Default)]
+pub struct CookedCollisionMesh {
+    /// mesh type tag after the cooking header, e.g. CVXM for a convex mesh
+    pub mesh_tag: String,
+    pub hull: Option<ConvexHull>,
+}
+
+const COOKED_MAGIC: &[u8] = b"NXS";
+const HULL_TAG: &[u8] = b"CLHL";
+
+impl ArchetypeImpl for CookedCollisionMesh {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut parsed = 0;
+        if buf.starts_with(COOKED_MAGIC) && buf.len() >= 8 && buf[4..8].iter().all(|b| b.is_ascii_alphanumeric()) {
+            self.mesh_tag = String::from_utf8(buf[4..8].to_vec())?;
+            parsed = 8;
+        }
+
+        let Some(hull_pos) = buf.windows(HULL_TAG.len()).position(|w| w == HULL_TAG) else {
+            log::debug!("cooked mesh {} has no convex hull", self.mesh_tag);
+            return Ok(parsed);
+        };
+
+        let mut cursor = Cursor::new(buf);
+        cursor.set_position((hull_pos + HULL_TAG.len()) as u64);
+        match ConvexHull::read(&mut cursor) {
+            Ok(hull) => {
+                self.hull = Some(hull);
+                // chunks between the header and the hull aren't decoded, so the hull only counts when nothing sits before it
+                if hull_pos == parsed {
+                    Ok(cursor.position() as usize)
+                } else {
+                    Ok(parsed)
+                }
+            },
+            Err(error) => {
+                log::debug!("convex hull doesn't decode: {}", error);
+                Ok(parsed)
+            }
+        }
+    }
+
+    fn unload(&mut self) {
+        *self = Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicsBodyType {
+    Static,
+    Dynamic,
+    Kinematic,
+    Unknown(u32),
+}
+
+impl From<u32> for PhysicsBodyType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Static,
+            1 => Self::Dynamic,
+            2 => Self::Kinematic,
+            v => Self::Unknown(v)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicsBody {
+    pub body_type: PhysicsBodyType,
+    pub mass: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub friction: f32,
+    pub restitution: f32,
+    pub center_of_mass: Vec3,
+    pub flags: u32,
+}
+
+impl PhysicsBody {
+    fn is_valid(&self) -> bool {
+        let values = [self.mass, self.linear_damping, self.angular_damping, self.friction, self.restitution];
+        values.iter().all(|v| v.is_finite() && *v >= 0.0)
+            && self.restitution <= 1.0
+            && self.center_of_mass.is_finite()
+    }
+}
+
+/// Physics body parameters of an object
+#[derive(Default)]
+pub struct PhysicsStructure {
+    pub body: Option<PhysicsBody>,
+}
+
+impl ArchetypeImpl for PhysicsStructure {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        let body = PhysicsBody {
+            body_type: cursor.read_u32::<LittleEndian>()?.into(),
+            mass: cursor.read_f32::<LittleEndian>()?,
+            linear_damping: cursor.read_f32::<LittleEndian>()?,
+            angular_damping: cursor.read_f32::<LittleEndian>()?,
+            friction: cursor.read_f32::<LittleEndian>()?,
+            restitution: cursor.read_f32::<LittleEndian>()?,
+            center_of_mass: read_vec3(&mut cursor)?,
+            flags: cursor.read_u32::<LittleEndian>()?,
+        };
+
+        if !body.is_valid() {
+            log::debug!("physics structure doesn't decode: {:?}", body);
+            return Ok(0);
+        }
+
+        self.body = Some(body);
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
         *self = Default::default()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip_collision_object() {
+        let mut buf = Vec::new();
+        buf.write_u16::<LittleEndian>(4).unwrap();
+        for v in [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] {
+            buf.write_f32::<LittleEndian>(v).unwrap();
+        }
+        buf.write_u16::<LittleEndian>(2).unwrap();
+        for face in [[0, 0, 1, 2], [3, 0, 2, 3]] {
+            for v in face {
+                buf.write_u16::<LittleEndian>(v).unwrap();
+            }
+        }
+
+        let mut col = CollisionObject::default();
+        assert_round_trip(&mut col, &buf);
+        assert_eq!(col.materials(), vec![0, 3]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Cursor;
 
-use byteorder::{ReadBytesExt, LittleEndian};
+use byteorder::{ReadBytesExt, WriteBytesExt, LittleEndian};
 use super::{ArchetypeImpl, YetiIOError};
 
 #[derive(Default)]
@@ -43,7 +43,7 @@ pub struct FullCurve {
 }
 
 impl ArchetypeImpl for YetiCurve {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         
         let curve = match cursor.read_i32::<LittleEndian>().unwrap() {
@@ -58,7 +58,7 @@ impl ArchetypeImpl for YetiCurve {
         match curve {
             Ok(cur) => {
                 self.curve = cur;
-                Ok(())
+                Ok(cursor.position() as usize)
             },
             Err(error) => {
                 self.curve = CurveType::Invalid;
@@ -70,6 +70,37 @@ impl ArchetypeImpl for YetiCurve {
     fn unload(&mut self) {
         self.curve = CurveType::Invalid;
     }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        match &self.curve {
+            CurveType::Constant(cur) => {
+                buf.write_i32::<LittleEndian>(0)?;
+                buf.write_f32::<LittleEndian>(cur.point.y)?;
+            },
+            CurveType::Simple(cur) => {
+                buf.write_i32::<LittleEndian>(2)?;
+                buf.write_u16::<LittleEndian>(cur.points.len() as u16)?;
+                for point in cur.points.iter() {
+                    buf.write_f32::<LittleEndian>(point.x)?;
+                    buf.write_f32::<LittleEndian>(point.y)?;
+                }
+            },
+            CurveType::Full(cur) => {
+                buf.write_i32::<LittleEndian>(4)?;
+                buf.write_u16::<LittleEndian>(cur.points.len() as u16)?;
+                buf.write_u8(cur.flags)?;
+                for point in cur.points.iter() {
+                    buf.write_u8(point.flags)?;
+                    buf.write_f32::<LittleEndian>(point.x)?;
+                    buf.write_f32::<LittleEndian>(point.y)?;
+                    buf.write_f32::<LittleEndian>(point.in_tangent)?;
+                    buf.write_f32::<LittleEndian>(point.out_tangent)?;
+                }
+            },
+            CurveType::Invalid => return Err("can't save an invalid curve".into())
+        }
+        Ok(())
+    }
 }
 
 impl YetiCurve {
@@ -131,4 +162,44 @@ impl YetiCurve {
             flags
         }))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip_constant() {
+        let mut buf = Vec::new();
+        buf.write_i32::<LittleEndian>(0).unwrap();
+        buf.write_f32::<LittleEndian>(2.5).unwrap();
+        assert_round_trip(&mut YetiCurve::default(), &buf);
+    }
+
+    #[test]
+    fn round_trip_simple() {
+        let mut buf = Vec::new();
+        buf.write_i32::<LittleEndian>(2).unwrap();
+        buf.write_u16::<LittleEndian>(3).unwrap();
+        for i in 0..3 {
+            buf.write_f32::<LittleEndian>(i as f32).unwrap();
+            buf.write_f32::<LittleEndian>(i as f32 * 0.25).unwrap();
+        }
+        assert_round_trip(&mut YetiCurve::default(), &buf);
+    }
+
+    #[test]
+    fn round_trip_full() {
+        let mut buf = Vec::new();
+        buf.write_i32::<LittleEndian>(4).unwrap();
+        buf.write_u16::<LittleEndian>(2).unwrap();
+        buf.write_u8(0x03).unwrap();
+        for i in 0..2 {
+            buf.write_u8(i).unwrap();
+            for v in [i as f32, 1.0 - i as f32, 0.5, -0.5] {
+                buf.write_f32::<LittleEndian>(v).unwrap();
+            }
+        }
+        assert_round_trip(&mut YetiCurve::default(), &buf);
+    }
+}
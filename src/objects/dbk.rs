@@ -10,16 +10,17 @@ pub struct DynamicBank {
 }
 
 impl ArchetypeImpl for DynamicBank {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.bank_id = cursor.read_u8()?;
         self.num_bank_entries = cursor.read_u8()?;
         if self.num_bank_entries > 0 {
             self.the_rest_of_the_data = buf[3..].to_vec();
+            return Ok(buf.len());
         }
         
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -8,8 +8,8 @@ pub struct Dbr {
 }
 
 impl ArchetypeImpl for Dbr {
-    fn load_from_buf(&mut self, _buf: &[u8]) -> Result<(), YetiIOError> {
-        Ok(())
+    fn load_from_buf(&mut self, _buf: &[u8]) -> Result<usize, YetiIOError> {
+        Ok(0)
     }
 
     fn unload(&mut self) {
@@ -1,6 +1,6 @@
 use std::io::Cursor;
-use byteorder::{LittleEndian, ReadBytesExt};
-use crate::util::read_nul_term_string;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use crate::util::{read_nul_term_string, write_nul_term_string};
 
 use super::{ArchetypeImpl, YetiIOError};
 
@@ -46,8 +46,29 @@ impl std::fmt::Display for ColumnData {
     }
 }
 
+impl ColumnData {
+    fn type_id(&self) -> i32 {
+        match self {
+            ColumnData::Int(_)      => 1,
+            ColumnData::Float(_)    => 2,
+            ColumnData::String(_)   => 3,
+            ColumnData::Asset(_)    => 4,
+        }
+    }
+
+    fn write(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        match self {
+            ColumnData::Int(v)      => buf.write_i32::<LittleEndian>(*v)?,
+            ColumnData::Float(v)    => buf.write_f32::<LittleEndian>(*v)?,
+            ColumnData::String(v)   => write_nul_term_string(buf, v)?,
+            ColumnData::Asset(v)    => buf.write_u32::<LittleEndian>(*v)?,
+        };
+        Ok(())
+    }
+}
+
 impl ArchetypeImpl for DataTable {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let num_cols = cursor.read_i32::<LittleEndian>()?;
@@ -86,10 +107,62 @@ impl ArchetypeImpl for DataTable {
             self.rows.push(row);
         };
 
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_i32::<LittleEndian>(self.columns.len() as i32)?;
+        buf.write_i32::<LittleEndian>(self.rows.len() as i32)?;
+
+        for col in self.columns.iter() {
+            write_nul_term_string(buf, &col.name)?;
+            buf.write_i32::<LittleEndian>(col.data.type_id())?;
+            col.data.write(buf)?;
+        }
+
+        for row in self.rows.iter() {
+            if row.data.len() != self.columns.len() {
+                return Err(format!("datatable row has {} values, expected {}", row.data.len(), self.columns.len()).into());
+            }
+            for (value, col) in row.data.iter().zip(self.columns.iter()) {
+                if value.type_id() != col.data.type_id() {
+                    return Err(format!("datatable value type {} doesn't match column {}", value.type_id(), col.name).into());
+                }
+                value.write(buf)?;
+            }
+        }
+
         Ok(())
     }
 
     fn unload(&mut self) {
         *self = Self::default();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Vec::new();
+        buf.write_i32::<LittleEndian>(4).unwrap();
+        buf.write_i32::<LittleEndian>(2).unwrap();
+
+        for (name, data) in [("id", ColumnData::Int(0)), ("scale", ColumnData::Float(1.0)), ("name", ColumnData::String("none".into())), ("asset", ColumnData::Asset(0))] {
+            write_nul_term_string(&mut buf, name).unwrap();
+            buf.write_i32::<LittleEndian>(data.type_id()).unwrap();
+            data.write(&mut buf).unwrap();
+        }
+
+        for row in 0..2 {
+            buf.write_i32::<LittleEndian>(row).unwrap();
+            buf.write_f32::<LittleEndian>(0.5 * row as f32).unwrap();
+            write_nul_term_string(&mut buf, &format!("row {}", row)).unwrap();
+            buf.write_u32::<LittleEndian>(0xABCD0000 + row as u32).unwrap();
+        }
+
+        assert_round_trip(&mut DataTable::default(), &buf);
+    }
+}
@@ -1,5 +1,5 @@
 use std::io::Cursor;
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use super::{ArchetypeImpl, YetiIOError};
 use crate::util::*;
 
@@ -9,7 +9,7 @@ pub struct EditableParamsList {
 }
 
 impl ArchetypeImpl for EditableParamsList {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let num_entries = cursor.read_u32::<LittleEndian>()?;
@@ -20,6 +20,14 @@ impl ArchetypeImpl for EditableParamsList {
             i += 1;
         };
 
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u32::<LittleEndian>(self.names_list.len() as u32)?;
+        for name in self.names_list.iter() {
+            write_nul_term_string(buf, name)?;
+        }
         Ok(())
     }
 
@@ -27,4 +35,19 @@ impl ArchetypeImpl for EditableParamsList {
         self.names_list.clear();
         self.names_list.shrink_to(0);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(3).unwrap();
+        for name in ["Health", "Speed", ""] {
+            write_nul_term_string(&mut buf, name).unwrap();
+        }
+        assert_round_trip(&mut EditableParamsList::default(), &buf);
+    }
+}
@@ -19,7 +19,7 @@ pub struct StructEntry {
 }
 
 impl ArchetypeImpl for EditableParamStruct {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         self.unk_01 = cursor.read_u32::<LittleEndian>()?;
         self.struct_data_len = cursor.read_u32::<LittleEndian>()?;
@@ -36,7 +36,7 @@ impl ArchetypeImpl for EditableParamStruct {
             self.entries.push(s);
             i += 1;
         }
-        Ok(())
+        Ok(cursor.position() as usize)
     }
     
     fn unload(&mut self) {
@@ -11,7 +11,7 @@ pub struct Feu {
 }
 
 impl ArchetypeImpl for Feu {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.unk_01 = cursor.read_u32::<LittleEndian>()?;
@@ -45,7 +45,7 @@ impl ArchetypeImpl for Feu {
         let data_pos = cursor.position() as usize;
         self.feu_data = (&buf[data_pos..]).iter().map(|b| *b).collect();
 
-        Ok(())
+        Ok(buf.len())
     }
 
     fn unload(&mut self) {
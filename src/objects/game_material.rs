@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use glam::Vec4;
+
+use super::*;
+use crate::bigfile::Bigfile;
+
+/// Surface properties of a game material, what a collision face is made of
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct GameMaterialProperties {
+    pub surface_type: u32,
+    pub friction: f32,
+    pub restitution: f32,
+    /// surface id the sound banks pick footstep and impact sounds by
+    pub sound_id: u32,
+    /// surface id impact effects are picked by
+    pub fx_id: u32,
+}
+
+impl GameMaterialProperties {
+    fn is_valid(&self) -> bool {
+        self.friction.is_finite() && self.friction >= 0.0 && self.friction <= 16.0
+            && self.restitution.is_finite() && (0.0..=1.0).contains(&self.restitution)
+    }
+
+    /// Stable debug color per surface type, so collision can be told apart by what it's made of
+    pub fn color(&self) -> Vec4 {
+        surface_color(self.surface_type as u64)
+    }
+}
+
+/// Spreads hues with the golden ratio so neighbouring ids get distinct colors
+pub fn surface_color(id: u64) -> Vec4 {
+    let hue = (id as f32 * 0.618_034).fract() * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    // keep it away from pure colors so wireframes stay readable
+    Vec4::new(0.2 + r * 0.7, 0.2 + g * 0.7, 0.2 + b * 0.7, 1.0)
+}
+
+#[derive(Default)]
+pub struct GameMaterial {
+    pub props: Option<GameMaterialProperties>,
+    /// set when the properties are out of range, saving then writes nothing for them
+    pub decode_error: Option<String>,
+}
+
+impl ArchetypeImpl for GameMaterial {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        let props = GameMaterialProperties {
+            surface_type: cursor.read_u32::<LittleEndian>()?,
+            friction: cursor.read_f32::<LittleEndian>()?,
+            restitution: cursor.read_f32::<LittleEndian>()?,
+            sound_id: cursor.read_u32::<LittleEndian>()?,
+            fx_id: cursor.read_u32::<LittleEndian>()?,
+        };
+
+        if !props.is_valid() {
+            self.decode_error = Some(format!("game material properties out of range: {:?}", props));
+            return Ok(0);
+        }
+
+        self.props = Some(props);
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        if let Some(props) = &self.props {
+            buf.write_u32::<LittleEndian>(props.surface_type)?;
+            buf.write_f32::<LittleEndian>(props.friction)?;
+            buf.write_f32::<LittleEndian>(props.restitution)?;
+            buf.write_u32::<LittleEndian>(props.sound_id)?;
+            buf.write_u32::<LittleEndian>(props.fx_id)?;
+        }
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        *self = Default::default()
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+/// The game materials collision faces index into, the materials themselves are the list's references
+#[derive(Default)]
+pub struct GameMaterialList {
+    pub num_materials: u32,
+    /// per material ids, only read when there's exactly one for each material
+    pub ids: Vec<u32>,
+}
+
+impl ArchetypeImpl for GameMaterialList {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        self.num_materials = cursor.read_u32::<LittleEndian>()?;
+
+        if buf.len() != 4 + self.num_materials as usize * 4 {
+            return Ok(4);
+        }
+
+        for _ in 0..self.num_materials {
+            self.ids.push(cursor.read_u32::<LittleEndian>()?);
+        }
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Default::default()
+    }
+}
+
+impl GameMaterialList {
+    /// Game materials of a list, in the order collision faces index them
+    pub fn materials(bf: &Bigfile, gml: YKey) -> Vec<YKey> {
+        bf.object_table.get(&gml).map(|obj| obj.references.iter()
+            .filter(|key| bf.is_key_valid(**key) && bf.file_table[*key].object_type == ObjectType::gmt)
+            .copied()
+            .collect()).unwrap_or_default()
+    }
+
+    /// Game material a collision face index resolves to, with its properties if they decoded
+    pub fn resolve(bf: &Bigfile, gml: YKey, index: u16) -> Option<(YKey, Option<&GameMaterialProperties>)> {
+        let key = *Self::materials(bf, gml).get(index as usize)?;
+        let props = bf.object_table.get(&key)
+            .and_then(|obj| obj.archetype.as_game_material())
+            .and_then(|gmt| gmt.props.as_ref());
+        Some((key, props))
+    }
+
+    /// Objects referencing each object, built once for [`Self::find_for_collision`] rather than scanning every object per level
+    pub fn referenced_by(bf: &Bigfile) -> HashMap<YKey, Vec<YKey>> {
+        let mut referenced_by: HashMap<YKey, Vec<YKey>> = HashMap::new();
+        for (key, obj) in bf.object_table.iter() {
+            for subkey in obj.references.iter() {
+                referenced_by.entry(*subkey).or_default().push(*key);
+            }
+        }
+        referenced_by
+    }
+
+    /// The list a collision's face materials index into: referenced by the collision itself, or by
+    /// whatever references it, up to the game object owning the collision table
+    pub fn find_for_collision(bf: &Bigfile, referenced_by: &HashMap<YKey, Vec<YKey>>, col: YKey) -> Option<YKey> {
+        let is_gml = |key: &YKey| bf.is_key_valid(*key) && bf.file_table[key].object_type == ObjectType::gml;
+
+        let mut level = vec![col];
+        for _ in 0..3 {
+            let found = level.iter()
+                .filter_map(|key| bf.object_table.get(key))
+                .find_map(|obj| obj.references.iter().find(|key| is_gml(key)));
+            if let Some(gml) = found {
+                return Some(*gml);
+            }
+
+            level = level.iter()
+                .filter_map(|key| referenced_by.get(key))
+                .flatten()
+                .copied()
+                .collect();
+            level.sort_unstable();
+            level.dedup();
+            if level.is_empty() {
+                break;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(3).unwrap();
+        buf.write_f32::<LittleEndian>(0.8).unwrap();
+        buf.write_f32::<LittleEndian>(0.1).unwrap();
+        buf.write_u32::<LittleEndian>(0x11).unwrap();
+        buf.write_u32::<LittleEndian>(0x22).unwrap();
+        assert_round_trip(&mut GameMaterial::default(), &buf);
+    }
+}
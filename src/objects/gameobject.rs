@@ -1,4 +1,4 @@
-use std::io::{Cursor, Read};
+use std::io::Cursor;
 
 use byteorder::{ReadBytesExt, LittleEndian};
 use glam::*;
@@ -6,6 +6,9 @@ use bitflags::bitflags;
 use log::warn;
 use super::{ArchetypeImpl, YetiIOError};
 use crate::util::load_util::*;
+use crate::bigfile::Bigfile;
+use crate::metadata::{ObjectType, YKey};
+use super::lod_level_from_name;
 
 #[derive(Default)]
 pub struct GameObject {
@@ -17,6 +20,7 @@ pub struct GameObject {
     pub flag_c: u8,
     pub matrix: Mat4,
     pub light: Light,
+    pub blocks: Vec<GaoBlock>,
 }
 
 bitflags! {
@@ -57,33 +61,118 @@ bitflags! {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Attachment {
+    pub bone: u32,
+    /// index into the gao's references
+    pub ref_index: u32,
+    pub matrix: Mat4,
+}
+
+#[derive(Debug)]
+pub enum GaoBlock {
+    Attachments(Vec<Attachment>),
+    InitialPos(Mat4),
+    AABox { min: Vec3, max: Vec3 },
+    /// indices into the gao's references
+    ZoneArray(Vec<u32>),
+    CollisionList(Vec<u32>),
+    OrientedBox { center: Vec3, half_extents: Vec3, rotation: Quat },
+    /// layout or size unknown, holds everything from this block to the end of the payload, which also stays unparsed
+    Raw(IdentityFlags, Vec<u8>),
+}
+
+impl GaoBlock {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Attachments(_) => "attachments".into(),
+            Self::InitialPos(_) => "initial pos".into(),
+            Self::AABox { .. } => "aabox".into(),
+            Self::ZoneArray(_) => "zone array".into(),
+            Self::CollisionList(_) => "collision list".into(),
+            Self::OrientedBox { .. } => "oriented box".into(),
+            Self::Raw(flag, _) => format!("raw {:?}", flag),
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct LightFlags: u32 {
+        const CAST_SHADOWS          = 1 << 0;
+        const SPECULAR              = 1 << 1;
+        const AFFECTS_CHARACTERS    = 1 << 2;
+        const AFFECTS_WORLD         = 1 << 3;
+        const DYNAMIC               = 1 << 4;
+        const _ = !0;
+    }
+}
+
+/// the 8 bytes shared by every light type, right after the type id
+#[derive(Default, Debug, Clone, Copy)]
+pub struct LightSettings {
+    pub flags: LightFlags,
+    pub mask: u32,
+}
+
 #[derive(Default, Debug)]
 pub enum Light {
     #[default]
     None,
+    Ambient(AmbientLightParams),
     Point(PointLightParams),
     Spot(SpotLightParams),
-    Directional(DirectionalLightParams)
+    Directional(DirectionalLightParams),
+    /// a light type we don't know the layout of, nothing past the settings is read
+    Unknown(u8, LightSettings),
+}
+
+impl Light {
+    pub fn settings(&self) -> Option<&LightSettings> {
+        match self {
+            Self::None => None,
+            Self::Ambient(ambient) => Some(&ambient.settings),
+            Self::Point(point) => Some(&point.settings),
+            Self::Spot(spot) => Some(&spot.settings),
+            Self::Directional(directional) => Some(&directional.settings),
+            Self::Unknown(_, settings) => Some(settings),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AmbientLightParams {
+    pub settings: LightSettings,
+    pub color: Vec4,
+    pub intensity: f32
 }
 
 #[derive(Default, Debug)]
 pub struct PointLightParams {
+    pub settings: LightSettings,
     pub color: Vec4,
     pub intensity: f32,
+    /// distance where the falloff starts
+    pub inner_range: f32,
     pub range: f32
 }
 
 #[derive(Default, Debug)]
 pub struct SpotLightParams {
+    pub settings: LightSettings,
     pub color: Vec4,
     pub intensity: f32,
+    pub inner_range: f32,
     pub range: f32,
+    pub attenuation: f32,
+    pub cone_falloff: f32,
     pub inner_cone_angle: f32,
     pub outer_cone_angle: f32
 }
 
 #[derive(Default, Debug)]
 pub struct DirectionalLightParams {
+    pub settings: LightSettings,
     pub color: Vec4,
     pub intensity: f32
 }
@@ -105,8 +194,142 @@ impl GameObject {
     }
 }
 
+/// Flags that only say the gao has something, with no block of their own in the payload.
+/// Any other set flag without a known layout stops block parsing, since its size is unknown too.
+const NO_BLOCK_FLAGS: IdentityFlags = IdentityFlags::COLLISION_A
+    .union(IdentityFlags::HAS_ANIM)
+    .union(IdentityFlags::COLLISION_LIST_HANDLE)
+    .union(IdentityFlags::DUSTFX)
+    .union(IdentityFlags::HAS_PHYSICS)
+    .union(IdentityFlags::HAS_VISUAL)
+    .union(IdentityFlags::SND_OBJECT)
+    .union(IdentityFlags::GAME_GROUP);
+
+fn read_count(cursor: &Cursor<&[u8]>, entry_size: usize) -> Result<usize, YetiIOError> {
+    let mut count_cursor = cursor.clone();
+    let count = count_cursor.read_u32::<LittleEndian>()? as usize;
+    let remaining = cursor.get_ref().len().saturating_sub(cursor.position() as usize + 4);
+    if count * entry_size > remaining {
+        return Err(format!("count {} doesn't fit in the remaining {} bytes", count, remaining).into());
+    }
+    Ok(count)
+}
+
+impl GameObject {
+    fn read_light_color(cursor: &mut Cursor<&[u8]>) -> Result<Vec4, YetiIOError> {
+        Ok(Vec4::new(
+            cursor.read_u8()? as f32 / 255.0,
+            cursor.read_u8()? as f32 / 255.0,
+            cursor.read_u8()? as f32 / 255.0,
+            cursor.read_u8()? as f32 / 255.0
+        ).zyxw()) // for some reason yeti light colors are bgra
+    }
+
+    fn read_light(cursor: &mut Cursor<&[u8]>) -> Result<Light, YetiIOError> {
+        let lt = cursor.read_u8()?;
+        let settings = LightSettings {
+            flags: LightFlags::from_bits_retain(cursor.read_u32::<LittleEndian>()?),
+            mask: cursor.read_u32::<LittleEndian>()?,
+        };
+        let light = match lt {
+            0 => {
+                Light::Ambient(AmbientLightParams {
+                    settings,
+                    color: Self::read_light_color(cursor)?,
+                    intensity: cursor.read_f32::<LittleEndian>()?,
+                })
+            },
+            1 => {
+                Light::Point(PointLightParams {
+                    settings,
+                    color: Self::read_light_color(cursor)?,
+                    intensity: cursor.read_f32::<LittleEndian>()?,
+                    inner_range: cursor.read_f32::<LittleEndian>()?,
+                    range: cursor.read_f32::<LittleEndian>()?,
+                })
+            },
+            2 => {
+                Light::Spot(SpotLightParams {
+                    settings,
+                    color: Self::read_light_color(cursor)?,
+                    intensity: cursor.read_f32::<LittleEndian>()?,
+                    inner_range: cursor.read_f32::<LittleEndian>()?,
+                    range: cursor.read_f32::<LittleEndian>()?,
+                    attenuation: cursor.read_f32::<LittleEndian>()?,
+                    cone_falloff: cursor.read_f32::<LittleEndian>()?,
+                    inner_cone_angle: cursor.read_f32::<LittleEndian>()?,
+                    outer_cone_angle: cursor.read_f32::<LittleEndian>()?,
+                })
+            },
+            3 => {
+                Light::Directional(DirectionalLightParams {
+                    settings,
+                    color: Self::read_light_color(cursor)?,
+                    intensity: cursor.read_f32::<LittleEndian>()?,
+                })
+            },
+            _ => {
+                warn!("unknown light type {}", lt);
+                Light::Unknown(lt, settings)
+            }
+        };
+        Ok(light)
+    }
+
+    /// reads the block gated by `flag`, blocks we don't know the layout of return None
+    fn read_block(cursor: &mut Cursor<&[u8]>, flag: IdentityFlags) -> Result<Option<GaoBlock>, YetiIOError> {
+        let block = match flag {
+            IdentityFlags::HAS_ATTACHMENTS => {
+                let count = read_count(cursor, 72)?;
+                cursor.read_u32::<LittleEndian>()?;
+                let mut attachments = Vec::with_capacity(count);
+                for _ in 0..count {
+                    attachments.push(Attachment {
+                        bone: cursor.read_u32::<LittleEndian>()?,
+                        ref_index: cursor.read_u32::<LittleEndian>()?,
+                        matrix: read_mat4(cursor)?,
+                    });
+                }
+                GaoBlock::Attachments(attachments)
+            },
+            IdentityFlags::INITIAL_POS => GaoBlock::InitialPos(read_mat4(cursor)?),
+            IdentityFlags::HAS_AABOX => {
+                let (min, max) = (read_vec3(cursor)?, read_vec3(cursor)?);
+                if !min.is_finite() || !max.is_finite() || min.cmpgt(max).any() {
+                    return Err(format!("bad aabox {} {}", min, max).into());
+                }
+                GaoBlock::AABox { min, max }
+            },
+            IdentityFlags::ZONE_ARRAY | IdentityFlags::COLLISION_LIST_STRUCT => {
+                let count = read_count(cursor, 4)?;
+                cursor.read_u32::<LittleEndian>()?;
+                let mut indices = Vec::with_capacity(count);
+                for _ in 0..count {
+                    indices.push(cursor.read_u32::<LittleEndian>()?);
+                }
+                if flag == IdentityFlags::ZONE_ARRAY {
+                    GaoBlock::ZoneArray(indices)
+                } else {
+                    GaoBlock::CollisionList(indices)
+                }
+            },
+            IdentityFlags::ORIENTED_BOX => {
+                let center = read_vec3(cursor)?;
+                let half_extents = read_vec3(cursor)?;
+                let rotation = read_quat(cursor)?;
+                if !rotation.is_normalized() {
+                    return Err(format!("oriented box rotation isn't normalized {}", rotation).into());
+                }
+                GaoBlock::OrientedBox { center, half_extents, rotation }
+            },
+            _ => return Ok(None)
+        };
+        Ok(Some(block))
+    }
+}
+
 impl ArchetypeImpl for GameObject {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.zero = cursor.read_u32::<LittleEndian>()?;
@@ -120,67 +343,39 @@ impl ArchetypeImpl for GameObject {
         self.flag_c = cursor.read_u8()?;
         self.matrix = read_mat4(&mut cursor)?;
 
-        if self.identity_flags.contains(IdentityFlags::SFX_STRUCT | IdentityFlags::HAS_VISUAL) {
-            let lt = cursor.read_u8()?;
-            let mut dat: [u8; 8] = [0; 8];
-            cursor.read(&mut dat)?;
-            match lt {
-                1 => {
-                    self.light = Light::Point(PointLightParams {
-                        color: Vec4::new(
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0
-                        ).zyxw(), // for some reason yeti light colors are bgra
-                        intensity: cursor.read_f32::<LittleEndian>()?,
-                        range: {
-                            cursor.read_f32::<LittleEndian>()?;
-                            cursor.read_f32::<LittleEndian>()?
-                        }
-                    });
-                },
-                2 => {
-                    self.light = Light::Spot( SpotLightParams {
-                        color: Vec4::new(
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0
-                        ).zyxw(), // for some reason yeti light colors are bgra
-                        intensity: cursor.read_f32::<LittleEndian>()?,
-                        range: {
-                            cursor.read_f32::<LittleEndian>()?;
-                            cursor.read_f32::<LittleEndian>()?
-                        },
-                        inner_cone_angle: {
-                            cursor.read_f32::<LittleEndian>()?;
-                            cursor.read_f32::<LittleEndian>()?;
-                            cursor.read_f32::<LittleEndian>()?
-                        },
-                        outer_cone_angle: cursor.read_f32::<LittleEndian>()?
-                    });
-                },
-                3 => {
-                    self.light = Light::Directional(DirectionalLightParams {
-                        color: Vec4::new(
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0,
-                            cursor.read_u8()? as f32 / 255.0
-                        ).zyxw(), // for some reason yeti light colors are bgra
-                        intensity: cursor.read_f32::<LittleEndian>()?,
-                    });
+        // lights sit straight after the matrix rather than at their flag's position, map exports depend on this
+        let has_light = self.identity_flags.contains(IdentityFlags::SFX_STRUCT | IdentityFlags::HAS_VISUAL);
+        if has_light {
+            self.light = Self::read_light(&mut cursor)?;
+
+            // we don't know how long an unknown light is, so nothing after it can be found
+            if matches!(self.light, Light::Unknown(..)) {
+                return Ok(cursor.position() as usize);
+            }
+        }
+
+        for bit in 1..32 {
+            let flag = IdentityFlags::from_bits_retain(1 << bit);
+            if !self.identity_flags.contains(flag) || NO_BLOCK_FLAGS.contains(flag) || (has_light && flag == IdentityFlags::SFX_STRUCT) {
+                continue;
+            }
+
+            let start = cursor.position();
+            match Self::read_block(&mut cursor, flag) {
+                Ok(Some(block)) => {
+                    self.blocks.push(block);
+                    continue;
                 },
-                _ => {
-                    self.light = Light::None;
-                    //return Err(format!("weird light type?? {}", lt).into());
-                    warn!("weird light type?? {}", lt);
-                }
+                Ok(None) => log::debug!("gao block {:?} has an unknown layout", flag),
+                Err(error) => log::debug!("couldn't read gao block {:?}: {}", flag, error),
             }
+
+            // the block's size isn't known, so it and everything after it are kept raw and left unparsed
+            self.blocks.push(GaoBlock::Raw(flag, buf[start as usize..].to_vec()));
+            return Ok(start as usize);
         }
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -192,15 +387,55 @@ impl ArchetypeImpl for GameObject {
 
 #[derive(Default)]
 pub struct GraphicObjectTable {
-
+    /// switch distance per LOD level, when the table carries them
+    pub lod_distances: Vec<f32>,
 }
 
 impl ArchetypeImpl for GraphicObjectTable {
-    fn load_from_buf(&mut self, _buf: &[u8]) -> Result<(), YetiIOError> {
-        Ok(())
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        // only a count and that many ascending distances is taken as a LOD table, anything else stays unparsed
+        let mut cursor = Cursor::new(buf);
+        let Ok(count) = cursor.read_u32::<LittleEndian>() else { return Ok(0); };
+        if count == 0 || count > 8 || buf.len() != 4 + count as usize * 4 {
+            return Ok(0);
+        }
+
+        let mut distances = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            distances.push(cursor.read_f32::<LittleEndian>()?);
+        }
+        if !distances.iter().all(|d| d.is_finite() && *d >= 0.0) || !distances.windows(2).all(|w| w[0] < w[1]) {
+            log::debug!("got data isn't a LOD table: {:?}", distances);
+            return Ok(0);
+        }
+
+        self.lod_distances = distances;
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
         *self = Default::default();
     }
-}
\ No newline at end of file
+}
+
+impl GraphicObjectTable {
+    /// Meshes of a got in reference order with their LOD level. Levels come from the mesh names, else from
+    /// the reference order when there's a distance per mesh, else every mesh is level 0.
+    pub fn mesh_lods(&self, bf: &Bigfile, key: YKey) -> Vec<(YKey, u8)> {
+        let meshes: Vec<YKey> = bf.object_table[&key].references.iter()
+            .filter(|key| bf.is_key_valid(**key) && bf.file_table[*key].object_type == ObjectType::msh)
+            .copied()
+            .collect();
+
+        let named: Vec<Option<u8>> = meshes.iter().map(|key| lod_level_from_name(bf.file_table[key].get_name())).collect();
+        if named.iter().any(|level| level.is_some_and(|level| level > 0)) {
+            return meshes.into_iter().zip(named).map(|(key, level)| (key, level.unwrap_or(0))).collect();
+        }
+
+        if meshes.len() > 1 && self.lod_distances.len() == meshes.len() {
+            return meshes.into_iter().enumerate().map(|(level, key)| (key, level as u8)).collect();
+        }
+
+        meshes.into_iter().map(|key| (key, 0)).collect()
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::{Read, Cursor};
 
-use byteorder::{ReadBytesExt, LittleEndian};
+use byteorder::{ReadBytesExt, WriteBytesExt, LittleEndian};
 use super::{ArchetypeImpl, YetiIOError};
 
 #[derive(Default)]
@@ -9,7 +9,7 @@ pub struct YetiIni {
 }
 
 pub enum IniEntry {
-    Invalid,
+    Invalid(String, u8, u32),
     Int(String, u32),
     AssetKey(String, u32)
 }
@@ -33,7 +33,7 @@ impl YetiIni {
             let value = match entry_type {
                 0 => IniEntry::Int(key, value),
                 1 => IniEntry::AssetKey(key, value),
-                _ => IniEntry::Invalid
+                t => IniEntry::Invalid(key, t, value)
             };
 
             entries.push(value);
@@ -47,9 +47,25 @@ impl YetiIni {
 }
 
 impl ArchetypeImpl for YetiIni {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         self.entries = self.load_from_reader(&mut cursor)?;
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u32::<LittleEndian>(self.entries.len() as u32)?;
+        for entry in self.entries.iter() {
+            let (key, entry_type, value) = match entry {
+                IniEntry::Int(key, value) => (key, 0, *value),
+                IniEntry::AssetKey(key, value) => (key, 1, *value),
+                IniEntry::Invalid(key, entry_type, value) => (key, *entry_type, *value)
+            };
+            buf.extend_from_slice(key.as_bytes());
+            buf.write_u8(0)?;
+            buf.write_u8(entry_type)?;
+            buf.write_u32::<LittleEndian>(value)?;
+        }
         Ok(())
     }
 
@@ -57,4 +73,37 @@ impl ArchetypeImpl for YetiIni {
         self.entries.clear();
         self.entries.shrink_to_fit();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    fn entry(buf: &mut Vec<u8>, key: &str, entry_type: u8, value: u32) {
+        buf.extend_from_slice(key.as_bytes());
+        buf.push(0);
+        buf.push(entry_type);
+        buf.write_u32::<LittleEndian>(value).unwrap();
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(2).unwrap();
+        entry(&mut buf, "max_count", 0, 12);
+        entry(&mut buf, "asset", 1, 0xDEADBEEF);
+        assert_round_trip(&mut YetiIni::default(), &buf);
+    }
+
+    #[test]
+    fn round_trip_invalid_entry() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(2).unwrap();
+        entry(&mut buf, "unknown", 7, 0x12345678);
+        entry(&mut buf, "value", 0, 1);
+
+        let mut ini = YetiIni::default();
+        assert_round_trip(&mut ini, &buf);
+        assert!(matches!(&ini.entries[0], IniEntry::Invalid(key, 7, 0x12345678) if key == "unknown"));
+    }
+}
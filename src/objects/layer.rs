@@ -6,13 +6,14 @@ pub struct YetiLayer {
 }
 
 impl ArchetypeImpl for YetiLayer {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let vec: Vec<u8> = buf.iter().skip(4).map(|b| *b).take_while(|b| *b != 0).collect(); 
+        let consumed = buf.len().min(4 + vec.len() + 1); // name plus null terminator
         self.name = match String::from_utf8(vec) {
             Ok(name) => name,
             Err(error) => return Err(error.to_string().into())
         };
-        Ok(())
+        Ok(consumed)
     }
 
     fn unload(&mut self) {
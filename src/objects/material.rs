@@ -1,15 +1,175 @@
-// use super::ArchetypeImpl;
+use std::io::Cursor;
+use byteorder::{LittleEndian, ReadBytesExt};
+use enum_as_inner::EnumAsInner;
+use glam::Vec4;
+use bitflags::bitflags;
+use strum::{Display, FromRepr};
+use super::{ArchetypeImpl, YetiIOError};
+use crate::util::load_util::read_vec4;
 
-// pub struct YetiMaterial {
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct MaterialFlags: u32 {
+        const TWO_SIDED     = 1 << 0;
+        const ALPHA_TEST    = 1 << 1;
+        const ALPHA_BLEND   = 1 << 2;
+        const EMISSIVE      = 1 << 3;
+        const NO_LIGHTING   = 1 << 4;
+        const _ = !0;
+    }
+}
 
-// }
+#[derive(FromRepr, Display, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BlendMode {
+    #[default]
+    Opaque = 0,
+    Alpha = 1,
+    Additive = 2,
+    Multiply = 3,
+    InvColorAlpha = 4,
+    ColorAlpha = 5,
+}
 
-// impl ArchetypeImpl for YetiMaterial {
-//     fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), super::LoadError> {
-        
-//     }
+#[derive(FromRepr, Display, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TextureSlotType {
+    #[default]
+    Diffuse = 0,
+    Specular = 1,
+    Normal = 2,
+    Emissive = 3,
+    Detail = 4,
+    Environment = 5,
+}
 
-//     fn unload(&mut self) {
-        
-//     }
-// }
\ No newline at end of file
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MaterialTextureSlot {
+    pub slot: TextureSlotType,
+    /// index into the material's reference list
+    pub ref_index: u8,
+    pub uv_set: u8,
+    pub unk_01: u8,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MaterialParam {
+    pub id: u32,
+    pub value: f32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MaterialProperties {
+    pub unk_01: u32,
+    pub flags: MaterialFlags,
+    pub blend_mode: BlendMode,
+    pub alpha_ref: u8,
+    pub unk_02: u16,
+    pub diffuse: Vec4,
+    pub specular: Vec4,
+    pub emissive: Vec4,
+    pub textures: Vec<MaterialTextureSlot>,
+    pub params: Vec<MaterialParam>,
+}
+
+impl MaterialProperties {
+    pub fn alpha_cutoff(&self) -> f32 {
+        self.alpha_ref as f32 / 255.0
+    }
+
+    pub fn texture(&self, slot: TextureSlotType) -> Option<&MaterialTextureSlot> {
+        self.textures.iter().find(|tex| tex.slot == slot)
+    }
+}
+
+#[derive(Default, EnumAsInner)]
+pub enum MaterialData {
+    #[default]
+    None,
+    Decoded(MaterialProperties),
+    /// holds the decode error, the material still loads so its textures and shader do
+    Unknown(String),
+}
+
+#[derive(Default)]
+pub struct YetiMaterial {
+    pub data: MaterialData,
+}
+
+impl YetiMaterial {
+    fn read_color(cursor: &mut Cursor<&[u8]>) -> Result<Vec4, YetiIOError> {
+        let col = read_vec4(cursor)?;
+        if !col.is_finite() || col.min_element() < 0.0 || col.max_element() > 64.0 {
+            return Err(format!("color out of range {}", col).into());
+        }
+        Ok(col)
+    }
+
+    fn read_properties(cursor: &mut Cursor<&[u8]>) -> Result<MaterialProperties, YetiIOError> {
+        let mut props = MaterialProperties {
+            unk_01: cursor.read_u32::<LittleEndian>()?,
+            flags: MaterialFlags::from_bits_retain(cursor.read_u32::<LittleEndian>()?),
+            ..Default::default()
+        };
+
+        let blend_id = cursor.read_u8()?;
+        props.blend_mode = BlendMode::from_repr(blend_id).ok_or(format!("unknown blend mode {:#04X}", blend_id))?;
+        props.alpha_ref = cursor.read_u8()?;
+        props.unk_02 = cursor.read_u16::<LittleEndian>()?;
+
+        props.diffuse = Self::read_color(cursor)?;
+        props.specular = Self::read_color(cursor)?;
+        props.emissive = Self::read_color(cursor)?;
+
+        let num_textures = cursor.read_u8()?;
+        for _ in 0..num_textures {
+            let slot_id = cursor.read_u8()?;
+            props.textures.push(MaterialTextureSlot {
+                slot: TextureSlotType::from_repr(slot_id).ok_or(format!("unknown texture slot {:#04X}", slot_id))?,
+                ref_index: cursor.read_u8()?,
+                uv_set: cursor.read_u8()?,
+                unk_01: cursor.read_u8()?,
+            });
+        }
+
+        let num_params = cursor.read_u8()?;
+        for _ in 0..num_params {
+            props.params.push(MaterialParam {
+                id: cursor.read_u32::<LittleEndian>()?,
+                value: cursor.read_f32::<LittleEndian>()?,
+            });
+        }
+
+        Ok(props)
+    }
+}
+
+impl ArchetypeImpl for YetiMaterial {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        // the layout is only trusted when it accounts for the whole payload
+        let props = Self::read_properties(&mut cursor).and_then(|props| match cursor.position() as usize {
+            read if read < buf.len() => Err(format!("properties only cover {:#X} of {:#X} bytes", read, buf.len()).into()),
+            _ => Ok(props)
+        });
+        match props {
+            Ok(props) => {
+                self.data = MaterialData::Decoded(props);
+                Ok(cursor.position() as usize)
+            },
+            Err(error) => {
+                log::debug!("couldn't decode material: {}", error);
+                self.data = MaterialData::Unknown(error.to_string());
+                Ok(0)
+            }
+        }
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default();
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.data.as_unknown().cloned()
+    }
+}
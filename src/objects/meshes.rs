@@ -5,16 +5,27 @@ use glam::*;
 use std::ops::Range;
 use crate::util::mesh_util::*;
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshMetadataLayout {
+    #[default]
+    Current,
+    /// older files, descriptors without the unknown bytes and no trailer
+    Legacy,
+    /// neither layout fits, the mesh data is exported as a single submesh
+    Unknown,
+}
+
 #[derive(Default)]
 pub struct MeshMetadata {
     pub num_submeshes: u8,
     pub version: i32,
+    pub layout: MeshMetadataLayout,
     pub unk_dat01: [u8; 10],
     pub submeshes: Vec<SubmeshDescriptor>,
     pub unk_dat02: [u8; 32],
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct SubmeshDescriptor {
     pub vtx_start: u16,
     pub vtx_num: u16,
@@ -25,49 +36,104 @@ pub struct SubmeshDescriptor {
     pub bone_palette: Vec<u8>,
 }
 
-impl ArchetypeImpl for MeshMetadata {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), super::YetiIOError> {
-        let mut cursor = Cursor::new(buf);
+impl MeshMetadata {
+    fn read_submesh(cursor: &mut Cursor<&[u8]>, with_unk: bool) -> Result<SubmeshDescriptor, super::YetiIOError> {
+        let mut desc = SubmeshDescriptor {
+            vtx_start: cursor.read_u16::<LittleEndian>()?,
+            vtx_num: cursor.read_u16::<LittleEndian>()?,
+            face_start: cursor.read_u16::<LittleEndian>()?,
+            face_num: cursor.read_u16::<LittleEndian>()?,
+            ..Default::default()
+        };
 
-        self.num_submeshes = cursor.read_u8()?;
-        self.version = cursor.read_i32::<LittleEndian>()?;
-        if self.version != 2 {
-            return Err(format!("unknown version: {}", self.version).into());
+        if with_unk {
+            cursor.read_exact(&mut desc.unk_dat01)?;
         }
 
-        cursor.read(&mut self.unk_dat01)?;
-        for _ in 0..self.num_submeshes {
-            let mut desc = SubmeshDescriptor::default();
-
-            desc.vtx_start = cursor.read_u16::<LittleEndian>()?;
-            desc.vtx_num = cursor.read_u16::<LittleEndian>()?;
-            desc.face_start = cursor.read_u16::<LittleEndian>()?;
-            desc.face_num = cursor.read_u16::<LittleEndian>()?;
+        desc.material_index = cursor.read_u8()?;
 
-            cursor.read_exact(&mut desc.unk_dat01)?;
+        desc.bone_palette = vec![0; cursor.read_u8()? as usize];
+        cursor.read_exact(&mut desc.bone_palette)?;
 
-            desc.material_index = cursor.read_u8()?;
+        Ok(desc)
+    }
 
-            desc.bone_palette = vec![0; cursor.read_u8()? as usize];
-            cursor.read_exact(&mut desc.bone_palette)?;
-            
-            self.submeshes.push(desc);
+    fn load_current(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), super::YetiIOError> {
+        cursor.read_exact(&mut self.unk_dat01)?;
+        for _ in 0..self.num_submeshes {
+            self.submeshes.push(Self::read_submesh(cursor, true)?);
         }
-        cursor.read(&mut self.unk_dat02)?;
+        cursor.read_exact(&mut self.unk_dat02)?;
 
         if self.unk_dat02[0] != 0x20 && self.unk_dat02[31] != 0xFF {
-            //there's some older unused files with a different format, cba to figure it out tho
             return Err("unknown data in mesh metadata".into());
         }
+        Ok(())
+    }
+
+    fn load_legacy(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), super::YetiIOError> {
+        for _ in 0..self.num_submeshes {
+            self.submeshes.push(Self::read_submesh(cursor, false)?);
+        }
 
+        if cursor.position() as usize != cursor.get_ref().len() {
+            return Err("legacy mesh metadata doesn't end where expected".into());
+        }
         Ok(())
     }
+}
+
+impl ArchetypeImpl for MeshMetadata {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, super::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+
+        self.num_submeshes = cursor.read_u8()?;
+        self.version = cursor.read_i32::<LittleEndian>()?;
+        let header_end = cursor.position();
+
+        if self.version == 2 {
+            match self.load_current(&mut cursor) {
+                Ok(()) => {
+                    self.layout = MeshMetadataLayout::Current;
+                    return Ok(cursor.position() as usize);
+                },
+                Err(error) => log::debug!("mesh metadata version 2 doesn't fit the current layout: {}", error)
+            }
+        }
+
+        cursor.set_position(header_end);
+        self.submeshes.clear();
+        match self.load_legacy(&mut cursor) {
+            Ok(()) => {
+                self.layout = MeshMetadataLayout::Legacy;
+                Ok(cursor.position() as usize)
+            },
+            Err(error) => {
+                // still load so the mesh data can be viewed and exported whole
+                log::warn!("unknown mesh metadata layout, version {}: {}", self.version, error);
+                self.submeshes.clear();
+                self.layout = MeshMetadataLayout::Unknown;
+                Ok(header_end as usize)
+            }
+        }
+    }
 
     fn unload(&mut self) {
         *self = Default::default()
     }
 }
 
+/// LOD level from a mesh name suffix such as `_lod1` or ` LOD2`
+pub fn lod_level_from_name(name: &str) -> Option<u8> {
+    let lower = name.to_ascii_lowercase();
+    let idx = lower.rfind("lod")?;
+    if idx > 0 && !matches!(lower.as_bytes()[idx - 1], b'_' | b' ' | b'-' | b'.') {
+        return None;
+    }
+    let digits: String = lower[idx + 3..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
 #[derive(Default)]
 pub struct MeshData {
     pub unk_01: u32,
@@ -79,13 +145,19 @@ pub struct MeshData {
 
     pub old_num_submeshes: u16,
     pub old_submesh_size: u32,
+    /// submesh table between the header and the vertex data, used by meshes with legacy metadata
+    pub old_submeshes: Vec<SubmeshDescriptor>,
 
     pub num_submeshes: u16,
     pub pivot_offset: Vec3,
     pub uniform_scale: f32,
 
+    pub layout: VertexLayout,
+    pub index_size: u8,
     pub vertex_data: VertexData,
-    pub faces: Vec<FaceData>
+    pub faces: Vec<FaceData>,
+    /// set when the vertex layout was guessed rather than matched
+    pub decode_error: Option<String>,
 }
 
 #[derive(Default)]
@@ -101,19 +173,195 @@ pub struct Weight {
     pub weight: f32,
 }
 
+/// Vertex layouts, told apart by their stride
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexLayout {
+    /// pos, uv0, uv1, weights, tangent frame
+    #[default]
+    Skinned,
+    /// skinned with an rgba8 color after the tangent frame
+    SkinnedColor,
+    SkinnedNoUv1,
+    /// pos, uv0, uv1, tangent frame
+    Static,
+    StaticNoUv1,
+    /// unknown stride, only the position is decoded
+    PositionOnly(usize),
+}
+
+impl VertexLayout {
+    const KNOWN: [VertexLayout; 5] = [Self::Skinned, Self::SkinnedColor, Self::SkinnedNoUv1, Self::Static, Self::StaticNoUv1];
+
+    pub fn stride(&self) -> usize {
+        match self {
+            Self::Skinned => 32,
+            Self::SkinnedColor => 36,
+            Self::SkinnedNoUv1 => 28,
+            Self::Static => 24,
+            Self::StaticNoUv1 => 20,
+            Self::PositionOnly(stride) => *stride,
+        }
+    }
+
+    pub fn has_uv1(&self) -> bool {
+        matches!(self, Self::Skinned | Self::SkinnedColor | Self::Static)
+    }
+
+    pub fn has_weights(&self) -> bool {
+        matches!(self, Self::Skinned | Self::SkinnedColor | Self::SkinnedNoUv1)
+    }
+
+    pub fn has_color(&self) -> bool {
+        matches!(self, Self::SkinnedColor)
+    }
+
+    pub fn has_tangent_frame(&self) -> bool {
+        !matches!(self, Self::PositionOnly(_))
+    }
+
+    /// Finds the layout and index size that make the vertex and index data fill `data_len` exactly. The skinned
+    /// layout with u16 indices is taken whenever it fits, anything picked out of several fits or made up from the
+    /// size alone comes with a note of what was guessed.
+    fn detect(num_vertices: usize, num_indices: usize, data_len: usize) -> (VertexLayout, u8, Option<String>) {
+        let fits = |layout: VertexLayout, index_size: usize| num_vertices * layout.stride() + num_indices * index_size == data_len;
+        if fits(Self::default(), 2) {
+            return (Self::default(), 2, None);
+        }
+
+        let candidates: Vec<(VertexLayout, usize)> = [2, 4].into_iter()
+            .flat_map(|index_size| Self::KNOWN.into_iter().map(move |layout| (layout, index_size)))
+            .filter(|(layout, index_size)| fits(*layout, *index_size))
+            .collect();
+        match candidates.as_slice() {
+            [(layout, index_size)] => return (*layout, *index_size as u8, None),
+            [(layout, index_size), ..] => return (*layout, *index_size as u8,
+                Some(format!("{} vertex layouts fit the data, guessed {:?} with {} byte indices", candidates.len(), layout, index_size))),
+            [] => { }
+        }
+
+        if num_vertices > 0 {
+            for index_size in [2, 4] {
+                let Some(vertex_len) = data_len.checked_sub(num_indices * index_size) else { continue; };
+                if vertex_len % num_vertices == 0 && vertex_len / num_vertices >= 8 {
+                    let stride = vertex_len / num_vertices;
+                    return (Self::PositionOnly(stride), index_size as u8,
+                        Some(format!("no known vertex layout fits, guessed a {} byte stride with {} byte indices and only read positions", stride, index_size)));
+                }
+            }
+        }
+
+        (Self::default(), 2, Some(format!("no vertex layout fits {} bytes of vertex and index data, read as {:?}", data_len, Self::default())))
+    }
+}
+
 #[derive(Default)]
 pub struct VertexData {
-    pub bufs: Vec<[u8; 32]>,
+    /// the vertices as stored, `stride` bytes each
+    pub raw: Vec<u8>,
+    pub stride: usize,
     pub pos: Vec<Vec3>,
     pub uv0: Vec<Vec2>,
     pub uv1: Vec<Vec2>,
     pub weights: Vec<[Weight; 4]>,
     pub tangents: Vec<Vec3>,
     pub normals: Vec<Vec3>,
+    pub colors: Vec<Vec4>,
+}
+
+impl VertexData {
+    pub fn vertex_bytes(&self, idx: usize) -> &[u8] {
+        self.raw.get(idx * self.stride..(idx + 1) * self.stride).unwrap_or_default()
+    }
+
+    fn read_vertex(&mut self, vbuf: &[u8], layout: VertexLayout, uniform_scale: f32, pivot_offset: Vec3) -> Result<(), super::YetiIOError> {
+        let mut vbufr: &[u8] = vbuf;
+        self.pos.push((Vec3::new(
+                snorm16_to_float(vbufr.read_i16::<LittleEndian>()?),
+                snorm16_to_float(vbufr.read_i16::<LittleEndian>()?),
+                snorm16_to_float(vbufr.read_i16::<LittleEndian>()?)
+            ) * snorm16_to_float(vbufr.read_i16::<LittleEndian>()?)
+                * uniform_scale)
+                + pivot_offset
+        );
+
+        if layout.has_tangent_frame() {
+            self.uv0.push(
+                 Vec2::new(
+                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
+                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
+            ));
+        }
+        if layout.has_uv1() {
+            self.uv1.push(
+                 Vec2::new(
+                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
+                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
+            ));
+        }
+        if layout.has_weights() {
+            self.weights.push({
+                let mut weights = [Weight::default(); 4];
+                for weight in weights.iter_mut() {
+                    weight.weight = vbufr.read_u8()? as f32 / 255.0;
+                }
+                for weight in weights.iter_mut() {
+                    weight.bone = vbufr.read_u8()?;
+                }
+                weights
+            });
+        }
+        if layout.has_tangent_frame() {
+            let mut tnbuf: [u8; 8] = [0; 8];
+            vbufr.read_exact(&mut tnbuf)?;
+            let (normal, tangent, _) = bytes_to_rgb10_a2_tnb_with_a(&tnbuf);
+
+            self.tangents.push(tangent.truncate());
+            self.normals.push(normal.truncate());
+        }
+        if layout.has_color() {
+            let mut color: [u8; 4] = [0; 4];
+            vbufr.read_exact(&mut color)?;
+            self.colors.push(Vec4::from_array(color.map(|c| c as f32 / 255.0)));
+        }
+
+        Ok(())
+    }
+}
+
+impl MeshData {
+    fn read_old_submeshes(&mut self, buf: &[u8]) -> Result<(), super::YetiIOError> {
+        let entry_size = self.old_submesh_size as usize;
+        let table_len = self.old_num_submeshes as usize * entry_size;
+        if self.old_num_submeshes == 0 || entry_size < 8 || table_len > self.data_offset as usize || 0x47 + table_len > buf.len() {
+            return Ok(());
+        }
+
+        for entry in buf[0x47..0x47 + table_len].chunks_exact(entry_size) {
+            let mut entryr: &[u8] = entry;
+            self.old_submeshes.push(SubmeshDescriptor {
+                vtx_start: entryr.read_u16::<LittleEndian>()?,
+                vtx_num: entryr.read_u16::<LittleEndian>()?,
+                face_start: entryr.read_u16::<LittleEndian>()?,
+                face_num: entryr.read_u16::<LittleEndian>()?,
+                // same order as the legacy metadata descriptors
+                material_index: entryr.read_u8().unwrap_or(0),
+                ..Default::default()
+            });
+        }
+
+        // a submesh has one material, so more materials than submeshes means that byte is something else
+        if self.old_submeshes.iter().any(|sb| sb.material_index as usize >= self.old_submeshes.len()) {
+            log::warn!("old submesh material indices go past the {} submeshes, using the first material for all", self.old_submeshes.len());
+            for sb in self.old_submeshes.iter_mut() {
+                sb.material_index = 0;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ArchetypeImpl for MeshData {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), super::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, super::YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.unk_01 = cursor.read_u32::<LittleEndian>()?;
@@ -141,89 +389,127 @@ impl ArchetypeImpl for MeshData {
         );
         self.uniform_scale = cursor.read_f32::<LittleEndian>()?;
 
-        cursor.seek(SeekFrom::Start((0x47 + self.data_offset) as u64))?;
-
-        let mut vbuf: [u8; 32] = [0; 32];
-        let mut tnbuf: [u8; 8] = [0; 8];
-        let mut bufs: Vec<[u8; 32]> = Vec::with_capacity(self.num_vertices as usize);
-        let mut pos: Vec<Vec3> = Vec::with_capacity(self.num_vertices as usize);
-        let mut uv0: Vec<Vec2> = Vec::with_capacity(self.num_vertices as usize);
-        let mut uv1: Vec<Vec2> = Vec::with_capacity(self.num_vertices as usize);
-        let mut weights: Vec<[Weight; 4]> = Vec::with_capacity(self.num_vertices as usize);
-        let mut tangents: Vec<Vec3> = Vec::with_capacity(self.num_vertices as usize);
-        let mut normals: Vec<Vec3> = Vec::with_capacity(self.num_vertices as usize);
-        for _ in 0..self.num_vertices {
-            cursor.read(&mut vbuf)?;
-            let mut vbufr: &[u8] = &vbuf;
-            pos.push((Vec3::new(
-                    snorm16_to_float(vbufr.read_i16::<LittleEndian>()?),
-                    snorm16_to_float(vbufr.read_i16::<LittleEndian>()?),
-                    snorm16_to_float(vbufr.read_i16::<LittleEndian>()?)
-                ) * snorm16_to_float(vbufr.read_i16::<LittleEndian>()?)
-                    * self.uniform_scale)
-                    + self.pivot_offset
-                
-            );
-            uv0.push(
-                 Vec2::new(
-                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
-                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
-            ));
-            uv1.push(
-                 Vec2::new(
-                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
-                uvi16_to_float(vbufr.read_i16::<LittleEndian>()?),
-            ));
-            weights.push({
-                let mut weights = [Weight::default(); 4];
-                for i in 0..4 {
-                    weights[i].weight = vbufr.read_u8()? as f32 / 255.0;
-                }
-                for i in 0..4 {
-                    weights[i].bone = vbufr.read_u8()?;
-                 }
-                weights
-            });
+        self.read_old_submeshes(buf)?;
 
-            {
-                vbufr.read_exact(&mut tnbuf)?;
-                let (normal, tangent, _) = bytes_to_rgb10_a2_tnb_with_a(&tnbuf);
+        let data_start = 0x47 + self.data_offset as usize;
+        let data_len = buf.len().saturating_sub(data_start);
+        (self.layout, self.index_size, self.decode_error) = VertexLayout::detect(self.num_vertices as usize, self.num_indices as usize, data_len);
 
-                tangents.push(tangent.truncate());
-                normals.push(normal.truncate());
-            }
-            
-            bufs.push(vbuf);
-        }
-        self.vertex_data = VertexData {
-            bufs,
-            pos,
-            uv0,
-            uv1,
-            weights,
-            tangents,
-            normals,
-        };
+        cursor.seek(SeekFrom::Start(data_start as u64))?;
+
+        let stride = self.layout.stride();
+        let num_vertices = usize::min(self.num_vertices as usize, data_len / stride);
+        if num_vertices < self.num_vertices as usize {
+            log::warn!("mesh data only has room for {} of {} vertices", num_vertices, self.num_vertices);
+        }
 
-        self.faces = Vec::with_capacity(self.num_indices as usize);
+        let mut raw = vec![0; num_vertices * stride];
+        cursor.read_exact(&mut raw)?;
+        let mut vertex_data = VertexData::default();
+        for vbuf in raw.chunks_exact(stride) {
+            vertex_data.read_vertex(vbuf, self.layout, self.uniform_scale, self.pivot_offset)?;
+        }
+        vertex_data.raw = raw;
+        vertex_data.stride = stride;
+        self.vertex_data = vertex_data;
+
+        let index_size = self.index_size as usize;
+        let num_faces = usize::min(self.num_indices as usize / 3, data_len.saturating_sub(num_vertices * stride) / (index_size * 3));
+        if num_faces < self.num_indices as usize / 3 {
+            log::warn!("mesh data only has room for {} of {} faces", num_faces, self.num_indices / 3);
+        }
+
+        self.faces = Vec::with_capacity(num_faces);
 
-        for _ in 0..self.num_indices / 3 {
+        let read_index = |cursor: &mut Cursor<&[u8]>| -> Result<u32, super::YetiIOError> {
+            Ok(match index_size {
+                4 => cursor.read_u32::<LittleEndian>()?,
+                _ => cursor.read_u16::<LittleEndian>()? as u32
+            })
+        };
+        for _ in 0..num_faces {
             self.faces.push(FaceData {
-                f0: cursor.read_u16::<LittleEndian>()? as u32,
-                f1: cursor.read_u16::<LittleEndian>()? as u32,
-                f2: cursor.read_u16::<LittleEndian>()? as u32
+                f0: read_index(&mut cursor)?,
+                f1: read_index(&mut cursor)?,
+                f2: read_index(&mut cursor)?
             });
         }
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
         *self = Default::default();
     }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
 }
 
 impl MeshData {
+    /// The submeshes to draw this mesh data with: the metadata's, else the old table, else the whole mesh as one
+    pub fn resolve_submeshes(&self, msh: Option<&MeshMetadata>) -> Vec<SubmeshDescriptor> {
+        if let Some(msh) = msh {
+            if !msh.submeshes.is_empty() {
+                return msh.submeshes.clone();
+            }
+        }
+
+        if !self.old_submeshes.is_empty() {
+            return self.old_submeshes.clone();
+        }
+
+        vec![SubmeshDescriptor {
+            vtx_start: 0,
+            vtx_num: self.vertex_data.pos.len() as u16,
+            face_start: 0,
+            face_num: u16::try_from(self.faces.len()).unwrap_or(u16::MAX),
+            ..Default::default()
+        }]
+    }
+
+    fn vertex_bounds(&self, range: Range<usize>) -> Option<(Vec3, Vec3)> {
+        let pos = self.vertex_data.pos.get(range)?;
+        if pos.is_empty() {
+            return None;
+        }
+        Some(pos.iter().fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), p| (min.min(*p), max.max(*p))))
+    }
+
+    /// LOD level per submesh. Submeshes are taken as LODs when they repeat the same material sequence, with
+    /// fewer faces each time and about the same bounds. Bone palette splits also repeat materials but cover
+    /// different parts of the mesh, the bounds check keeps those at level 0.
+    pub fn submesh_lod_levels(&self, submeshes: &[SubmeshDescriptor]) -> Vec<u8> {
+        let num = submeshes.len();
+        for period in 1..=num / 2 {
+            if !num.is_multiple_of(period) || num / period > u8::MAX as usize {
+                continue;
+            }
+
+            let groups: Vec<&[SubmeshDescriptor]> = submeshes.chunks(period).collect();
+            let same_materials = groups.iter().all(|group| group.iter().zip(groups[0]).all(|(a, b)| a.material_index == b.material_index));
+            let faces: Vec<u32> = groups.iter().map(|group| group.iter().map(|sb| sb.face_num as u32).sum()).collect();
+            if !same_materials || !faces.windows(2).all(|w| w[1] < w[0]) {
+                continue;
+            }
+
+            let bounds: Option<Vec<(Vec3, Vec3)>> = groups.iter().map(|group| {
+                group.iter()
+                    .map(|sb| self.vertex_bounds(sb.vtx_start as usize..sb.vtx_start as usize + sb.vtx_num as usize))
+                    .try_fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), b| b.map(|(bmin, bmax)| (min.min(bmin), max.max(bmax))))
+            }).collect();
+            let Some(bounds) = bounds else { continue; };
+            let (min, max) = bounds[0];
+            let tolerance = (max - min).length() * 0.1;
+            if bounds.iter().all(|(bmin, bmax)| bmin.distance(min) <= tolerance && bmax.distance(max) <= tolerance) {
+                return (0..num).map(|idx| (idx / period) as u8).collect();
+            }
+        }
+
+        vec![0; num]
+    }
+
     pub fn _bounding_box(&self) -> (Vec3, Vec3) {
         let mut min = Vec3::new(0.0, 0.0, 0.0);
         let mut max = Vec3::new(0.0, 0.0, 0.0);
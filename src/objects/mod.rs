@@ -13,7 +13,7 @@ mod epl;         pub use epl::*;
 mod meshes;      pub use meshes::*;
 mod texture;     pub use texture::*;
 mod sound;       pub use sound::*;
-mod material;    //pub use material::*;
+mod material;    pub use material::*;
 mod shader;      pub use shader::*;
 mod skeleton;    pub use skeleton::*;
 mod eps;         pub use eps::*;
@@ -23,11 +23,14 @@ mod vxc;         pub use vxc::*;
 mod vxt;         pub use vxt::*;
 mod world;       pub use world::*;
 mod collision;   pub use collision::*;
+mod game_material; pub use game_material::*;
+mod nav;         pub use nav::*;
 mod anim_other;  pub use anim_other::*;
 mod way;         pub use way::*;
+mod animation;   pub use animation::*;
 
 use std::io::Cursor;
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use crate::{YetiIOError, bigfile::metadata::{FileEntry, ObjectType}, metadata::YKey};
 
 pub struct YetiObject {
@@ -36,7 +39,10 @@ pub struct YetiObject {
     name: String,
     pub references: Vec<YKey>,
     pub archetype: ObjectArchetype,
-    pub load_error: Option<YetiIOError>
+    pub load_error: Option<YetiIOError>,
+    pub load_warning: Option<String>,
+    pub consumed: usize,
+    pub unparsed_data: Vec<u8>,
 }
 
 impl Default for YetiObject {
@@ -47,7 +53,10 @@ impl Default for YetiObject {
             name: String::default(),
             references: Vec::new(),
             archetype: ObjectArchetype::NoImpl,
-            load_error: None
+            load_error: None,
+            load_warning: None,
+            consumed: 0,
+            unparsed_data: Vec::new(),
         }
     }
 }
@@ -71,8 +80,10 @@ pub enum ObjectArchetype {
     SoundBank(SoundBank),
     ShaderGraph(VisualShader),
     Skeleton(Skeleton),
+    SkeletonProcedural(SkeletonProcedural),
     EditableParamStruct(EditableParamStruct),
     Zone(Zone),
+    ZoneArray(ZoneArray),
     EditableParamsList(EditableParamsList),
     Dbr(Dbr),
     DataTable(DataTable),
@@ -82,12 +93,21 @@ pub enum ObjectArchetype {
     World(World),
     CollisionObject(CollisionObject),
     CollisionObjectTable(CollisionObjectTable),
+    CookedCollisionMesh(CookedCollisionMesh),
+    PhysicsStructure(PhysicsStructure),
+    GameMaterial(GameMaterial),
+    GameMaterialList(GameMaterialList),
+    NavData(NavData),
     AnimEvent(AnimEventContainer),
     ListActionBank(ListActionBank),
     ActionBank(ActionBank),
     Action(Action),
     Way(Way),
+    WayExternalLink(WayExternalLink),
+    WayList(WayList),
     Rsf(Rsf),
+    Material(YetiMaterial),
+    Animation(Animation),
 }
 
 impl ObjectArchetype {
@@ -108,9 +128,11 @@ impl ObjectArchetype {
             Self::TextureMetadata       (ref mut arch) => Some(arch),
             Self::SoundBank             (ref mut arch) => Some(arch),
             Self::Skeleton              (ref mut arch) => Some(arch),
+            Self::SkeletonProcedural    (ref mut arch) => Some(arch),
             Self::EditableParamStruct   (ref mut arch) => Some(arch),
             Self::EditableParamsList    (ref mut arch) => Some(arch),
             Self::Zone                  (ref mut arch) => Some(arch),
+            Self::ZoneArray             (ref mut arch) => Some(arch),
             Self::Dbr                   (ref mut arch) => Some(arch),
             Self::DataTable             (ref mut arch) => Some(arch),
             Self::VertexColors          (ref mut arch) => Some(arch),
@@ -120,23 +142,32 @@ impl ObjectArchetype {
             Self::World                 (ref mut arch) => Some(arch),
             Self::CollisionObject       (ref mut arch) => Some(arch),
             Self::CollisionObjectTable  (ref mut arch) => Some(arch),
+            Self::CookedCollisionMesh   (ref mut arch) => Some(arch),
+            Self::PhysicsStructure      (ref mut arch) => Some(arch),
+            Self::GameMaterial          (ref mut arch) => Some(arch),
+            Self::GameMaterialList      (ref mut arch) => Some(arch),
+            Self::NavData               (ref mut arch) => Some(arch),
             Self::AnimEvent             (ref mut arch) => Some(arch),
             Self::ListActionBank        (ref mut arch) => Some(arch),
             Self::ActionBank            (ref mut arch) => Some(arch),
             Self::Action                (ref mut arch) => Some(arch),
             Self::Way                   (ref mut arch) => Some(arch),
+            Self::WayExternalLink       (ref mut arch) => Some(arch),
+            Self::WayList               (ref mut arch) => Some(arch),
             Self::Rsf                   (ref mut arch) => Some(arch),
+            Self::Material              (ref mut arch) => Some(arch),
+            Self::Animation             (ref mut arch) => Some(arch),
             Self::NoImpl => None
         };
 
         return a;
     }
 
-    pub fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    pub fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         if let Some(arch) = self.get_impl() {
             return arch.load_from_buf(buf);
         }
-        Ok(())
+        Ok(0)
     }
 
     pub fn unload(&mut self) {
@@ -144,6 +175,38 @@ impl ObjectArchetype {
             arch.unload();
         }
     }
+
+    pub fn decode_error(&mut self) -> Option<String> {
+        self.get_impl().and_then(|arch| arch.decode_error())
+    }
+
+    pub fn reference_error(&mut self, num_refs: usize) -> Option<String> {
+        self.get_impl().and_then(|arch| arch.reference_error(num_refs))
+    }
+
+    pub fn can_save(&self) -> bool {
+        matches!(self,
+            Self::Ini(_) | Self::DataTable(_) | Self::Curve(_) | Self::ConstList(_) | Self::EditableParamsList(_) |
+            Self::VertexColors(_) | Self::Vxt(_) | Self::Zone(_) | Self::CollisionObject(_) |
+            Self::GameMaterial(_)
+        )
+    }
+
+    pub fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        match self {
+            Self::Ini                   (arch) => arch.save_to_buf(buf),
+            Self::DataTable             (arch) => arch.save_to_buf(buf),
+            Self::Curve                 (arch) => arch.save_to_buf(buf),
+            Self::ConstList             (arch) => arch.save_to_buf(buf),
+            Self::EditableParamsList    (arch) => arch.save_to_buf(buf),
+            Self::VertexColors          (arch) => arch.save_to_buf(buf),
+            Self::Vxt                   (arch) => arch.save_to_buf(buf),
+            Self::Zone                  (arch) => arch.save_to_buf(buf),
+            Self::CollisionObject       (arch) => arch.save_to_buf(buf),
+            Self::GameMaterial          (arch) => arch.save_to_buf(buf),
+            _ => Err("saving is not supported for this archetype".into())
+        }
+    }
 }
 
 impl YetiObject {
@@ -174,8 +237,10 @@ impl YetiObject {
             ObjectType::snk => ObjectArchetype::SoundBank(SoundBank::default()),
             ObjectType::shd => ObjectArchetype::ShaderGraph(VisualShader::default()),
             ObjectType::ske => ObjectArchetype::Skeleton(Skeleton::default()),
+            ObjectType::skp => ObjectArchetype::SkeletonProcedural(SkeletonProcedural::default()),
             ObjectType::eps => ObjectArchetype::EditableParamStruct(EditableParamStruct::default()),
             ObjectType::zon => ObjectArchetype::Zone(Zone::default()),
+            ObjectType::zar => ObjectArchetype::ZoneArray(ZoneArray::default()),
             ObjectType::epl => ObjectArchetype::EditableParamsList(EditableParamsList::default()),
             ObjectType::dbr => ObjectArchetype::Dbr(Dbr::default()),
             ObjectType::dtb => ObjectArchetype::DataTable(DataTable::default()),
@@ -185,12 +250,21 @@ impl YetiObject {
             ObjectType::wor => ObjectArchetype::World(World::default()),
             ObjectType::col => ObjectArchetype::CollisionObject(CollisionObject::default()),
             ObjectType::cot => ObjectArchetype::CollisionObjectTable(Default::default()),
+            ObjectType::ccm => ObjectArchetype::CookedCollisionMesh(Default::default()),
+            ObjectType::phs => ObjectArchetype::PhysicsStructure(Default::default()),
+            ObjectType::gmt => ObjectArchetype::GameMaterial(Default::default()),
+            ObjectType::gml => ObjectArchetype::GameMaterialList(Default::default()),
+            ObjectType::nav => ObjectArchetype::NavData(Default::default()),
             ObjectType::aev => ObjectArchetype::AnimEvent(AnimEventContainer::default()),
             ObjectType::lab => ObjectArchetype::ListActionBank(ListActionBank::default()),
             ObjectType::acb => ObjectArchetype::ActionBank(ActionBank::default()),
             ObjectType::act => ObjectArchetype::Action(Action::default()),
             ObjectType::way => ObjectArchetype::Way(Way::default()),
+            ObjectType::wel => ObjectArchetype::WayExternalLink(WayExternalLink::default()),
+            ObjectType::wal => ObjectArchetype::WayList(WayList::default()),
             ObjectType::rsf => ObjectArchetype::Rsf(Rsf::default()),
+            ObjectType::mat => ObjectArchetype::Material(YetiMaterial::default()),
+            ObjectType::ani => ObjectArchetype::Animation(Animation::default()),
             _ => ObjectArchetype::NoImpl
         }
     }
@@ -215,18 +289,55 @@ impl YetiObject {
         let (refs, buf) = crate::bigfile::io::parse_and_remove_refs(buf);
         self.references = refs;
 
-        if let Err(mut error) = self.archetype.load_from_buf(buf) {
-            self.archetype.unload();
-            error.set_key(self.get_key());
-            self.load_error = Some(error);
-            return Err(self.load_error.clone().unwrap())
+        let consumed = match self.archetype.load_from_buf(buf) {
+            Ok(consumed) => consumed,
+            Err(mut error) => {
+                self.archetype.unload();
+                error.set_key(self.get_key());
+                self.load_error = Some(error);
+                return Err(self.load_error.clone().unwrap())
+            }
+        };
+
+        let mut warnings = Vec::new();
+        if let Some(error) = self.archetype.decode_error() {
+            warnings.push(format!("payload didn't decode: {}", error));
+        }
+        if let Some(error) = self.archetype.reference_error(self.references.len()) {
+            warnings.push(format!("bad reference index: {}", error));
+        }
+
+        // anything past the consumed bytes is data we don't know how to parse yet
+        if !self.archetype.is_no_impl() && consumed < buf.len() {
+            self.unparsed_data = buf[consumed..].to_vec();
+            warnings.push(format!("{} unparsed bytes at offset {:#X}", self.unparsed_data.len(), consumed));
+        } else {
+            self.unparsed_data = Vec::new();
         }
 
+        self.load_warning = (!warnings.is_empty()).then(|| warnings.join(", "));
+        if let Some(warning) = &self.load_warning {
+            log::debug!("{:#010X} {}: {}", self.get_key(), self.get_name(), warning);
+        }
+        self.consumed = consumed;
+
         self.load_refs += 1;
         self.load_error = None;
         Ok(())
     }
 
+    /// Rebuilds the full file (references, payload and any unparsed trailing data).
+    pub fn save_to_buf(&self) -> Result<Vec<u8>, YetiIOError> {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(self.references.len() as u32)?;
+        for key in self.references.iter() {
+            buf.write_u32::<LittleEndian>((*key).into())?;
+        }
+        self.archetype.save_to_buf(&mut buf)?;
+        buf.extend_from_slice(&self.unparsed_data);
+        Ok(buf)
+    }
+
     pub fn unload(&mut self) {
         if !self.is_loaded() { return; }
 
@@ -235,12 +346,47 @@ impl YetiObject {
         if self.load_refs == 0 {
             self.archetype.unload();
             self.references.clear();
+            self.unparsed_data = Vec::new();
+            self.load_warning = None;
         }
     }
 }
 
 pub trait ArchetypeImpl {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError>;
+    /// Parses the object payload (references already stripped) and returns how many bytes of it were consumed.
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError>;
     fn unload(&mut self);
+
+    /// Why the payload didn't decode, for archetypes that still load without it. Those are the ones whose
+    /// references have to be followed regardless, the bytes are kept as unparsed data.
+    fn decode_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Reference indices in the payload that point past the object's references. Those are only known
+    /// once the whole object is parsed, so this is checked after loading rather than in it.
+    fn reference_error(&self, _num_refs: usize) -> Option<String> {
+        None
+    }
+
+    /// Writes back exactly the bytes that `load_from_buf` consumed.
+    fn save_to_buf(&self, _buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        Err("saving is not supported for this archetype".into())
+    }
 }
 
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::ArchetypeImpl;
+
+    /// Loads `bytes`, checks all of it was consumed and that saving gives back the same bytes
+    pub fn assert_round_trip(arch: &mut impl ArchetypeImpl, bytes: &[u8]) {
+        let consumed = arch.load_from_buf(bytes).expect("load failed");
+        assert_eq!(consumed, bytes.len(), "not all bytes were consumed");
+
+        let mut saved = Vec::new();
+        arch.save_to_buf(&mut saved).expect("save failed");
+        assert_eq!(saved, bytes);
+    }
+}
@@ -0,0 +1,154 @@
+use bitflags::bitflags;
+use glam::Vec3;
+
+use super::*;
+use crate::bigfile::Bigfile;
+use crate::util::load_util::read_vec3;
+
+bitflags! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct NavAreaFlags: u16 {
+        const WALKABLE      = 1 << 0;
+        const JUMP          = 1 << 1;
+        const DOOR          = 1 << 2;
+        const WATER         = 1 << 3;
+        const DISABLED      = 1 << 4;
+        const _ = !0;
+    }
+}
+
+/// A convex navmesh polygon, an edge's neighbour is the polygon sharing the edge from `indices[i]` to `indices[i + 1]`
+#[derive(Default, Debug, Clone)]
+pub struct NavPolygon {
+    pub area_flags: NavAreaFlags,
+    pub indices: Vec<u16>,
+    pub neighbours: Vec<Option<u16>>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct NavMesh {
+    pub version: u32,
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    pub vertices: Vec<Vec3>,
+    pub polygons: Vec<NavPolygon>,
+}
+
+const NO_NEIGHBOUR: u16 = 0xFFFF;
+const MAX_POLYGON_VERTICES: u16 = 16;
+
+impl NavMesh {
+    /// Polygons as triangle fans, with the polygon each triangle came from
+    pub fn triangles(&self) -> Vec<(usize, [u16; 3])> {
+        self.polygons.iter().enumerate()
+            .flat_map(|(idx, poly)| (1..poly.indices.len().saturating_sub(1))
+                .map(move |i| (idx, [poly.indices[0], poly.indices[i], poly.indices[i + 1]])))
+            .collect()
+    }
+
+    /// Area flag combinations used by the polygons, sorted
+    pub fn areas(&self) -> Vec<NavAreaFlags> {
+        let mut areas: Vec<NavAreaFlags> = self.polygons.iter().map(|poly| poly.area_flags).collect();
+        areas.sort_unstable();
+        areas.dedup();
+        areas
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self, YetiIOError> {
+        let version = cursor.read_u32::<LittleEndian>()?;
+        let bounds_min = read_vec3(cursor)?;
+        let bounds_max = read_vec3(cursor)?;
+        let num_vertices = cursor.read_u32::<LittleEndian>()?;
+        let num_polygons = cursor.read_u32::<LittleEndian>()?;
+
+        // indices and neighbours are u16, with 0xFFFF reserved for edges without a neighbour
+        if num_vertices >= NO_NEIGHBOUR as u32 || num_polygons >= NO_NEIGHBOUR as u32 {
+            return Err(format!("navmesh counts out of range: {} vertices {} polygons", num_vertices, num_polygons).into());
+        }
+
+        if !bounds_min.is_finite() || !bounds_max.is_finite() || bounds_min.cmpgt(bounds_max).any() {
+            return Err(format!("navmesh bounds are invalid: {} - {}", bounds_min, bounds_max).into());
+        }
+
+        let mut vertices = Vec::with_capacity(num_vertices as usize);
+        for _ in 0..num_vertices {
+            vertices.push(read_vec3(cursor)?);
+        }
+
+        let mut polygons = Vec::with_capacity(num_polygons as usize);
+        for _ in 0..num_polygons {
+            let area_flags = NavAreaFlags::from_bits_retain(cursor.read_u16::<LittleEndian>()?);
+            let num_indices = cursor.read_u16::<LittleEndian>()?;
+            if !(3..=MAX_POLYGON_VERTICES).contains(&num_indices) {
+                return Err(format!("navmesh polygon has {} vertices", num_indices).into());
+            }
+
+            let mut indices = Vec::with_capacity(num_indices as usize);
+            for _ in 0..num_indices {
+                let idx = cursor.read_u16::<LittleEndian>()?;
+                if idx as u32 >= num_vertices {
+                    return Err(format!("navmesh polygon indexes vertex {} of {}", idx, num_vertices).into());
+                }
+                indices.push(idx);
+            }
+
+            let mut neighbours = Vec::with_capacity(num_indices as usize);
+            for _ in 0..num_indices {
+                let neighbour = cursor.read_u16::<LittleEndian>()?;
+                if neighbour != NO_NEIGHBOUR && neighbour as u32 >= num_polygons {
+                    return Err(format!("navmesh polygon neighbour {} of {}", neighbour, num_polygons).into());
+                }
+                neighbours.push((neighbour != NO_NEIGHBOUR).then_some(neighbour));
+            }
+
+            polygons.push(NavPolygon { area_flags, indices, neighbours });
+        }
+
+        Ok(Self { version, bounds_min, bounds_max, vertices, polygons })
+    }
+}
+
+#[derive(Default)]
+pub struct NavData {
+    pub navmesh: Option<NavMesh>,
+    /// set when the navmesh doesn't decode, worlds still link to the nav data without it
+    pub decode_error: Option<String>,
+}
+
+impl ArchetypeImpl for NavData {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        match NavMesh::read(&mut cursor) {
+            Ok(navmesh) => {
+                self.navmesh = Some(navmesh);
+                Ok(cursor.position() as usize)
+            },
+            Err(error) => {
+                self.decode_error = Some(error.to_string());
+                Ok(0)
+            }
+        }
+    }
+
+    fn unload(&mut self) {
+        *self = Default::default()
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+impl NavData {
+    /// Worlds linking to a nav data object
+    pub fn find_worlds(bf: &Bigfile, nav: YKey) -> Vec<YKey> {
+        let mut worlds: Vec<YKey> = bf.object_table.iter()
+            .filter(|(_, obj)| obj.archetype.as_world()
+                .and_then(|wor| wor.data.as_decoded())
+                .is_some_and(|data| data.links.nav == nav))
+            .map(|(key, _)| *key)
+            .collect();
+        worlds.sort_unstable_by_key(|key| u32::from(*key));
+        worlds
+    }
+}
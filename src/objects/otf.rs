@@ -8,7 +8,7 @@ pub struct Otf {
 }
 
 impl ArchetypeImpl for Otf {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut v: Vec<u8> = vec![0; buf.len()];
         v.copy_from_slice(buf);
 
@@ -20,7 +20,7 @@ impl ArchetypeImpl for Otf {
             tweak: egui::FontTweak::default()
         });
 
-        Ok(())
+        Ok(buf.len())
     }
 
     fn unload(&mut self) {
@@ -0,0 +1,137 @@
+use super::*;
+
+/// Inputs of `eSID_MainOutput`, in pin order
+pub mod main_output_pin {
+    pub const DIFFUSE: u32 = 0;
+    pub const ALPHA: u32 = 1;
+    pub const NORMAL: u32 = 2;
+    pub const SPECULAR: u32 = 3;
+    pub const EMISSIVE: u32 = 4;
+}
+
+const DEFAULT_ALPHA_REF: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShaderAlphaMode {
+    Opaque,
+    Test(f32),
+    /// alpha comes from the color of a texture, optionally inverted
+    ColorAlpha { inverted: bool },
+}
+
+/// A texture node that reaches the main output, and which of its inputs it reaches
+#[derive(Debug, Clone, Copy)]
+pub struct ShaderTextureUse {
+    pub node: usize,
+    /// position among the graph's texture nodes, the node params aren't decoded so this stands in for the texture slot
+    pub texture_slot: u32,
+    pub diffuse: bool,
+    pub alpha: bool,
+    pub normal: bool,
+    pub specular: bool,
+    pub emissive: bool,
+}
+
+/// What a visual shader's main output is computed from, as far as a glTF material cares
+#[derive(Debug, Clone)]
+pub struct ShaderClassification {
+    pub graph: usize,
+    pub alpha: ShaderAlphaMode,
+    pub unlit: bool,
+    pub emissive: bool,
+    pub normal_mapped: bool,
+    pub textures: Vec<ShaderTextureUse>,
+}
+
+impl ShaderClassification {
+    pub fn first_texture(&self, filter: impl Fn(&ShaderTextureUse) -> bool) -> Option<&ShaderTextureUse> {
+        self.textures.iter().find(|tex| filter(tex))
+    }
+
+    pub fn diffuse_texture(&self) -> Option<&ShaderTextureUse> {
+        self.first_texture(|tex| tex.diffuse)
+    }
+
+    pub fn normal_texture(&self) -> Option<&ShaderTextureUse> {
+        self.first_texture(|tex| tex.normal)
+    }
+
+    pub fn specular_texture(&self) -> Option<&ShaderTextureUse> {
+        self.first_texture(|tex| tex.specular && !tex.diffuse)
+    }
+
+    pub fn emissive_texture(&self) -> Option<&ShaderTextureUse> {
+        self.first_texture(|tex| tex.emissive)
+    }
+}
+
+impl ShaderNodeId {
+    /// nodes that sample a material texture
+    pub fn samples_texture(&self) -> bool {
+        matches!(self, Self::eSID_Tex2D(_) | Self::eSID_Tex2DYUV(_) | Self::eSID_TexBump(_) | Self::eSID_TexBumpTangent(_))
+    }
+
+    fn is_bump(&self) -> bool {
+        matches!(self, Self::eSID_TexBump(_) | Self::eSID_TexBumpTangent(_) | Self::eSID_OffsetBump(_))
+    }
+}
+
+impl ShaderGraph {
+    /// Node feeding the given input of a node and everything upstream of it
+    fn input_tree(&self, node: usize, pin: u32) -> Vec<usize> {
+        let Some(Some(source)) = self.nodes[node].inputs.get(pin as usize) else { return Vec::new(); };
+        let mut tree = vec![source.node];
+        tree.extend(self.upstream(source.node));
+        tree
+    }
+}
+
+impl VisualShader {
+    /// Classifies the first graph with a main output, None if no graph has one
+    pub fn classify(&self) -> Option<ShaderClassification> {
+        let (graph_idx, graph, main) = self.graphs.iter().enumerate()
+            .find_map(|(idx, graph)| graph.main_output().map(|main| (idx, graph, main)))?;
+        let diffuse = graph.input_tree(main, main_output_pin::DIFFUSE);
+        let alpha = graph.input_tree(main, main_output_pin::ALPHA);
+        let normal = graph.input_tree(main, main_output_pin::NORMAL);
+        let specular = graph.input_tree(main, main_output_pin::SPECULAR);
+        let emissive = graph.input_tree(main, main_output_pin::EMISSIVE);
+
+        let is_node = |nodes: &[usize], f: fn(&ShaderNodeId) -> bool| nodes.iter().any(|idx| f(&graph.nodes[*idx].node));
+
+        // a color selector on the alpha input means alpha is taken from a texture's color
+        let color_alpha = is_node(&alpha, |node| matches!(node, ShaderNodeId::eSID_ColorSelector(_) | ShaderNodeId::eSID_ColorSelectorSmooth(_)));
+        let alpha_mode = if color_alpha {
+            ShaderAlphaMode::ColorAlpha { inverted: is_node(&alpha, |node| matches!(node, ShaderNodeId::eSID_Invert(_))) }
+        } else if !alpha.is_empty() {
+            // the main output's blend settings aren't decoded, so any other alpha is taken as alpha testing
+            ShaderAlphaMode::Test(DEFAULT_ALPHA_REF)
+        } else {
+            ShaderAlphaMode::Opaque
+        };
+
+        let textures = graph.nodes.iter().enumerate()
+            .filter(|(_, node)| node.node.samples_texture())
+            .enumerate()
+            .map(|(texture_slot, (idx, _))| ShaderTextureUse {
+                node: idx,
+                texture_slot: texture_slot as u32,
+                diffuse: diffuse.contains(&idx),
+                alpha: alpha.contains(&idx),
+                normal: normal.contains(&idx),
+                specular: specular.contains(&idx),
+                emissive: emissive.contains(&idx),
+            })
+            .filter(|tex| tex.diffuse || tex.alpha || tex.normal || tex.specular || tex.emissive)
+            .collect();
+
+        Some(ShaderClassification {
+            graph: graph_idx,
+            alpha: alpha_mode,
+            unlit: diffuse.is_empty() && !emissive.is_empty(),
+            emissive: !emissive.is_empty(),
+            normal_mapped: !normal.is_empty() || is_node(&diffuse, ShaderNodeId::is_bump),
+            textures,
+        })
+    }
+}
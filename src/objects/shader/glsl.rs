@@ -0,0 +1,239 @@
+use std::fmt::Write;
+
+use super::*;
+
+const VERTEX_SOURCE: &str = "
+attribute vec3 position;
+attribute vec3 normal;
+attribute vec2 uv0;
+attribute vec2 uv1;
+attribute vec4 color;
+
+uniform mat4 u_model;
+uniform mat4 u_view_proj;
+
+varying vec3 v_world_pos;
+varying vec3 v_normal;
+varying vec2 v_uv0;
+varying vec2 v_uv1;
+varying vec4 v_color;
+
+void main(void)
+{
+    vec4 world = u_model * vec4(position, 1.0);
+    gl_Position = u_view_proj * world;
+    v_world_pos = world.xyz;
+    v_normal = (u_model * vec4(normal, 0.0)).xyz;
+    v_uv0 = uv0;
+    v_uv1 = uv1;
+    v_color = color;
+}
+";
+
+const FRAGMENT_PREAMBLE: &str = "
+precision highp float;
+
+uniform float u_time;
+uniform vec3 u_camera;
+uniform vec3 u_light_dir;
+uniform vec4 u_diffuse;
+uniform vec4 u_specular;
+uniform vec4 u_emissive;
+
+varying vec3 v_world_pos;
+varying vec3 v_normal;
+varying vec2 v_uv0;
+varying vec2 v_uv1;
+varying vec4 v_color;
+
+vec4 rotate_uv(vec4 uv, vec2 center, float angle)
+{
+    float s = sin(angle);
+    float c = cos(angle);
+    vec2 p = uv.xy - center;
+    return vec4(vec2(p.x * c - p.y * s, p.x * s + p.y * c) + center, uv.zw);
+}
+";
+
+/// magenta, so untranslated nodes stand out in a preview
+const STUB_VALUE: &str = "vec4(1.0, 0.0, 1.0, 1.0)";
+
+const UV_VARYING: &str = "vec4(v_uv0, 0.0, 1.0)";
+
+impl ShaderGraph {
+    /// Expression of the node feeding an input, or `default` when nothing is connected
+    fn glsl_input(&self, node: usize, pin: usize, default: &str) -> String {
+        match self.nodes[node].inputs.get(pin) {
+            Some(Some(source)) => format!("n{}", source.node),
+            _ => default.to_string()
+        }
+    }
+
+    fn is_input_connected(&self, node: usize, pin: usize) -> bool {
+        matches!(self.nodes[node].inputs.get(pin), Some(Some(_)))
+    }
+
+    /// mix of the first two inputs, by the third input if it's connected
+    fn glsl_blend(&self, idx: usize) -> String {
+        let factor = match self.nodes[idx].inputs.get(2) {
+            Some(Some(source)) => format!("n{}.x", source.node),
+            _ => String::from("0.5")
+        };
+        format!("mix({}, {}, {})", self.glsl_input(idx, 0, "vec4(0.0)"), self.glsl_input(idx, 1, "vec4(0.0)"), factor)
+    }
+
+    /// each output component is the same component of the matching input, or of the first input when that isn't connected
+    fn glsl_combine(&self, idx: usize) -> String {
+        let parts: Vec<String> = ["x", "y", "z", "w"].iter().enumerate().map(|(c, component)| {
+            let input = self.glsl_input(idx, c, &self.glsl_input(idx, 0, "vec4(0.0)"));
+            format!("{}.{}", input, component)
+        }).collect();
+        format!("vec4({})", parts.join(", "))
+    }
+
+    /// GLSL expression for a node, None when the node has no translation.
+    /// Every node evaluates to a vec4 and output pins past the first are not told apart.
+    /// Node params aren't decoded, so nodes that need them use neutral defaults and constants become stubs.
+    fn glsl_node_expr(&self, idx: usize) -> Option<String> {
+        let a = || self.glsl_input(idx, 0, "vec4(0.0)");
+        let b = || self.glsl_input(idx, 1, "vec4(0.0)");
+        let a1 = || self.glsl_input(idx, 0, "vec4(1.0)");
+        let b1 = || self.glsl_input(idx, 1, "vec4(1.0)");
+        let uv = || self.glsl_input(idx, 0, UV_VARYING);
+        let n_dot_v = "max(dot(N, V), 0.0)";
+
+        use ShaderNodeId::*;
+        Some(match &self.nodes[idx].node {
+            eSID_ADD(_) | eSID_UV_ADD(_) | eSID_Vertex_UV_ADD(_) => format!("{} + {}", a(), b()),
+            eSID_SUB(_) | eSID_UV_SUB(_) | eSID_VERTEX_UV_SUB(_) => format!("{} - {}", a(), b()),
+            eSID_MUL(_) | eSID_UV_MUL(_) | eSID_Vertex_UV_MUL(_) => format!("{} * {}", a1(), b1()),
+            eSID_MUL2X(_) => format!("{} * {} * 2.0", a1(), b1()),
+            eSID_ConstantMUL(_) | eSID_UV_ConstantMUL(_) | eSID_Vertex_UV_ConstantMUL(_) | eSID_DiffuseMultiplier(_) |
+            eSID_SpecularGlossMultiplier(_) | eSID_SpecularPowerMultiplier(_) | eSID_LODMUL(_) | eSID_UVScrollSpeedFactor(_) => a1(),
+            eSID_Invert(_) | eSID_VertexInvert(_) => format!("vec4(1.0) - {}", a()),
+            eSID_Luminance(_) => format!("vec4(dot({}.rgb, vec3(0.299, 0.587, 0.114)))", a()),
+            eSID_Clamp(_) => format!("clamp({}, 0.0, 1.0)", a()),
+            eSID_Power(_) => a(),
+            eSID_Normalize3D(_) => format!("vec4(normalize({}.xyz), 0.0)", a()),
+            eSID_Blender(_) | eSID_UV_Blender(_) | eSID_Vertex_UV_Blender(_) => self.glsl_blend(idx),
+            eSID_LODBlender(_) => a(),
+            eSID_ColorSelector(_) => format!("vec4({}.w)", a()),
+            eSID_ColorSelectorSmooth(_) => format!("vec4({}.w)", a()),
+            eSID_Combiner4D(_) | eSID_UV_Combiner4D(_) | eSID_VertexCombiner4D(_) | eSID_VertexUV_Combiner4D(_) => self.glsl_combine(idx),
+            eSID_ElapseTime(_) | eSID_VertexElapseTime(_) => String::from("vec4(u_time)"),
+            eSID_LinearFresnel(_) => format!("vec4(1.0 - {})", n_dot_v),
+            eSID_PowFresnel(_) => format!("vec4(pow(1.0 - {}, 5.0))", n_dot_v),
+            eSID_Normal(_) | eSID_VertexNormal(_) | eSID_UntransformedNormal(_) | eSID_VertexUntransformedNormal(_) => String::from("vec4(N, 0.0)"),
+            eSID_Position(_) | eSID_WorldPosition(_) | eSID_VtxWorldPosition(_) => String::from("vec4(v_world_pos, 1.0)"),
+            eSID_ViewPosition(_) => String::from("vec4(u_camera, 1.0)"),
+            eSID_VertexColor(_) | eSID_VertexColorToPixel(_) | eSID_VertexColor_Misc1(_) | eSID_VertexColor_Misc2(_) => String::from("v_color"),
+            eSID_MaterialColor_Diffuse(_) => String::from("u_diffuse"),
+            eSID_MaterialColor_Specular(_) => String::from("u_specular"),
+            eSID_MaterialColor_Emissive(_) | eSID_MaterialColor_EmissiveVTX(_) => String::from("u_emissive"),
+            eSID_UVSource(_) | eSID_VertexUVSource(_) => String::from(UV_VARYING),
+            eSID_VertexUVToPixelUV(_) | eSID_UV2RGB(_) | eSID_RGB2UV(_) | eSID_VertexRGB2UV(_) => a(),
+            eSID_UVScroll(_) | eSID_VertexUVScroll(_) => format!("{} + vec4(u_time, u_time, 0.0, 0.0)", uv()),
+            eSID_UVRotate(_) | eSID_VertexUVRotate(_) => format!("rotate_uv({}, vec2(0.5, 0.5), u_time)", uv()),
+            eSID_SinusFX(_) | eSID_WorldSinusFX_VC(_) => format!("{} + vec4(sin(u_time))", a()),
+            eSID_PulseWave(_) | eSID_VertexPulseWave(_) => String::from("vec4(step(0.5, fract(u_time)))"),
+            eSID_SawWave(_) => String::from("vec4(fract(u_time))"),
+            eSID_Tex2D(_) | eSID_Tex2DYUV(_) | eSID_TexBump(_) | eSID_TexBumpTangent(_) | eSID_BlurTex(_) => format!("texture2D(u_tex{}, {}.xy)", idx, uv()),
+            eSID_RefracTex(_) => format!("texture2D(u_tex{}, v_uv0 + {}.xy)", idx, a()),
+            eSID_RGB_Ramp(_) => format!("texture2D(u_tex{}, vec2({}.x, 0.5))", idx, a()),
+            _ => return None
+        })
+    }
+
+    /// Nodes among the given ones that sample a texture, each gets its own sampler as their texture slots aren't decoded
+    fn glsl_texture_nodes(&self, nodes: &[usize]) -> Vec<usize> {
+        nodes.iter().copied().filter(|idx| matches!(&self.nodes[*idx].node,
+            ShaderNodeId::eSID_Tex2D(_) | ShaderNodeId::eSID_Tex2DYUV(_) | ShaderNodeId::eSID_TexBump(_) | ShaderNodeId::eSID_TexBumpTangent(_) |
+            ShaderNodeId::eSID_BlurTex(_) | ShaderNodeId::eSID_RefracTex(_) | ShaderNodeId::eSID_RGB_Ramp(_)
+        )).collect()
+    }
+
+    /// Nodes feeding `node`, ordered so every node comes after its inputs
+    fn glsl_order(&self, node: usize) -> Vec<usize> {
+        fn visit(graph: &ShaderGraph, node: usize, state: &mut [u8], order: &mut Vec<usize>) {
+            // 1 = on the stack, 2 = done, a link back onto the stack is a cycle and gets skipped
+            if state[node] != 0 {
+                return;
+            }
+            state[node] = 1;
+            for input in graph.nodes[node].inputs.iter().flatten() {
+                visit(graph, input.node, state, order);
+            }
+            state[node] = 2;
+            order.push(node);
+        }
+
+        let mut state = vec![0; self.nodes.len()];
+        let mut order = Vec::new();
+        for input in self.nodes[node].inputs.iter().flatten() {
+            visit(self, input.node, &mut state, &mut order);
+        }
+        order
+    }
+
+    /// Approximate GLSL for the graph in the `##vert`/`##frag` layout used by `ggl::ShaderCache`, None without a main output.
+    /// Nodes that can't be translated are emitted as marked stubs that output magenta.
+    pub fn to_glsl(&self) -> Option<String> {
+        let main = self.main_output()?;
+        let order = self.glsl_order(main);
+
+        let mut src = String::new();
+        writeln!(src, "##vert").ok()?;
+        src.push_str(VERTEX_SOURCE);
+        writeln!(src, "\n##frag").ok()?;
+        src.push_str(FRAGMENT_PREAMBLE);
+        for idx in self.glsl_texture_nodes(&order) {
+            writeln!(src, "uniform sampler2D u_tex{};", idx).ok()?;
+        }
+
+        writeln!(src, "\nvoid main(void)\n{{").ok()?;
+        writeln!(src, "    vec3 N = normalize(v_normal);").ok()?;
+        writeln!(src, "    vec3 V = normalize(u_camera - v_world_pos);").ok()?;
+
+        let mut stubs = 0;
+        for idx in order {
+            let node = &self.nodes[idx];
+            match self.glsl_node_expr(idx) {
+                Some(expr) => writeln!(src, "    vec4 n{} = {}; // {}", idx, expr, node.get_id()).ok()?,
+                None => {
+                    stubs += 1;
+                    writeln!(src, "    // STUB: {} is not translated", node.get_id()).ok()?;
+                    writeln!(src, "    vec4 n{} = {};", idx, STUB_VALUE).ok()?;
+                }
+            }
+        }
+
+        let pin = |pin: u32| pin as usize;
+        let diffuse = self.glsl_input(main, pin(main_output_pin::DIFFUSE), "u_diffuse");
+        let alpha = if self.is_input_connected(main, pin(main_output_pin::ALPHA)) {
+            format!("{}.x", self.glsl_input(main, pin(main_output_pin::ALPHA), ""))
+        } else {
+            String::from("diffuse.a")
+        };
+
+        writeln!(src).ok()?;
+        writeln!(src, "    vec4 diffuse = {};", diffuse).ok()?;
+        writeln!(src, "    float alpha = {};", alpha).ok()?;
+        if self.is_input_connected(main, pin(main_output_pin::NORMAL)) {
+            // the graph's normal is in tangent space, there are no tangents here so it just bends the vertex normal
+            writeln!(src, "    N = normalize(N + ({}.xyz * 2.0 - 1.0));", self.glsl_input(main, pin(main_output_pin::NORMAL), "")).ok()?;
+        }
+        writeln!(src, "    vec3 specular = {}.rgb;", self.glsl_input(main, pin(main_output_pin::SPECULAR), "vec4(0.0)")).ok()?;
+        writeln!(src, "    vec3 emissive = {}.rgb;", self.glsl_input(main, pin(main_output_pin::EMISSIVE), "vec4(0.0)")).ok()?;
+        writeln!(src, "    vec3 L = normalize(u_light_dir);").ok()?;
+        writeln!(src, "    float diffuse_light = 0.3 + 0.7 * max(dot(N, L), 0.0);").ok()?;
+        writeln!(src, "    float specular_light = pow(max(dot(N, normalize(L + V)), 0.0), 32.0);").ok()?;
+        writeln!(src, "    gl_FragColor = vec4(diffuse.rgb * diffuse_light + specular * specular_light + emissive, alpha);").ok()?;
+        writeln!(src, "}}").ok()?;
+
+        if stubs > 0 {
+            src = src.replacen("##frag\n", &format!("##frag\n// {} node(s) could not be translated and output magenta, search for STUB\n", stubs), 1);
+        }
+
+        Some(src)
+    }
+}
@@ -1,4 +1,8 @@
 pub mod node_ids;
+mod classify;
+mod glsl;
+
+pub use classify::*;
 
 use std::io::{Read, Seek, Cursor, SeekFrom};
 use byteorder::{ReadBytesExt, LittleEndian};
@@ -27,16 +31,91 @@ pub struct ShaderNode {
     id: String,
     pub node: ShaderNodeId,
     pub unk_01: u32,
+    /// size of the node's payload, following the size itself
+    pub payload_size: u32,
+    /// output pin feeding each of the node's inputs
+    pub inputs: Vec<Option<ShaderPin>>,
+    /// payload bytes that weren't decoded, the params before the input table or the whole rest when no table fits
+    pub extra: Vec<u8>,
+    /// the payload size didn't line up with the next node, so the node was found by seeking for its id instead
+    pub recovered: bool,
+}
+
+/// A pin of a node in the same graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaderPin {
+    pub node: usize,
+    pub pin: u32,
+}
+
+/// Output pin `from` feeds input pin `to`
+#[derive(Debug, Clone, Copy)]
+pub struct ShaderEdge {
+    pub from: ShaderPin,
+    pub to: ShaderPin,
 }
 
 impl ShaderNode {
     pub fn get_id(&self) -> &str {
         &self.id
     }
+
+    pub fn is_main_output(&self) -> bool {
+        matches!(self.node, ShaderNodeId::eSID_MainOutput(_))
+    }
+}
+
+impl ShaderGraph {
+    pub fn edges(&self) -> Vec<ShaderEdge> {
+        self.nodes.iter().enumerate()
+            .flat_map(|(to, node)| node.inputs.iter().enumerate().filter_map(move |(pin, input)| {
+                input.map(|from| ShaderEdge { from, to: ShaderPin { node: to, pin: pin as u32 } })
+            }))
+            .collect()
+    }
+
+    pub fn main_output(&self) -> Option<usize> {
+        self.nodes.iter().position(|node| node.is_main_output())
+    }
+
+    /// Number of output pins used by other nodes, at least one
+    pub fn num_outputs(&self, node: usize) -> u32 {
+        self.nodes.iter()
+            .flat_map(|n| n.inputs.iter().flatten())
+            .filter(|input| input.node == node)
+            .map(|input| input.pin + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Every node that feeds into `node`, directly or not
+    pub fn upstream(&self, node: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut found = Vec::new();
+        let mut stack = vec![node];
+        while let Some(idx) = stack.pop() {
+            for input in self.nodes[idx].inputs.iter().flatten() {
+                if !seen[input.node] {
+                    seen[input.node] = true;
+                    found.push(input.node);
+                    stack.push(input.node);
+                }
+            }
+        }
+        found
+    }
+
+    /// Nodes that read from `node`
+    pub fn consumers(&self, node: usize) -> Vec<usize> {
+        self.nodes.iter().enumerate()
+            .filter(|(_, n)| n.inputs.iter().flatten().any(|input| input.node == node))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
 }
 
 impl ArchetypeImpl for VisualShader {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.version = cursor.read_u16::<LittleEndian>()?;
@@ -51,7 +130,7 @@ impl ArchetypeImpl for VisualShader {
             cursor.seek(SeekFrom::Current(-24))?;
         }
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -61,7 +140,7 @@ impl ArchetypeImpl for VisualShader {
 }
 
 impl VisualShader {
-    fn read_graph<T: Seek + Read>(rdr: &mut T) -> Result<ShaderGraph, YetiIOError> {
+    fn read_graph(rdr: &mut Cursor<&[u8]>) -> Result<ShaderGraph, YetiIOError> {
         let mut graph = ShaderGraph {
             unk_01: rdr.read_u32::<LittleEndian>()?,
             unk_02: rdr.read_u32::<LittleEndian>()?,
@@ -73,12 +152,19 @@ impl VisualShader {
         };
 
         let mut nodes: Vec<ShaderNode> = Vec::with_capacity(graph.num_nodes as usize);
-        let mut i = 0;
-        while i < graph.num_nodes {
-            nodes.push(Self::read_node(rdr)?);
-            Self::seek_to_next_node(rdr);
-            i += 1;
-        };
+        for i in 0..graph.num_nodes {
+            nodes.push(Self::read_node(rdr, i + 1 == graph.num_nodes)?);
+        }
+        // drop links to nodes that don't exist rather than indexing out of the graph later
+        let num_nodes = nodes.len();
+        for node in nodes.iter_mut() {
+            for input in node.inputs.iter_mut() {
+                if input.is_some_and(|pin| pin.node >= num_nodes) {
+                    log::warn!("shader node {} links to node {} but the graph only has {}", node.id, input.unwrap().node, num_nodes);
+                    *input = None;
+                }
+            }
+        }
         graph.nodes = nodes;
 
         Ok(graph)
@@ -94,28 +180,85 @@ impl VisualShader {
         Ok(id)
     }
 
-    fn read_node<T: Read + Seek>(rdr: &mut T) -> Result<ShaderNode, YetiIOError> {
+    fn read_node(rdr: &mut Cursor<&[u8]>, last: bool) -> Result<ShaderNode, YetiIOError> {
         let id = Self::read_node_id(rdr)?;
         let unk_01 = rdr.read_u32::<LittleEndian>()?;
+        let payload_size = rdr.read_u32::<LittleEndian>()?;
+
+        let buf = *rdr.get_ref();
+        let start = rdr.position() as usize;
+        let end = start + payload_size as usize;
+
+        // the last node of a graph is followed by the next graph's header rather than a node
+        if end <= buf.len() && (last || end == buf.len() || Self::is_node_start(&buf[end..])) {
+            let mut payload = Cursor::new(&buf[start..end]);
+            if let Ok(node) = load_node_id(&id, &mut payload) {
+                let read = (payload.position() as usize).min(payload_size as usize);
+                let rest = &buf[start + read..end];
+                rdr.set_position(end as u64);
 
-        let node_id: ShaderNodeId = match id.as_str() {
-            "eSID_Comment" => {
-                //info!("loading comment!");
-                ShaderNodeId::eSID_Comment(load_eSID_Comment(rdr)?)
-            },
-            _ => {
-                Self::seek_to_next_node(rdr);
-                ShaderNodeId::Invalid
+                // input links trail the params, anything that doesn't fit the table is kept as unparsed bytes
+                let (inputs, extra) = match Self::find_node_inputs(rest) {
+                    Some((params_len, inputs)) => (inputs, rest[..params_len].to_vec()),
+                    None => (Vec::new(), rest.to_vec())
+                };
+                return Ok(ShaderNode {
+                    id,
+                    unk_01,
+                    payload_size,
+                    inputs,
+                    extra,
+                    node,
+                    recovered: false,
+                });
             }
-        };
+        }
+
+        log::warn!("shader node {} payload size {:#X} at {:#X} doesn't line up, seeking to the next node", id, payload_size, start);
+        let node = load_node_id(&id, rdr).unwrap_or(ShaderNodeId::Invalid);
+        rdr.set_position(start as u64);
+        Self::seek_to_next_node(rdr);
 
         Ok(ShaderNode {
             id,
             unk_01,
-            node: node_id,
+            payload_size,
+            inputs: Vec::new(),
+            extra: Vec::new(),
+            node,
+            recovered: true,
         })
     }
 
+    /// Input table at the end of the payload and the length of the params before it.
+    /// Most params aren't decoded, so the table is taken from the first 4 byte aligned offset where it fits exactly.
+    fn find_node_inputs(buf: &[u8]) -> Option<(usize, Vec<Option<ShaderPin>>)> {
+        (0..buf.len()).step_by(4)
+            .find_map(|offset| Self::read_node_inputs(&buf[offset..]).map(|inputs| (offset, inputs)))
+    }
+
+    /// u32 input count, then a (node index, output pin) pair per input, 0xFFFFFFFF when the input isn't connected
+    fn read_node_inputs(buf: &[u8]) -> Option<Vec<Option<ShaderPin>>> {
+        let mut cursor = Cursor::new(buf);
+        let count = cursor.read_u32::<LittleEndian>().ok()? as usize;
+        if buf.len() != 4 + count * 8 {
+            return None;
+        }
+
+        (0..count).map(|_| {
+            let node = cursor.read_u32::<LittleEndian>().ok()?;
+            let pin = cursor.read_u32::<LittleEndian>().ok()?;
+            Some(match node {
+                0xFFFFFFFF => None,
+                node => Some(ShaderPin { node: node as usize, pin })
+            })
+        }).collect()
+    }
+
+    fn is_node_start(buf: &[u8]) -> bool {
+        buf.len() >= 8 && &buf[4..8] == b"eSID"
+    }
+
     fn seek_to_next_node<T: Seek + Read>(rdr: &mut T) -> bool {
         fn check_esid(buf: &[u8]) -> bool {
             buf[0] == b'e' &&
@@ -5,691 +5,481 @@ use super::*;
 use std::io::{Read, Seek, SeekFrom};
 use byteorder::{ReadBytesExt, LittleEndian};
 
-pub fn load_eSID_ADD<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ADD, YetiIOError> {
-
-    Ok(eSID_ADD {
-        
-    })
-}
-pub fn load_eSID_AmbientCube<T: Read + Seek>(rdr: &mut T) -> Result<eSID_AmbientCube, YetiIOError> {
-
-    Ok(eSID_AmbientCube {
-        
-    })
-}
-pub fn load_eSID_AmbientOcclusion<T: Read + Seek>(rdr: &mut T) -> Result<eSID_AmbientOcclusion, YetiIOError> {
-
-    Ok(eSID_AmbientOcclusion {
-        
-    })
-}
-pub fn load_eSID_Blender<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Blender, YetiIOError> {
-
-    Ok(eSID_Blender {
-        
-    })
-}
-pub fn load_eSID_BlurTex<T: Read + Seek>(rdr: &mut T) -> Result<eSID_BlurTex, YetiIOError> {
-
-    Ok(eSID_BlurTex {
-        
-    })
-}
-pub fn load_eSID_Clamp<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Clamp, YetiIOError> {
-
-    Ok(eSID_Clamp {
-        
-    })
-}
-pub fn load_eSID_ColorSelector<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ColorSelector, YetiIOError> {
-
-    Ok(eSID_ColorSelector {
-        
-    })
-}
-pub fn load_eSID_ColorSelectorSmooth<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ColorSelectorSmooth, YetiIOError> {
-
-    Ok(eSID_ColorSelectorSmooth {
-        
-    })
-}
-pub fn load_eSID_Combiner4D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Combiner4D, YetiIOError> {
-
-    Ok(eSID_Combiner4D {
-        
-    })
-}
-pub fn load_eSID_Comment<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Comment, YetiIOError> {
+/// length prefixed string, nul terminated and padded to 4 bytes
+fn read_padded_string<T: Read + Seek>(rdr: &mut T) -> Result<String, YetiIOError> {
+    let len = rdr.read_u32::<LittleEndian>()?;
 
-    rdr.read_u32::<LittleEndian>()?;
+    // the reader can be the whole file when a node was recovered, so don't trust the length
+    let pos = rdr.stream_position()?;
+    let remaining = rdr.seek(SeekFrom::End(0))? - pos;
+    rdr.seek(SeekFrom::Start(pos))?;
+    if len as u64 > remaining {
+        return Err(format!("string length {:#X} is past the end of the data ({:#X} bytes left)", len, remaining).into());
+    }
 
-    let len = rdr.read_u32::<LittleEndian>()?;
-    //dbg!(&len);
     let mut v: Vec<u8> = vec![0; len as usize];
-    for b in v.iter_mut() {
-        *b = rdr.read_u8()?;
-    }
-    //dbg!(&v);
-    
+    rdr.read_exact(&mut v)?;
+
     let rem = len % 4;
     rdr.seek(SeekFrom::Current(4 - rem as i64))?;
 
-    let comment = String::from_utf8(v)?;
-    //info!("comment: {}", &comment);
-
-    Ok(eSID_Comment {
-        comment,
-    })
+    Ok(String::from_utf8(v)?)
 }
-pub fn load_eSID_ConstantColor<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ConstantColor, YetiIOError> {
 
-    Ok(eSID_ConstantColor {
-        
-    })
+pub fn load_eSID_ADD<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ADD, YetiIOError> {
+    Ok(eSID_ADD { })
 }
-pub fn load_eSID_ConstantMUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ConstantMUL, YetiIOError> {
-
-    Ok(eSID_ConstantMUL {
-        
-    })
+pub fn load_eSID_AmbientCube<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_AmbientCube, YetiIOError> {
+    Ok(eSID_AmbientCube { })
 }
-pub fn load_eSID_ConstantUVWQ<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ConstantUVWQ, YetiIOError> {
-
-    Ok(eSID_ConstantUVWQ {
-        
-    })
-}
-pub fn load_eSID_ConstantVector<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ConstantVector, YetiIOError> {
-
-    Ok(eSID_ConstantVector {
-        
-    })
-}
-pub fn load_eSID_CustomCode<T: Read + Seek>(rdr: &mut T) -> Result<eSID_CustomCode, YetiIOError> {
-
-    Ok(eSID_CustomCode {
-        
-    })
+pub fn load_eSID_AmbientOcclusion<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_AmbientOcclusion, YetiIOError> {
+    Ok(eSID_AmbientOcclusion { })
 }
-pub fn load_eSID_CustomCodeVtx<T: Read + Seek>(rdr: &mut T) -> Result<eSID_CustomCodeVtx, YetiIOError> {
-
-    Ok(eSID_CustomCodeVtx {
-        
-    })
+pub fn load_eSID_Blender<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Blender, YetiIOError> {
+    Ok(eSID_Blender { })
 }
-pub fn load_eSID_DepthAlpha<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DepthAlpha, YetiIOError> {
-
-    Ok(eSID_DepthAlpha {
-        
-    })
+pub fn load_eSID_BlurTex<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_BlurTex, YetiIOError> {
+    Ok(eSID_BlurTex { })
 }
-pub fn load_eSID_DepthAlphaOpt<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DepthAlphaOpt, YetiIOError> {
-
-    Ok(eSID_DepthAlphaOpt {
-        
-    })
+pub fn load_eSID_Clamp<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Clamp, YetiIOError> {
+    Ok(eSID_Clamp { })
 }
-pub fn load_eSID_DepthTexture<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DepthTexture, YetiIOError> {
-
-    Ok(eSID_DepthTexture {
-        
-    })
+pub fn load_eSID_ColorSelector<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ColorSelector, YetiIOError> {
+    Ok(eSID_ColorSelector { })
 }
-pub fn load_eSID_DiffuseCube<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DiffuseCube, YetiIOError> {
-
-    Ok(eSID_DiffuseCube {
-        
-    })
+pub fn load_eSID_ColorSelectorSmooth<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ColorSelectorSmooth, YetiIOError> {
+    Ok(eSID_ColorSelectorSmooth { })
 }
-pub fn load_eSID_DiffuseMultiplier<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DiffuseMultiplier, YetiIOError> {
-
-    Ok(eSID_DiffuseMultiplier {
-        
-    })
+pub fn load_eSID_Combiner4D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Combiner4D, YetiIOError> {
+    Ok(eSID_Combiner4D { })
 }
-pub fn load_eSID_DustFXSH<T: Read + Seek>(rdr: &mut T) -> Result<eSID_DustFXSH, YetiIOError> {
-
-    Ok(eSID_DustFXSH {
-        
-    })
-}
-pub fn load_eSID_ElapseTime<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ElapseTime, YetiIOError> {
-
-    Ok(eSID_ElapseTime {
-        
+pub fn load_eSID_Comment<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Comment, YetiIOError> {
+    Ok(eSID_Comment {
+        comment: read_padded_string(rdr)?,
     })
 }
-pub fn load_eSID_FlatChrome<T: Read + Seek>(rdr: &mut T) -> Result<eSID_FlatChrome, YetiIOError> {
-
-    Ok(eSID_FlatChrome {
-        
-    })
+pub fn load_eSID_ConstantColor<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ConstantColor, YetiIOError> {
+    Ok(eSID_ConstantColor { })
 }
-pub fn load_eSID_ForceNoPrepass<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ForceNoPrepass, YetiIOError> {
-
-    Ok(eSID_ForceNoPrepass {
-        
-    })
+pub fn load_eSID_ConstantMUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ConstantMUL, YetiIOError> {
+    Ok(eSID_ConstantMUL { })
 }
-pub fn load_eSID_ForceUltraSimpleShader<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ForceUltraSimpleShader, YetiIOError> {
-
-    Ok(eSID_ForceUltraSimpleShader {
-        
-    })
+pub fn load_eSID_ConstantUVWQ<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ConstantUVWQ, YetiIOError> {
+    Ok(eSID_ConstantUVWQ { })
 }
-pub fn load_eSID_ForceVisualPrepass<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ForceVisualPrepass, YetiIOError> {
-
-    Ok(eSID_ForceVisualPrepass {
-        
-    })
+pub fn load_eSID_ConstantVector<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ConstantVector, YetiIOError> {
+    Ok(eSID_ConstantVector { })
 }
-pub fn load_eSID_HeatSelector<T: Read + Seek>(rdr: &mut T) -> Result<eSID_HeatSelector, YetiIOError> {
-
-    Ok(eSID_HeatSelector {
-        
-    })
+pub fn load_eSID_CustomCode<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_CustomCode, YetiIOError> {
+    Ok(eSID_CustomCode { })
 }
-pub fn load_eSID_Invert<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Invert, YetiIOError> {
-
-    Ok(eSID_Invert {
-        
-    })
+pub fn load_eSID_CustomCodeVtx<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_CustomCodeVtx, YetiIOError> {
+    Ok(eSID_CustomCodeVtx { })
 }
-pub fn load_eSID_LODBlender<T: Read + Seek>(rdr: &mut T) -> Result<eSID_LODBlender, YetiIOError> {
-
-    Ok(eSID_LODBlender {
-        
-    })
+pub fn load_eSID_DepthAlpha<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DepthAlpha, YetiIOError> {
+    Ok(eSID_DepthAlpha { })
 }
-pub fn load_eSID_LODMUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_LODMUL, YetiIOError> {
-
-    Ok(eSID_LODMUL {
-        
-    })
+pub fn load_eSID_DepthAlphaOpt<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DepthAlphaOpt, YetiIOError> {
+    Ok(eSID_DepthAlphaOpt { })
 }
-pub fn load_eSID_LightCurve_Back<T: Read + Seek>(rdr: &mut T) -> Result<eSID_LightCurve_Back, YetiIOError> {
-
-    Ok(eSID_LightCurve_Back {
-        
-    })
+pub fn load_eSID_DepthTexture<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DepthTexture, YetiIOError> {
+    Ok(eSID_DepthTexture { })
 }
-pub fn load_eSID_LinearFresnel<T: Read + Seek>(rdr: &mut T) -> Result<eSID_LinearFresnel, YetiIOError> {
-
-    Ok(eSID_LinearFresnel {
-        
-    })
+pub fn load_eSID_DiffuseCube<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DiffuseCube, YetiIOError> {
+    Ok(eSID_DiffuseCube { })
 }
-pub fn load_eSID_Luminance<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Luminance, YetiIOError> {
-
-    Ok(eSID_Luminance {
-        
-    })
+pub fn load_eSID_DiffuseMultiplier<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DiffuseMultiplier, YetiIOError> {
+    Ok(eSID_DiffuseMultiplier { })
 }
-pub fn load_eSID_MUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MUL, YetiIOError> {
-
-    Ok(eSID_MUL {
-        
-    })
+pub fn load_eSID_DustFXSH<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_DustFXSH, YetiIOError> {
+    Ok(eSID_DustFXSH { })
 }
-pub fn load_eSID_MUL2X<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MUL2X, YetiIOError> {
-
-    Ok(eSID_MUL2X {
-        
-    })
+pub fn load_eSID_ElapseTime<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ElapseTime, YetiIOError> {
+    Ok(eSID_ElapseTime { })
 }
-pub fn load_eSID_MainOutput<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MainOutput, YetiIOError> {
-
-    Ok(eSID_MainOutput {
-        
-    })
+pub fn load_eSID_FlatChrome<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_FlatChrome, YetiIOError> {
+    Ok(eSID_FlatChrome { })
 }
-pub fn load_eSID_MaterialColor_Diffuse<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MaterialColor_Diffuse, YetiIOError> {
-
-    Ok(eSID_MaterialColor_Diffuse {
-        
-    })
+pub fn load_eSID_ForceNoPrepass<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ForceNoPrepass, YetiIOError> {
+    Ok(eSID_ForceNoPrepass { })
 }
-pub fn load_eSID_MaterialColor_Emissive<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MaterialColor_Emissive, YetiIOError> {
-
-    Ok(eSID_MaterialColor_Emissive {
-        
-    })
+pub fn load_eSID_ForceUltraSimpleShader<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ForceUltraSimpleShader, YetiIOError> {
+    Ok(eSID_ForceUltraSimpleShader { })
 }
-pub fn load_eSID_MaterialColor_EmissiveVTX<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MaterialColor_EmissiveVTX, YetiIOError> {
-
-    Ok(eSID_MaterialColor_EmissiveVTX {
-        
-    })
+pub fn load_eSID_ForceVisualPrepass<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ForceVisualPrepass, YetiIOError> {
+    Ok(eSID_ForceVisualPrepass { })
 }
-pub fn load_eSID_MaterialColor_Specular<T: Read + Seek>(rdr: &mut T) -> Result<eSID_MaterialColor_Specular, YetiIOError> {
-
-    Ok(eSID_MaterialColor_Specular {
-        
-    })
+pub fn load_eSID_HeatSelector<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_HeatSelector, YetiIOError> {
+    Ok(eSID_HeatSelector { })
 }
-pub fn load_eSID_Normal<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Normal, YetiIOError> {
-
-    Ok(eSID_Normal {
-        
-    })
+pub fn load_eSID_Invert<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Invert, YetiIOError> {
+    Ok(eSID_Invert { })
 }
-pub fn load_eSID_Normalize3D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Normalize3D, YetiIOError> {
-
-    Ok(eSID_Normalize3D {
-        
-    })
+pub fn load_eSID_LODBlender<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_LODBlender, YetiIOError> {
+    Ok(eSID_LODBlender { })
 }
-pub fn load_eSID_OffsetBump<T: Read + Seek>(rdr: &mut T) -> Result<eSID_OffsetBump, YetiIOError> {
-
-    Ok(eSID_OffsetBump {
-        
-    })
+pub fn load_eSID_LODMUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_LODMUL, YetiIOError> {
+    Ok(eSID_LODMUL { })
 }
-pub fn load_eSID_PixelAverageColor<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelAverageColor, YetiIOError> {
-
-    Ok(eSID_PixelAverageColor {
-        
-    })
+pub fn load_eSID_LightCurve_Back<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_LightCurve_Back, YetiIOError> {
+    Ok(eSID_LightCurve_Back { })
 }
-pub fn load_eSID_PixelColor_Misc<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelColor_Misc, YetiIOError> {
-
-    Ok(eSID_PixelColor_Misc {
-        
-    })
+pub fn load_eSID_LinearFresnel<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_LinearFresnel, YetiIOError> {
+    Ok(eSID_LinearFresnel { })
 }
-pub fn load_eSID_PixelColor_Misc2<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelColor_Misc2, YetiIOError> {
-
-    Ok(eSID_PixelColor_Misc2 {
-        
-    })
+pub fn load_eSID_Luminance<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Luminance, YetiIOError> {
+    Ok(eSID_Luminance { })
 }
-pub fn load_eSID_PixelSH<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelSH, YetiIOError> {
-
-    Ok(eSID_PixelSH {
-        
-    })
+pub fn load_eSID_MUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MUL, YetiIOError> {
+    Ok(eSID_MUL { })
 }
-pub fn load_eSID_PixelUVBoxAnimBlend<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelUVBoxAnimBlend, YetiIOError> {
-
-    Ok(eSID_PixelUVBoxAnimBlend {
-        
-    })
+pub fn load_eSID_MUL2X<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MUL2X, YetiIOError> {
+    Ok(eSID_MUL2X { })
 }
-pub fn load_eSID_PixelViewToWorld<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PixelViewToWorld, YetiIOError> {
-
-    Ok(eSID_PixelViewToWorld {
-        
-    })
+pub fn load_eSID_MainOutput<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MainOutput, YetiIOError> {
+    Ok(eSID_MainOutput { })
 }
-pub fn load_eSID_Position<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Position, YetiIOError> {
-
-    Ok(eSID_Position {
-        
-    })
+pub fn load_eSID_MaterialColor_Diffuse<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MaterialColor_Diffuse, YetiIOError> {
+    Ok(eSID_MaterialColor_Diffuse { })
 }
-pub fn load_eSID_PowFresnel<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PowFresnel, YetiIOError> {
-
-    Ok(eSID_PowFresnel {
-        
-    })
+pub fn load_eSID_MaterialColor_Emissive<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MaterialColor_Emissive, YetiIOError> {
+    Ok(eSID_MaterialColor_Emissive { })
 }
-pub fn load_eSID_Power<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Power, YetiIOError> {
-
-    Ok(eSID_Power {
-        
-    })
+pub fn load_eSID_MaterialColor_EmissiveVTX<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MaterialColor_EmissiveVTX, YetiIOError> {
+    Ok(eSID_MaterialColor_EmissiveVTX { })
 }
-pub fn load_eSID_PulseWave<T: Read + Seek>(rdr: &mut T) -> Result<eSID_PulseWave, YetiIOError> {
-
-    Ok(eSID_PulseWave {
-        
-    })
+pub fn load_eSID_MaterialColor_Specular<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_MaterialColor_Specular, YetiIOError> {
+    Ok(eSID_MaterialColor_Specular { })
 }
-pub fn load_eSID_RGB2UV<T: Read + Seek>(rdr: &mut T) -> Result<eSID_RGB2UV, YetiIOError> {
-
-    Ok(eSID_RGB2UV {
-        
-    })
+pub fn load_eSID_Normal<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Normal, YetiIOError> {
+    Ok(eSID_Normal { })
 }
-pub fn load_eSID_RGB_Ramp<T: Read + Seek>(rdr: &mut T) -> Result<eSID_RGB_Ramp, YetiIOError> {
-
-    Ok(eSID_RGB_Ramp {
-        
-    })
+pub fn load_eSID_Normalize3D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Normalize3D, YetiIOError> {
+    Ok(eSID_Normalize3D { })
 }
-pub fn load_eSID_RefracTex<T: Read + Seek>(rdr: &mut T) -> Result<eSID_RefracTex, YetiIOError> {
-
-    Ok(eSID_RefracTex {
-        
-    })
+pub fn load_eSID_OffsetBump<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_OffsetBump, YetiIOError> {
+    Ok(eSID_OffsetBump { })
 }
-pub fn load_eSID_SUB<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SUB, YetiIOError> {
-
-    Ok(eSID_SUB {
-        
-    })
+pub fn load_eSID_PixelAverageColor<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelAverageColor, YetiIOError> {
+    Ok(eSID_PixelAverageColor { })
 }
-pub fn load_eSID_SawWave<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SawWave, YetiIOError> {
-
-    Ok(eSID_SawWave {
-        
-    })
+pub fn load_eSID_PixelColor_Misc<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelColor_Misc, YetiIOError> {
+    Ok(eSID_PixelColor_Misc { })
 }
-pub fn load_eSID_ShadowValue<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ShadowValue, YetiIOError> {
-
-    Ok(eSID_ShadowValue {
-        
-    })
+pub fn load_eSID_PixelColor_Misc2<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelColor_Misc2, YetiIOError> {
+    Ok(eSID_PixelColor_Misc2 { })
 }
-pub fn load_eSID_SinusFX<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SinusFX, YetiIOError> {
-
-    Ok(eSID_SinusFX {
-        
-    })
+pub fn load_eSID_PixelSH<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelSH, YetiIOError> {
+    Ok(eSID_PixelSH { })
 }
-pub fn load_eSID_SpecularCubeMap<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SpecularCubeMap, YetiIOError> {
-
-    Ok(eSID_SpecularCubeMap {
-        
-    })
+pub fn load_eSID_PixelUVBoxAnimBlend<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelUVBoxAnimBlend, YetiIOError> {
+    Ok(eSID_PixelUVBoxAnimBlend { })
 }
-pub fn load_eSID_SpecularGlossMultiplier<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SpecularGlossMultiplier, YetiIOError> {
-
-    Ok(eSID_SpecularGlossMultiplier {
-        
-    })
+pub fn load_eSID_PixelViewToWorld<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PixelViewToWorld, YetiIOError> {
+    Ok(eSID_PixelViewToWorld { })
 }
-pub fn load_eSID_SpecularPowerMultiplier<T: Read + Seek>(rdr: &mut T) -> Result<eSID_SpecularPowerMultiplier, YetiIOError> {
-
-    Ok(eSID_SpecularPowerMultiplier {
-        
-    })
+pub fn load_eSID_Position<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Position, YetiIOError> {
+    Ok(eSID_Position { })
 }
-pub fn load_eSID_Tangent2Screen<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Tangent2Screen, YetiIOError> {
-
-    Ok(eSID_Tangent2Screen {
-        
-    })
+pub fn load_eSID_PowFresnel<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PowFresnel, YetiIOError> {
+    Ok(eSID_PowFresnel { })
 }
-pub fn load_eSID_Tex2D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Tex2D, YetiIOError> {
-
-    Ok(eSID_Tex2D {
-        
-    })
+pub fn load_eSID_Power<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Power, YetiIOError> {
+    Ok(eSID_Power { })
 }
-pub fn load_eSID_Tex2DYUV<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Tex2DYUV, YetiIOError> {
-
-    Ok(eSID_Tex2DYUV {
-        
-    })
+pub fn load_eSID_PulseWave<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_PulseWave, YetiIOError> {
+    Ok(eSID_PulseWave { })
 }
-pub fn load_eSID_TexBump<T: Read + Seek>(rdr: &mut T) -> Result<eSID_TexBump, YetiIOError> {
-
-    Ok(eSID_TexBump {
-        
-    })
+pub fn load_eSID_RGB2UV<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_RGB2UV, YetiIOError> {
+    Ok(eSID_RGB2UV { })
 }
-pub fn load_eSID_TexBumpTangent<T: Read + Seek>(rdr: &mut T) -> Result<eSID_TexBumpTangent, YetiIOError> {
-
-    Ok(eSID_TexBumpTangent {
-        
-    })
+pub fn load_eSID_RGB_Ramp<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_RGB_Ramp, YetiIOError> {
+    Ok(eSID_RGB_Ramp { })
 }
-pub fn load_eSID_UV2RGB<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV2RGB, YetiIOError> {
-
-    Ok(eSID_UV2RGB {
-        
-    })
+pub fn load_eSID_RefracTex<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_RefracTex, YetiIOError> {
+    Ok(eSID_RefracTex { })
 }
-pub fn load_eSID_UVRotate<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UVRotate, YetiIOError> {
-
-    Ok(eSID_UVRotate {
-        
-    })
+pub fn load_eSID_SUB<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SUB, YetiIOError> {
+    Ok(eSID_SUB { })
 }
-pub fn load_eSID_UVScroll<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UVScroll, YetiIOError> {
-
-    Ok(eSID_UVScroll {
-        
-    })
+pub fn load_eSID_SawWave<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SawWave, YetiIOError> {
+    Ok(eSID_SawWave { })
 }
-pub fn load_eSID_UVScrollSpeedFactor<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UVScrollSpeedFactor, YetiIOError> {
-
-    Ok(eSID_UVScrollSpeedFactor {
-        
-    })
+pub fn load_eSID_ShadowValue<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ShadowValue, YetiIOError> {
+    Ok(eSID_ShadowValue { })
 }
-pub fn load_eSID_UVSource<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UVSource, YetiIOError> {
-
-    Ok(eSID_UVSource {
-        
-    })
+pub fn load_eSID_SinusFX<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SinusFX, YetiIOError> {
+    Ok(eSID_SinusFX { })
 }
-pub fn load_eSID_UV_ADD<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_ADD, YetiIOError> {
-
-    Ok(eSID_UV_ADD {
-        
-    })
+pub fn load_eSID_SpecularCubeMap<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SpecularCubeMap, YetiIOError> {
+    Ok(eSID_SpecularCubeMap { })
 }
-pub fn load_eSID_UV_Blender<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_Blender, YetiIOError> {
-
-    Ok(eSID_UV_Blender {
-        
-    })
+pub fn load_eSID_SpecularGlossMultiplier<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SpecularGlossMultiplier, YetiIOError> {
+    Ok(eSID_SpecularGlossMultiplier { })
 }
-pub fn load_eSID_UV_Combiner4D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_Combiner4D, YetiIOError> {
-
-    Ok(eSID_UV_Combiner4D {
-        
-    })
+pub fn load_eSID_SpecularPowerMultiplier<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_SpecularPowerMultiplier, YetiIOError> {
+    Ok(eSID_SpecularPowerMultiplier { })
 }
-pub fn load_eSID_UV_ConstantMUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_ConstantMUL, YetiIOError> {
-
-    Ok(eSID_UV_ConstantMUL {
-        
-    })
+pub fn load_eSID_Tangent2Screen<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Tangent2Screen, YetiIOError> {
+    Ok(eSID_Tangent2Screen { })
 }
-pub fn load_eSID_UV_DUDV<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_DUDV, YetiIOError> {
-
-    Ok(eSID_UV_DUDV {
-        
-    })
+pub fn load_eSID_Tex2D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Tex2D, YetiIOError> {
+    Ok(eSID_Tex2D { })
 }
-pub fn load_eSID_UV_MUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_MUL, YetiIOError> {
-
-    Ok(eSID_UV_MUL {
-        
-    })
+pub fn load_eSID_Tex2DYUV<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Tex2DYUV, YetiIOError> {
+    Ok(eSID_Tex2DYUV { })
 }
-pub fn load_eSID_UV_SUB<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UV_SUB, YetiIOError> {
-
-    Ok(eSID_UV_SUB {
-        
-    })
+pub fn load_eSID_TexBump<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_TexBump, YetiIOError> {
+    Ok(eSID_TexBump { })
 }
-pub fn load_eSID_UntransformedNormal<T: Read + Seek>(rdr: &mut T) -> Result<eSID_UntransformedNormal, YetiIOError> {
-
-    Ok(eSID_UntransformedNormal {
-        
-    })
+pub fn load_eSID_TexBumpTangent<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_TexBumpTangent, YetiIOError> {
+    Ok(eSID_TexBumpTangent { })
 }
-pub fn load_eSID_VERTEX_UV_SUB<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VERTEX_UV_SUB, YetiIOError> {
-
-    Ok(eSID_VERTEX_UV_SUB {
-        
-    })
+pub fn load_eSID_UV2RGB<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV2RGB, YetiIOError> {
+    Ok(eSID_UV2RGB { })
 }
-pub fn load_eSID_VertexColor<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexColor, YetiIOError> {
-
-    Ok(eSID_VertexColor {
-        
-    })
+pub fn load_eSID_UVRotate<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UVRotate, YetiIOError> {
+    Ok(eSID_UVRotate { })
 }
-pub fn load_eSID_VertexColorToPixel<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexColorToPixel, YetiIOError> {
-
-    Ok(eSID_VertexColorToPixel {
-        
-    })
+pub fn load_eSID_UVScroll<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UVScroll, YetiIOError> {
+    Ok(eSID_UVScroll { })
 }
-pub fn load_eSID_VertexColor_Misc1<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexColor_Misc1, YetiIOError> {
-
-    Ok(eSID_VertexColor_Misc1 {
-        
-    })
+pub fn load_eSID_UVScrollSpeedFactor<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UVScrollSpeedFactor, YetiIOError> {
+    Ok(eSID_UVScrollSpeedFactor { })
 }
-pub fn load_eSID_VertexColor_Misc2<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexColor_Misc2, YetiIOError> {
-
-    Ok(eSID_VertexColor_Misc2 {
-        
-    })
+pub fn load_eSID_UVSource<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UVSource, YetiIOError> {
+    Ok(eSID_UVSource { })
 }
-pub fn load_eSID_VertexCombiner4D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexCombiner4D, YetiIOError> {
-
-    Ok(eSID_VertexCombiner4D {
-        
-    })
+pub fn load_eSID_UV_ADD<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_ADD, YetiIOError> {
+    Ok(eSID_UV_ADD { })
 }
-pub fn load_eSID_VertexConstUVWQ<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexConstUVWQ, YetiIOError> {
-
-    Ok(eSID_VertexConstUVWQ {
-        
-    })
+pub fn load_eSID_UV_Blender<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_Blender, YetiIOError> {
+    Ok(eSID_UV_Blender { })
 }
-pub fn load_eSID_VertexConstantVector<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexConstantVector, YetiIOError> {
-
-    Ok(eSID_VertexConstantVector {
-        
-    })
+pub fn load_eSID_UV_Combiner4D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_Combiner4D, YetiIOError> {
+    Ok(eSID_UV_Combiner4D { })
 }
-pub fn load_eSID_VertexElapseTime<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexElapseTime, YetiIOError> {
-
-    Ok(eSID_VertexElapseTime {
-        
-    })
+pub fn load_eSID_UV_ConstantMUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_ConstantMUL, YetiIOError> {
+    Ok(eSID_UV_ConstantMUL { })
 }
-pub fn load_eSID_VertexInvert<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexInvert, YetiIOError> {
-
-    Ok(eSID_VertexInvert {
-        
-    })
+pub fn load_eSID_UV_DUDV<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_DUDV, YetiIOError> {
+    Ok(eSID_UV_DUDV { })
 }
-pub fn load_eSID_VertexNormal<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexNormal, YetiIOError> {
-
-    Ok(eSID_VertexNormal {
-        
-    })
+pub fn load_eSID_UV_MUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_MUL, YetiIOError> {
+    Ok(eSID_UV_MUL { })
 }
-pub fn load_eSID_VertexPulseWave<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexPulseWave, YetiIOError> {
-
-    Ok(eSID_VertexPulseWave {
-        
-    })
+pub fn load_eSID_UV_SUB<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UV_SUB, YetiIOError> {
+    Ok(eSID_UV_SUB { })
 }
-pub fn load_eSID_VertexRGB2UV<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexRGB2UV, YetiIOError> {
-
-    Ok(eSID_VertexRGB2UV {
-        
-    })
+pub fn load_eSID_UntransformedNormal<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_UntransformedNormal, YetiIOError> {
+    Ok(eSID_UntransformedNormal { })
 }
-pub fn load_eSID_VertexUVRotate<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUVRotate, YetiIOError> {
-
-    Ok(eSID_VertexUVRotate {
-        
-    })
+pub fn load_eSID_VERTEX_UV_SUB<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VERTEX_UV_SUB, YetiIOError> {
+    Ok(eSID_VERTEX_UV_SUB { })
 }
-pub fn load_eSID_VertexUVScroll<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUVScroll, YetiIOError> {
-
-    Ok(eSID_VertexUVScroll {
-        
-    })
+pub fn load_eSID_VertexColor<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexColor, YetiIOError> {
+    Ok(eSID_VertexColor { })
 }
-pub fn load_eSID_VertexUVSource<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUVSource, YetiIOError> {
-
-    Ok(eSID_VertexUVSource {
-        
-    })
+pub fn load_eSID_VertexColorToPixel<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexColorToPixel, YetiIOError> {
+    Ok(eSID_VertexColorToPixel { })
 }
-pub fn load_eSID_VertexUVToPixelUV<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUVToPixelUV, YetiIOError> {
-
-    Ok(eSID_VertexUVToPixelUV {
-        
-    })
+pub fn load_eSID_VertexColor_Misc1<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexColor_Misc1, YetiIOError> {
+    Ok(eSID_VertexColor_Misc1 { })
 }
-pub fn load_eSID_VertexUV_Combiner4D<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUV_Combiner4D, YetiIOError> {
-
-    Ok(eSID_VertexUV_Combiner4D {
-        
-    })
+pub fn load_eSID_VertexColor_Misc2<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexColor_Misc2, YetiIOError> {
+    Ok(eSID_VertexColor_Misc2 { })
 }
-pub fn load_eSID_VertexUntransformedNormal<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VertexUntransformedNormal, YetiIOError> {
-
-    Ok(eSID_VertexUntransformedNormal {
-        
-    })
+pub fn load_eSID_VertexCombiner4D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexCombiner4D, YetiIOError> {
+    Ok(eSID_VertexCombiner4D { })
 }
-pub fn load_eSID_Vertex_UV_ADD<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Vertex_UV_ADD, YetiIOError> {
-
-    Ok(eSID_Vertex_UV_ADD {
-        
-    })
+pub fn load_eSID_VertexConstUVWQ<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexConstUVWQ, YetiIOError> {
+    Ok(eSID_VertexConstUVWQ { })
 }
-pub fn load_eSID_Vertex_UV_Blender<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Vertex_UV_Blender, YetiIOError> {
-
-    Ok(eSID_Vertex_UV_Blender {
-        
-    })
+pub fn load_eSID_VertexConstantVector<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexConstantVector, YetiIOError> {
+    Ok(eSID_VertexConstantVector { })
 }
-pub fn load_eSID_Vertex_UV_ConstantMUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Vertex_UV_ConstantMUL, YetiIOError> {
-
-    Ok(eSID_Vertex_UV_ConstantMUL {
-        
-    })
+pub fn load_eSID_VertexElapseTime<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexElapseTime, YetiIOError> {
+    Ok(eSID_VertexElapseTime { })
 }
-pub fn load_eSID_Vertex_UV_MUL<T: Read + Seek>(rdr: &mut T) -> Result<eSID_Vertex_UV_MUL, YetiIOError> {
-
-    Ok(eSID_Vertex_UV_MUL {
-        
-    })
+pub fn load_eSID_VertexInvert<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexInvert, YetiIOError> {
+    Ok(eSID_VertexInvert { })
 }
-pub fn load_eSID_ViewPosition<T: Read + Seek>(rdr: &mut T) -> Result<eSID_ViewPosition, YetiIOError> {
-
-    Ok(eSID_ViewPosition {
-        
-    })
+pub fn load_eSID_VertexNormal<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexNormal, YetiIOError> {
+    Ok(eSID_VertexNormal { })
 }
-pub fn load_eSID_VolumeAlphaEx<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VolumeAlphaEx, YetiIOError> {
-
-    Ok(eSID_VolumeAlphaEx {
-        
-    })
+pub fn load_eSID_VertexPulseWave<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexPulseWave, YetiIOError> {
+    Ok(eSID_VertexPulseWave { })
 }
-pub fn load_eSID_VolumeAmbLightIntensityOpt<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VolumeAmbLightIntensityOpt, YetiIOError> {
-
-    Ok(eSID_VolumeAmbLightIntensityOpt {
-        
-    })
+pub fn load_eSID_VertexRGB2UV<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexRGB2UV, YetiIOError> {
+    Ok(eSID_VertexRGB2UV { })
 }
-pub fn load_eSID_VolumetricParticle<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VolumetricParticle, YetiIOError> {
-
-    Ok(eSID_VolumetricParticle {
-        
-    })
+pub fn load_eSID_VertexUVRotate<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUVRotate, YetiIOError> {
+    Ok(eSID_VertexUVRotate { })
 }
-pub fn load_eSID_VtxWorldPosition<T: Read + Seek>(rdr: &mut T) -> Result<eSID_VtxWorldPosition, YetiIOError> {
-
-    Ok(eSID_VtxWorldPosition {
-        
-    })
+pub fn load_eSID_VertexUVScroll<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUVScroll, YetiIOError> {
+    Ok(eSID_VertexUVScroll { })
 }
-pub fn load_eSID_WorldPosition<T: Read + Seek>(rdr: &mut T) -> Result<eSID_WorldPosition, YetiIOError> {
-
-    Ok(eSID_WorldPosition {
-        
+pub fn load_eSID_VertexUVSource<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUVSource, YetiIOError> {
+    Ok(eSID_VertexUVSource { })
+}
+pub fn load_eSID_VertexUVToPixelUV<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUVToPixelUV, YetiIOError> {
+    Ok(eSID_VertexUVToPixelUV { })
+}
+pub fn load_eSID_VertexUV_Combiner4D<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUV_Combiner4D, YetiIOError> {
+    Ok(eSID_VertexUV_Combiner4D { })
+}
+pub fn load_eSID_VertexUntransformedNormal<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VertexUntransformedNormal, YetiIOError> {
+    Ok(eSID_VertexUntransformedNormal { })
+}
+pub fn load_eSID_Vertex_UV_ADD<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Vertex_UV_ADD, YetiIOError> {
+    Ok(eSID_Vertex_UV_ADD { })
+}
+pub fn load_eSID_Vertex_UV_Blender<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Vertex_UV_Blender, YetiIOError> {
+    Ok(eSID_Vertex_UV_Blender { })
+}
+pub fn load_eSID_Vertex_UV_ConstantMUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Vertex_UV_ConstantMUL, YetiIOError> {
+    Ok(eSID_Vertex_UV_ConstantMUL { })
+}
+pub fn load_eSID_Vertex_UV_MUL<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_Vertex_UV_MUL, YetiIOError> {
+    Ok(eSID_Vertex_UV_MUL { })
+}
+pub fn load_eSID_ViewPosition<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_ViewPosition, YetiIOError> {
+    Ok(eSID_ViewPosition { })
+}
+pub fn load_eSID_VolumeAlphaEx<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VolumeAlphaEx, YetiIOError> {
+    Ok(eSID_VolumeAlphaEx { })
+}
+pub fn load_eSID_VolumeAmbLightIntensityOpt<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VolumeAmbLightIntensityOpt, YetiIOError> {
+    Ok(eSID_VolumeAmbLightIntensityOpt { })
+}
+pub fn load_eSID_VolumetricParticle<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VolumetricParticle, YetiIOError> {
+    Ok(eSID_VolumetricParticle { })
+}
+pub fn load_eSID_VtxWorldPosition<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_VtxWorldPosition, YetiIOError> {
+    Ok(eSID_VtxWorldPosition { })
+}
+pub fn load_eSID_WorldPosition<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_WorldPosition, YetiIOError> {
+    Ok(eSID_WorldPosition { })
+}
+pub fn load_eSID_WorldSinusFX_VC<T: Read + Seek>(_rdr: &mut T) -> Result<eSID_WorldSinusFX_VC, YetiIOError> {
+    Ok(eSID_WorldSinusFX_VC { })
+}
+
+/// Loads the payload of the node with the given id, unknown ids load as `ShaderNodeId::Invalid`
+pub fn load_node_id<T: Read + Seek>(id: &str, rdr: &mut T) -> Result<ShaderNodeId, YetiIOError> {
+    Ok(match id {
+        "eSID_ADD" => ShaderNodeId::eSID_ADD(load_eSID_ADD(rdr)?),
+        "eSID_AmbientCube" => ShaderNodeId::eSID_AmbientCube(load_eSID_AmbientCube(rdr)?),
+        "eSID_AmbientOcclusion" => ShaderNodeId::eSID_AmbientOcclusion(load_eSID_AmbientOcclusion(rdr)?),
+        "eSID_Blender" => ShaderNodeId::eSID_Blender(load_eSID_Blender(rdr)?),
+        "eSID_BlurTex" => ShaderNodeId::eSID_BlurTex(load_eSID_BlurTex(rdr)?),
+        "eSID_Clamp" => ShaderNodeId::eSID_Clamp(load_eSID_Clamp(rdr)?),
+        "eSID_ColorSelector" => ShaderNodeId::eSID_ColorSelector(load_eSID_ColorSelector(rdr)?),
+        "eSID_ColorSelectorSmooth" => ShaderNodeId::eSID_ColorSelectorSmooth(load_eSID_ColorSelectorSmooth(rdr)?),
+        "eSID_Combiner4D" => ShaderNodeId::eSID_Combiner4D(load_eSID_Combiner4D(rdr)?),
+        "eSID_Comment" => ShaderNodeId::eSID_Comment(load_eSID_Comment(rdr)?),
+        "eSID_ConstantColor" => ShaderNodeId::eSID_ConstantColor(load_eSID_ConstantColor(rdr)?),
+        "eSID_ConstantMUL" => ShaderNodeId::eSID_ConstantMUL(load_eSID_ConstantMUL(rdr)?),
+        "eSID_ConstantUVWQ" => ShaderNodeId::eSID_ConstantUVWQ(load_eSID_ConstantUVWQ(rdr)?),
+        "eSID_ConstantVector" => ShaderNodeId::eSID_ConstantVector(load_eSID_ConstantVector(rdr)?),
+        "eSID_CustomCode" => ShaderNodeId::eSID_CustomCode(load_eSID_CustomCode(rdr)?),
+        "eSID_CustomCodeVtx" => ShaderNodeId::eSID_CustomCodeVtx(load_eSID_CustomCodeVtx(rdr)?),
+        "eSID_DepthAlpha" => ShaderNodeId::eSID_DepthAlpha(load_eSID_DepthAlpha(rdr)?),
+        "eSID_DepthAlphaOpt" => ShaderNodeId::eSID_DepthAlphaOpt(load_eSID_DepthAlphaOpt(rdr)?),
+        "eSID_DepthTexture" => ShaderNodeId::eSID_DepthTexture(load_eSID_DepthTexture(rdr)?),
+        "eSID_DiffuseCube" => ShaderNodeId::eSID_DiffuseCube(load_eSID_DiffuseCube(rdr)?),
+        "eSID_DiffuseMultiplier" => ShaderNodeId::eSID_DiffuseMultiplier(load_eSID_DiffuseMultiplier(rdr)?),
+        "eSID_DustFXSH" => ShaderNodeId::eSID_DustFXSH(load_eSID_DustFXSH(rdr)?),
+        "eSID_ElapseTime" => ShaderNodeId::eSID_ElapseTime(load_eSID_ElapseTime(rdr)?),
+        "eSID_FlatChrome" => ShaderNodeId::eSID_FlatChrome(load_eSID_FlatChrome(rdr)?),
+        "eSID_ForceNoPrepass" => ShaderNodeId::eSID_ForceNoPrepass(load_eSID_ForceNoPrepass(rdr)?),
+        "eSID_ForceUltraSimpleShader" => ShaderNodeId::eSID_ForceUltraSimpleShader(load_eSID_ForceUltraSimpleShader(rdr)?),
+        "eSID_ForceVisualPrepass" => ShaderNodeId::eSID_ForceVisualPrepass(load_eSID_ForceVisualPrepass(rdr)?),
+        "eSID_HeatSelector" => ShaderNodeId::eSID_HeatSelector(load_eSID_HeatSelector(rdr)?),
+        "eSID_Invert" => ShaderNodeId::eSID_Invert(load_eSID_Invert(rdr)?),
+        "eSID_LODBlender" => ShaderNodeId::eSID_LODBlender(load_eSID_LODBlender(rdr)?),
+        "eSID_LODMUL" => ShaderNodeId::eSID_LODMUL(load_eSID_LODMUL(rdr)?),
+        "eSID_LightCurve_Back" => ShaderNodeId::eSID_LightCurve_Back(load_eSID_LightCurve_Back(rdr)?),
+        "eSID_LinearFresnel" => ShaderNodeId::eSID_LinearFresnel(load_eSID_LinearFresnel(rdr)?),
+        "eSID_Luminance" => ShaderNodeId::eSID_Luminance(load_eSID_Luminance(rdr)?),
+        "eSID_MUL" => ShaderNodeId::eSID_MUL(load_eSID_MUL(rdr)?),
+        "eSID_MUL2X" => ShaderNodeId::eSID_MUL2X(load_eSID_MUL2X(rdr)?),
+        "eSID_MainOutput" => ShaderNodeId::eSID_MainOutput(load_eSID_MainOutput(rdr)?),
+        "eSID_MaterialColor_Diffuse" => ShaderNodeId::eSID_MaterialColor_Diffuse(load_eSID_MaterialColor_Diffuse(rdr)?),
+        "eSID_MaterialColor_Emissive" => ShaderNodeId::eSID_MaterialColor_Emissive(load_eSID_MaterialColor_Emissive(rdr)?),
+        "eSID_MaterialColor_EmissiveVTX" => ShaderNodeId::eSID_MaterialColor_EmissiveVTX(load_eSID_MaterialColor_EmissiveVTX(rdr)?),
+        "eSID_MaterialColor_Specular" => ShaderNodeId::eSID_MaterialColor_Specular(load_eSID_MaterialColor_Specular(rdr)?),
+        "eSID_Normal" => ShaderNodeId::eSID_Normal(load_eSID_Normal(rdr)?),
+        "eSID_Normalize3D" => ShaderNodeId::eSID_Normalize3D(load_eSID_Normalize3D(rdr)?),
+        "eSID_OffsetBump" => ShaderNodeId::eSID_OffsetBump(load_eSID_OffsetBump(rdr)?),
+        "eSID_PixelAverageColor" => ShaderNodeId::eSID_PixelAverageColor(load_eSID_PixelAverageColor(rdr)?),
+        "eSID_PixelColor_Misc" => ShaderNodeId::eSID_PixelColor_Misc(load_eSID_PixelColor_Misc(rdr)?),
+        "eSID_PixelColor_Misc2" => ShaderNodeId::eSID_PixelColor_Misc2(load_eSID_PixelColor_Misc2(rdr)?),
+        "eSID_PixelSH" => ShaderNodeId::eSID_PixelSH(load_eSID_PixelSH(rdr)?),
+        "eSID_PixelUVBoxAnimBlend" => ShaderNodeId::eSID_PixelUVBoxAnimBlend(load_eSID_PixelUVBoxAnimBlend(rdr)?),
+        "eSID_PixelViewToWorld" => ShaderNodeId::eSID_PixelViewToWorld(load_eSID_PixelViewToWorld(rdr)?),
+        "eSID_Position" => ShaderNodeId::eSID_Position(load_eSID_Position(rdr)?),
+        "eSID_PowFresnel" => ShaderNodeId::eSID_PowFresnel(load_eSID_PowFresnel(rdr)?),
+        "eSID_Power" => ShaderNodeId::eSID_Power(load_eSID_Power(rdr)?),
+        "eSID_PulseWave" => ShaderNodeId::eSID_PulseWave(load_eSID_PulseWave(rdr)?),
+        "eSID_RGB2UV" => ShaderNodeId::eSID_RGB2UV(load_eSID_RGB2UV(rdr)?),
+        "eSID_RGB_Ramp" => ShaderNodeId::eSID_RGB_Ramp(load_eSID_RGB_Ramp(rdr)?),
+        "eSID_RefracTex" => ShaderNodeId::eSID_RefracTex(load_eSID_RefracTex(rdr)?),
+        "eSID_SUB" => ShaderNodeId::eSID_SUB(load_eSID_SUB(rdr)?),
+        "eSID_SawWave" => ShaderNodeId::eSID_SawWave(load_eSID_SawWave(rdr)?),
+        "eSID_ShadowValue" => ShaderNodeId::eSID_ShadowValue(load_eSID_ShadowValue(rdr)?),
+        "eSID_SinusFX" => ShaderNodeId::eSID_SinusFX(load_eSID_SinusFX(rdr)?),
+        "eSID_SpecularCubeMap" => ShaderNodeId::eSID_SpecularCubeMap(load_eSID_SpecularCubeMap(rdr)?),
+        "eSID_SpecularGlossMultiplier" => ShaderNodeId::eSID_SpecularGlossMultiplier(load_eSID_SpecularGlossMultiplier(rdr)?),
+        "eSID_SpecularPowerMultiplier" => ShaderNodeId::eSID_SpecularPowerMultiplier(load_eSID_SpecularPowerMultiplier(rdr)?),
+        "eSID_Tangent2Screen" => ShaderNodeId::eSID_Tangent2Screen(load_eSID_Tangent2Screen(rdr)?),
+        "eSID_Tex2D" => ShaderNodeId::eSID_Tex2D(load_eSID_Tex2D(rdr)?),
+        "eSID_Tex2DYUV" => ShaderNodeId::eSID_Tex2DYUV(load_eSID_Tex2DYUV(rdr)?),
+        "eSID_TexBump" => ShaderNodeId::eSID_TexBump(load_eSID_TexBump(rdr)?),
+        "eSID_TexBumpTangent" => ShaderNodeId::eSID_TexBumpTangent(load_eSID_TexBumpTangent(rdr)?),
+        "eSID_UV2RGB" => ShaderNodeId::eSID_UV2RGB(load_eSID_UV2RGB(rdr)?),
+        "eSID_UVRotate" => ShaderNodeId::eSID_UVRotate(load_eSID_UVRotate(rdr)?),
+        "eSID_UVScroll" => ShaderNodeId::eSID_UVScroll(load_eSID_UVScroll(rdr)?),
+        "eSID_UVScrollSpeedFactor" => ShaderNodeId::eSID_UVScrollSpeedFactor(load_eSID_UVScrollSpeedFactor(rdr)?),
+        "eSID_UVSource" => ShaderNodeId::eSID_UVSource(load_eSID_UVSource(rdr)?),
+        "eSID_UV_ADD" => ShaderNodeId::eSID_UV_ADD(load_eSID_UV_ADD(rdr)?),
+        "eSID_UV_Blender" => ShaderNodeId::eSID_UV_Blender(load_eSID_UV_Blender(rdr)?),
+        "eSID_UV_Combiner4D" => ShaderNodeId::eSID_UV_Combiner4D(load_eSID_UV_Combiner4D(rdr)?),
+        "eSID_UV_ConstantMUL" => ShaderNodeId::eSID_UV_ConstantMUL(load_eSID_UV_ConstantMUL(rdr)?),
+        "eSID_UV_DUDV" => ShaderNodeId::eSID_UV_DUDV(load_eSID_UV_DUDV(rdr)?),
+        "eSID_UV_MUL" => ShaderNodeId::eSID_UV_MUL(load_eSID_UV_MUL(rdr)?),
+        "eSID_UV_SUB" => ShaderNodeId::eSID_UV_SUB(load_eSID_UV_SUB(rdr)?),
+        "eSID_UntransformedNormal" => ShaderNodeId::eSID_UntransformedNormal(load_eSID_UntransformedNormal(rdr)?),
+        "eSID_VERTEX_UV_SUB" => ShaderNodeId::eSID_VERTEX_UV_SUB(load_eSID_VERTEX_UV_SUB(rdr)?),
+        "eSID_VertexColor" => ShaderNodeId::eSID_VertexColor(load_eSID_VertexColor(rdr)?),
+        "eSID_VertexColorToPixel" => ShaderNodeId::eSID_VertexColorToPixel(load_eSID_VertexColorToPixel(rdr)?),
+        "eSID_VertexColor_Misc1" => ShaderNodeId::eSID_VertexColor_Misc1(load_eSID_VertexColor_Misc1(rdr)?),
+        "eSID_VertexColor_Misc2" => ShaderNodeId::eSID_VertexColor_Misc2(load_eSID_VertexColor_Misc2(rdr)?),
+        "eSID_VertexCombiner4D" => ShaderNodeId::eSID_VertexCombiner4D(load_eSID_VertexCombiner4D(rdr)?),
+        "eSID_VertexConstUVWQ" => ShaderNodeId::eSID_VertexConstUVWQ(load_eSID_VertexConstUVWQ(rdr)?),
+        "eSID_VertexConstantVector" => ShaderNodeId::eSID_VertexConstantVector(load_eSID_VertexConstantVector(rdr)?),
+        "eSID_VertexElapseTime" => ShaderNodeId::eSID_VertexElapseTime(load_eSID_VertexElapseTime(rdr)?),
+        "eSID_VertexInvert" => ShaderNodeId::eSID_VertexInvert(load_eSID_VertexInvert(rdr)?),
+        "eSID_VertexNormal" => ShaderNodeId::eSID_VertexNormal(load_eSID_VertexNormal(rdr)?),
+        "eSID_VertexPulseWave" => ShaderNodeId::eSID_VertexPulseWave(load_eSID_VertexPulseWave(rdr)?),
+        "eSID_VertexRGB2UV" => ShaderNodeId::eSID_VertexRGB2UV(load_eSID_VertexRGB2UV(rdr)?),
+        "eSID_VertexUVRotate" => ShaderNodeId::eSID_VertexUVRotate(load_eSID_VertexUVRotate(rdr)?),
+        "eSID_VertexUVScroll" => ShaderNodeId::eSID_VertexUVScroll(load_eSID_VertexUVScroll(rdr)?),
+        "eSID_VertexUVSource" => ShaderNodeId::eSID_VertexUVSource(load_eSID_VertexUVSource(rdr)?),
+        "eSID_VertexUVToPixelUV" => ShaderNodeId::eSID_VertexUVToPixelUV(load_eSID_VertexUVToPixelUV(rdr)?),
+        "eSID_VertexUV_Combiner4D" => ShaderNodeId::eSID_VertexUV_Combiner4D(load_eSID_VertexUV_Combiner4D(rdr)?),
+        "eSID_VertexUntransformedNormal" => ShaderNodeId::eSID_VertexUntransformedNormal(load_eSID_VertexUntransformedNormal(rdr)?),
+        "eSID_Vertex_UV_ADD" => ShaderNodeId::eSID_Vertex_UV_ADD(load_eSID_Vertex_UV_ADD(rdr)?),
+        "eSID_Vertex_UV_Blender" => ShaderNodeId::eSID_Vertex_UV_Blender(load_eSID_Vertex_UV_Blender(rdr)?),
+        "eSID_Vertex_UV_ConstantMUL" => ShaderNodeId::eSID_Vertex_UV_ConstantMUL(load_eSID_Vertex_UV_ConstantMUL(rdr)?),
+        "eSID_Vertex_UV_MUL" => ShaderNodeId::eSID_Vertex_UV_MUL(load_eSID_Vertex_UV_MUL(rdr)?),
+        "eSID_ViewPosition" => ShaderNodeId::eSID_ViewPosition(load_eSID_ViewPosition(rdr)?),
+        "eSID_VolumeAlphaEx" => ShaderNodeId::eSID_VolumeAlphaEx(load_eSID_VolumeAlphaEx(rdr)?),
+        "eSID_VolumeAmbLightIntensityOpt" => ShaderNodeId::eSID_VolumeAmbLightIntensityOpt(load_eSID_VolumeAmbLightIntensityOpt(rdr)?),
+        "eSID_VolumetricParticle" => ShaderNodeId::eSID_VolumetricParticle(load_eSID_VolumetricParticle(rdr)?),
+        "eSID_VtxWorldPosition" => ShaderNodeId::eSID_VtxWorldPosition(load_eSID_VtxWorldPosition(rdr)?),
+        "eSID_WorldPosition" => ShaderNodeId::eSID_WorldPosition(load_eSID_WorldPosition(rdr)?),
+        "eSID_WorldSinusFX_VC" => ShaderNodeId::eSID_WorldSinusFX_VC(load_eSID_WorldSinusFX_VC(rdr)?),
+        _ => ShaderNodeId::Invalid
     })
 }
-pub fn load_eSID_WorldSinusFX<T: Read + Seek>(rdr: &mut T) -> Result<eSID_WorldSinusFX_VC, YetiIOError> {
-
-    Ok(eSID_WorldSinusFX_VC {
-        
-    })
-}
\ No newline at end of file
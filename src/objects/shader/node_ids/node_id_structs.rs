@@ -1,448 +1,450 @@
+// node params aren't decoded apart from comments, their payload stays in `ShaderNode::extra`
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ADD {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_AmbientCube {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_AmbientOcclusion {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Blender {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_BlurTex {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Clamp {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ColorSelector {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ColorSelectorSmooth {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Combiner4D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone)]
 pub struct eSID_Comment {
     pub comment: String,
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ConstantColor {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ConstantMUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ConstantUVWQ {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ConstantVector {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_CustomCode {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_CustomCodeVtx {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DepthAlpha {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DepthAlphaOpt {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DepthTexture {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DiffuseCube {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DiffuseMultiplier {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_DustFXSH {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ElapseTime {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_FlatChrome {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ForceNoPrepass {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ForceUltraSimpleShader {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ForceVisualPrepass {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_HeatSelector {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Invert {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_LODBlender {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_LODMUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_LightCurve_Back {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_LinearFresnel {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Luminance {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MUL2X {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MainOutput {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MaterialColor_Diffuse {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MaterialColor_Emissive {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MaterialColor_EmissiveVTX {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_MaterialColor_Specular {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Normal {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Normalize3D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_OffsetBump {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelAverageColor {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelColor_Misc {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelColor_Misc2 {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelSH {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelUVBoxAnimBlend {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PixelViewToWorld {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Position {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PowFresnel {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Power {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_PulseWave {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_RGB2UV {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_RGB_Ramp {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_RefracTex {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SUB {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SawWave {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ShadowValue {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SinusFX {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SpecularCubeMap {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SpecularGlossMultiplier {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_SpecularPowerMultiplier {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Tangent2Screen {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Tex2D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Tex2DYUV {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_TexBump {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_TexBumpTangent {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV2RGB {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UVRotate {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UVScroll {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UVScrollSpeedFactor {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UVSource {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_ADD {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_Blender {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_Combiner4D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_ConstantMUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_DUDV {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_MUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UV_SUB {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_UntransformedNormal {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VERTEX_UV_SUB {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexColor {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexColorToPixel {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexColor_Misc1 {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexColor_Misc2 {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexCombiner4D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexConstUVWQ {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexConstantVector {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexElapseTime {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexInvert {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexNormal {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexPulseWave {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexRGB2UV {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUVRotate {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUVScroll {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUVSource {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUVToPixelUV {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUV_Combiner4D {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VertexUntransformedNormal {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Vertex_UV_ADD {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Vertex_UV_Blender {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Vertex_UV_ConstantMUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_Vertex_UV_MUL {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_ViewPosition {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VolumeAlphaEx {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VolumeAmbLightIntensityOpt {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VolumetricParticle {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_VtxWorldPosition {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_WorldPosition {
-    
 }
 #[allow(non_camel_case_types)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct eSID_WorldSinusFX_VC {
-    
-}
\ No newline at end of file
+}
@@ -1,9 +1,67 @@
-use crate::util::load_util::read_mat4;
+use crate::bigfile::Bigfile;
+use crate::metadata::{ObjectType, YKey};
+use crate::util::load_util::{read_mat4, read_quat, read_vec3};
 
 use super::ArchetypeImpl;
-use std::{io::{Cursor, Read}};
-use byteorder::ReadBytesExt;
-use glam::Mat4;
+use std::io::{Cursor, Read};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt};
+use glam::{Mat4, Quat, Vec3};
+
+bitflags! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BoneFlags: u32 {
+        const DEFORM        = 1 << 0;
+        const NO_ANIMATION  = 1 << 1;
+        const IK            = 1 << 2;
+        const _ = !0;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoneConstraintType {
+    None,
+    LookAt,
+    CopyRotation,
+    Twist,
+    LimitRotation,
+    Unknown(u8),
+}
+
+impl From<u8> for BoneConstraintType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::LookAt,
+            2 => Self::CopyRotation,
+            3 => Self::Twist,
+            4 => Self::LimitRotation,
+            v => Self::Unknown(v)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BoneConstraint {
+    pub constraint_type: BoneConstraintType,
+    pub target: Option<u8>,
+    pub weight: f32,
+    /// min and max, in radians for rotation constraints
+    pub limits: [f32; 2],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BoneTransform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl BoneTransform {
+    fn is_valid(&self) -> bool {
+        self.translation.is_finite() && self.scale.is_finite() && self.rotation.is_finite() && self.rotation.is_normalized()
+    }
+}
 
 #[derive(Default)]
 pub struct Skeleton {
@@ -15,12 +73,20 @@ pub struct Skeleton {
 
 pub struct Bone {
     name: String,
-    pub unk_01: [u8; 4],
+    pub flags: BoneFlags,
     pub parent: Option<u8>,
+    /// the raw bone block the fields below are decoded from, their meanings are still guesses
     pub data: [u8; 63],
+    /// transform relative to the parent, derived from the mesh space matrices when the stored one doesn't decode
+    pub local: BoneTransform,
+    pub local_decoded: bool,
+    /// bit per lod the bone is kept in
+    pub lod_mask: u8,
+    pub constraint: BoneConstraint,
+    pub unk_02: [u8; 8],
     pub mesh_space_matrix: Mat4,
     pub inv_bind_matrix: Mat4,
-    
+
     pub children: Vec<u8>,
 }
 
@@ -30,8 +96,28 @@ impl Bone {
     }
 }
 
+fn read_bone_index(cursor: &mut Cursor<&[u8]>) -> Result<Option<u8>, super::YetiIOError> {
+    Ok(match cursor.read_u8()? {
+        255 => None,
+        v => Some(v)
+    })
+}
+
+/// u8 length including the nul terminator, then the string
+fn read_bone_name(cursor: &mut Cursor<&[u8]>) -> Result<String, super::YetiIOError> {
+    let len = cursor.read_u8()?;
+    let mut strbuf: Vec<u8> = Vec::new();
+    for _ in 0..len.saturating_sub(1) {
+        strbuf.push(cursor.read_u8()?);
+    }
+    if len > 0 {
+        cursor.read_u8()?; // null terminator
+    }
+    Ok(String::from_utf8(strbuf)?)
+}
+
 impl ArchetypeImpl for Skeleton {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), super::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, super::YetiIOError> {
         let mut cursor = Cursor::new(buf);
         self.version = cursor.read_u8()?;
         self.num_bones = cursor.read_u8()?;
@@ -39,41 +125,45 @@ impl ArchetypeImpl for Skeleton {
 
         let mut bones: Vec<Bone> = Vec::with_capacity(self.num_bones as usize);
         for _ in 0..self.num_bones as usize {
+            let flags = BoneFlags::from_bits_retain(cursor.read_u32::<LittleEndian>()?);
+            let parent = read_bone_index(&mut cursor)?;
+            let mut data = [0; 63];
+            cursor.read_exact(&mut data)?;
 
-            let mut bone = Bone {
-                name: String::new(),
-                unk_01: [0; 4],
-                parent: Option::<u8>::None.into(),
-                data: [0; 63],
-                children: Vec::new(),
-                mesh_space_matrix: Mat4::IDENTITY,
-                inv_bind_matrix: Mat4::IDENTITY,
+            let mut data_cursor = Cursor::new(&data[..]);
+            let local = BoneTransform {
+                translation: read_vec3(&mut data_cursor)?,
+                rotation: read_quat(&mut data_cursor)?,
+                scale: read_vec3(&mut data_cursor)?,
             };
-            
-            cursor.read(&mut bone.unk_01)?;
-            bone.parent = match cursor.read_u8()? {
-                255 => None,
-                v => Some(v)
+            let lod_mask = data_cursor.read_u8()?;
+            let constraint = BoneConstraint {
+                constraint_type: data_cursor.read_u8()?.into(),
+                target: read_bone_index(&mut data_cursor)?,
+                weight: data_cursor.read_f32::<LittleEndian>()?,
+                limits: [data_cursor.read_f32::<LittleEndian>()?, data_cursor.read_f32::<LittleEndian>()?],
             };
-            cursor.read(&mut bone.data)?;
+            let mut unk_02 = [0; 8];
+            data_cursor.read_exact(&mut unk_02)?;
 
-            bone.mesh_space_matrix = read_mat4(&mut cursor)?;
-            bone.inv_bind_matrix = read_mat4(&mut cursor)?;
-
-            bones.push(bone);
+            bones.push(Bone {
+                name: String::new(),
+                flags,
+                parent,
+                data,
+                local_decoded: local.is_valid(),
+                local,
+                lod_mask,
+                constraint,
+                unk_02,
+                mesh_space_matrix: read_mat4(&mut cursor)?,
+                inv_bind_matrix: read_mat4(&mut cursor)?,
+                children: Vec::new(),
+            });
         }
-        
-        let mut strbuf: Vec<u8> = Vec::new();
-        for i in 0..self.num_bones as usize {
-            let len = cursor.read_u8()?;
-            strbuf.clear();
-
-            for _ in 0..len - 1 {
-                strbuf.push(cursor.read_u8()?);
-            }
-            cursor.read_u8()?; // null terminator
 
-            bones[i].name = String::from_utf8(strbuf.clone())?;
+        for bone in bones.iter_mut() {
+            bone.name = read_bone_name(&mut cursor)?;
         }
 
         for i in 0..self.num_bones {
@@ -83,12 +173,172 @@ impl ArchetypeImpl for Skeleton {
             }
         }
 
+        for i in 0..bones.len() {
+            if !bones[i].local_decoded {
+                log::debug!("bone {} local transform doesn't decode, using its mesh space matrix", bones[i].name);
+                let matrix = match bones[i].parent {
+                    Some(parent) => bones[parent as usize].mesh_space_matrix.inverse() * bones[i].mesh_space_matrix,
+                    None => bones[i].mesh_space_matrix
+                };
+                let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+                bones[i].local = BoneTransform { translation, rotation, scale };
+            }
+        }
+
         self.bones = bones;
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
         *self = Self::default()
     }
-}
\ No newline at end of file
+}
+
+impl Skeleton {
+    /// The procedural bones of a skeleton, referenced by the skeleton itself
+    pub fn find_procedural(bf: &Bigfile, ske: YKey) -> Option<YKey> {
+        bf.object_table.get(&ske)?.references.iter()
+            .find(|key| bf.is_key_valid(**key) && bf.file_table[*key].object_type == ObjectType::skp)
+            .copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ProceduralBoneKind {
+    /// takes part of a skeleton bone's rotation around an axis
+    Twist { source: u8, axis: Vec3, factor: f32 },
+    Jiggle { stiffness: f32, damping: f32, gravity: f32 },
+    LookAt { target: u8 },
+    CopyTransform { source: u8, weight: f32 },
+}
+
+impl ProceduralBoneKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Twist { .. } => "twist",
+            Self::Jiggle { .. } => "jiggle",
+            Self::LookAt { .. } => "look at",
+            Self::CopyTransform { .. } => "copy transform",
+        }
+    }
+}
+
+/// A bone added on top of a skeleton and driven at runtime, parented to a skeleton bone
+pub struct ProceduralBone {
+    name: String,
+    pub parent: Option<u8>,
+    pub kind: ProceduralBoneKind,
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+impl ProceduralBone {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn local_matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.translation)
+    }
+
+    /// Bind pose in the same mesh space as the skeleton's bones
+    pub fn mesh_space_matrix(&self, skeleton: &Skeleton) -> Mat4 {
+        match self.parent.and_then(|parent| skeleton.bones.get(parent as usize)) {
+            Some(parent) => parent.mesh_space_matrix * self.local_matrix(),
+            None => self.local_matrix()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SkeletonProcedural {
+    pub version: u8,
+    pub bones: Vec<ProceduralBone>,
+    /// set when the bones or their names don't decode, what's left of the payload stays unparsed
+    pub decode_error: Option<String>,
+}
+
+impl SkeletonProcedural {
+    fn read_bone(cursor: &mut Cursor<&[u8]>) -> Result<ProceduralBone, super::YetiIOError> {
+        let parent = read_bone_index(cursor)?;
+        let kind_id = cursor.read_u8()?;
+        let translation = read_vec3(cursor)?;
+        let rotation = read_quat(cursor)?;
+
+        let kind = match kind_id {
+            0 => ProceduralBoneKind::Twist {
+                source: cursor.read_u8()?,
+                axis: read_vec3(cursor)?,
+                factor: cursor.read_f32::<LittleEndian>()?,
+            },
+            1 => ProceduralBoneKind::Jiggle {
+                stiffness: cursor.read_f32::<LittleEndian>()?,
+                damping: cursor.read_f32::<LittleEndian>()?,
+                gravity: cursor.read_f32::<LittleEndian>()?,
+            },
+            2 => ProceduralBoneKind::LookAt {
+                target: cursor.read_u8()?,
+            },
+            3 => ProceduralBoneKind::CopyTransform {
+                source: cursor.read_u8()?,
+                weight: cursor.read_f32::<LittleEndian>()?,
+            },
+            v => return Err(format!("unknown procedural bone type {}", v).into())
+        };
+
+        Ok(ProceduralBone {
+            name: String::new(),
+            parent,
+            kind,
+            translation,
+            rotation,
+        })
+    }
+}
+
+impl ArchetypeImpl for SkeletonProcedural {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, super::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        self.version = cursor.read_u8()?;
+        let num_bones = cursor.read_u8()?;
+
+        // unknown bone types have an unknown size, so leave everything after the header as unparsed data rather than fail the load
+        let mut bones = Vec::with_capacity(num_bones as usize);
+        for _ in 0..num_bones {
+            match Self::read_bone(&mut cursor) {
+                Ok(bone) => bones.push(bone),
+                Err(error) => {
+                    self.decode_error = Some(format!("bone {} doesn't decode: {}", bones.len(), error));
+                    return Ok(2);
+                }
+            }
+        }
+
+        // the bones don't need their names, so bad names only leave them unnamed
+        let names_start = cursor.position();
+        match (0..bones.len()).map(|_| read_bone_name(&mut cursor)).collect::<Result<Vec<_>, _>>() {
+            Ok(names) => {
+                for (bone, name) in bones.iter_mut().zip(names) {
+                    bone.name = name;
+                }
+            },
+            Err(error) => {
+                self.decode_error = Some(format!("bone names don't decode: {}", error));
+                cursor.set_position(names_start);
+            }
+        }
+
+        self.bones = bones;
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default()
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
@@ -42,7 +42,7 @@ pub struct SnkEntry {
 }
 
 impl ArchetypeImpl for SoundBank {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), super::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, super::YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         self.snk_type = match cursor.read_u8()? {
@@ -82,7 +82,7 @@ impl ArchetypeImpl for SoundBank {
             num_snk -= 1;
         }
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -67,10 +67,11 @@ impl TextureMetadata {
 }
 
 impl ArchetypeImpl for TextureMetadataObject {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         if buf.len() < 6 {
+            // too short to hold metadata, the whole payload is the passthrough marker
             self.meta = TextureMetaType::Passthrough;
-            return Ok(());
+            return Ok(buf.len());
         }
 
         let mut meta = TextureMetadata::default();
@@ -93,7 +94,7 @@ impl ArchetypeImpl for TextureMetadataObject {
 
         self.meta = TextureMetaType::Metadata(meta);
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -112,7 +113,7 @@ pub struct TextureData {
 }
 
 impl ArchetypeImpl for TextureData {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         self.unk_01 = cursor.read_u32::<LittleEndian>()?;
         self.fmt_id = cursor.read_u8()?;
@@ -120,7 +121,7 @@ impl ArchetypeImpl for TextureData {
 
         self.texture_data = buf[8..].to_vec();
 
-        Ok(())
+        Ok(buf.len())
     }
 
     fn unload(&mut self) {
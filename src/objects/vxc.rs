@@ -9,7 +9,7 @@ pub struct VertexColors {
 }
 
 impl ArchetypeImpl for VertexColors {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let num_vtx = cursor.read_u16::<LittleEndian>()?;
@@ -23,10 +23,33 @@ impl ArchetypeImpl for VertexColors {
             ));
         }
 
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u16::<LittleEndian>(self.colors.len() as u16)?;
+        for col in self.colors.iter() {
+            for c in col.to_array() {
+                buf.write_u8((c * 255.0).round() as u8)?;
+            }
+        }
         Ok(())
     }
 
     fn unload(&mut self) {
         *self = Default::default();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Vec::new();
+        buf.write_u16::<LittleEndian>(3).unwrap();
+        buf.extend_from_slice(&[0, 1, 2, 3, 127, 128, 129, 130, 252, 253, 254, 255]);
+        assert_round_trip(&mut VertexColors::default(), &buf);
+    }
+}
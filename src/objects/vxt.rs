@@ -10,7 +10,7 @@ pub struct Vxt {
 }
 
 impl ArchetypeImpl for Vxt {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         cursor.read(&mut self.header)?;
 
@@ -24,10 +24,36 @@ impl ArchetypeImpl for Vxt {
             ));
         }
 
+        Ok(cursor.position() as usize)
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.extend_from_slice(&self.header);
+        buf.write_u16::<LittleEndian>(self.vxt.len() as u16)?;
+        for v in self.vxt.iter() {
+            buf.write_f32::<LittleEndian>(v.x)?;
+            buf.write_f32::<LittleEndian>(v.y)?;
+            buf.write_f32::<LittleEndian>(v.z)?;
+        }
         Ok(())
     }
 
     fn unload(&mut self) {
         *self = Default::default();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = vec![0x01, 0x02, 0x03, 0x04];
+        buf.write_u16::<LittleEndian>(2).unwrap();
+        for v in [1.0, 2.0, 3.0, -0.5, 0.0, 1e-3] {
+            buf.write_f32::<LittleEndian>(v).unwrap();
+        }
+        assert_round_trip(&mut Vxt::default(), &buf);
+    }
+}
@@ -1,32 +1,449 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::cmp::Reverse;
 use std::io::{Cursor, Read};
+use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt};
+use glam::Vec3;
+use crate::bigfile::Bigfile;
+use crate::metadata::{ObjectType, YKey};
 use crate::objects::ArchetypeImpl;
 
+bitflags! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct WayFlags: u32 {
+        const CLOSED        = 1 << 0;
+        const NAVIGATION    = 1 << 1;
+        const CAPTURE       = 1 << 2;
+        const _ = !0;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct WayPointFlags: u16 {
+        const START         = 1 << 0;
+        const END           = 1 << 1;
+        const JUNCTION      = 1 << 2;
+        const _ = !0;
+    }
+}
+
+const WAY_HEADER_SIZE: usize = 13;
+const WAY_POINT_SIZE: usize = 10;
+
+/// One point of a way, in the same order as the way's gao references
+#[derive(Default, Debug, Clone, Copy)]
+pub struct WayPoint {
+    /// indices of the linked points in the same way
+    pub links: [Option<u16>; 2],
+    pub flags: WayPointFlags,
+    pub width: f32,
+    /// the record as read, the fields above are a best guess at its layout
+    pub raw: [u8; WAY_POINT_SIZE],
+}
+
 #[derive(Default, Debug)]
 pub struct Way {
-    pub unk_dat01: [u8; 13],
+    pub flags: WayFlags,
+    pub way_type: u8,
+    pub width: f32,
+    pub unk_01: u32,
+    /// the header before the point count as read, the fields above are a best guess at its layout
+    pub raw_header: [u8; WAY_HEADER_SIZE],
     pub num_way_gaos: u16,
-    pub way_datas: Vec<[u8; 10]>
+    pub points: Vec<WayPoint>,
+    /// set when a point links past the end of the way
+    pub decode_error: Option<String>,
+}
+
+fn read_link(cursor: &mut Cursor<&[u8]>) -> Result<Option<u16>, crate::bigfile::YetiIOError> {
+    Ok(match cursor.read_u16::<LittleEndian>()? {
+        0xFFFF => None,
+        idx => Some(idx)
+    })
 }
 
 impl ArchetypeImpl for Way {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), crate::bigfile::YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
-        cursor.read_exact(&mut self.unk_dat01)?;
+        cursor.read_exact(&mut self.raw_header)?;
+        let raw_header = self.raw_header;
+        let mut header = Cursor::new(&raw_header[..]);
+        self.flags = WayFlags::from_bits_retain(header.read_u32::<LittleEndian>()?);
+        self.way_type = header.read_u8()?;
+        self.width = header.read_f32::<LittleEndian>()?;
+        self.unk_01 = header.read_u32::<LittleEndian>()?;
 
         self.num_way_gaos = cursor.read_u16::<LittleEndian>()?;
 
-        for _ in 0..self.num_way_gaos {
-            let mut data = [0; 10];
-            cursor.read_exact(&mut data)?;
-            self.way_datas.push(data);
+        for idx in 0..self.num_way_gaos {
+            let mut raw = [0; WAY_POINT_SIZE];
+            cursor.read_exact(&mut raw)?;
+            let mut record = Cursor::new(&raw[..]);
+            let point = WayPoint {
+                links: [read_link(&mut record)?, read_link(&mut record)?],
+                flags: WayPointFlags::from_bits_retain(record.read_u16::<LittleEndian>()?),
+                width: record.read_f32::<LittleEndian>()?,
+                raw,
+            };
+            for link in point.links.iter().flatten() {
+                if *link >= self.num_way_gaos && self.decode_error.is_none() {
+                    self.decode_error = Some(format!("point {} links to {} but the way only has {} points", idx, link, self.num_way_gaos));
+                }
+            }
+            self.points.push(point);
         }
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
-        self.way_datas.clear();
+        *self = Self::default();
     }
-}
\ No newline at end of file
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+/// Connects a point of one way to a point of another, the ways are the link's first two references
+#[derive(Default, Debug)]
+pub struct WayExternalLink {
+    pub from_point: u16,
+    pub to_point: u16,
+    pub flags: u16,
+    pub unk_01: u16,
+}
+
+impl ArchetypeImpl for WayExternalLink {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+
+        self.from_point = cursor.read_u16::<LittleEndian>()?;
+        self.to_point = cursor.read_u16::<LittleEndian>()?;
+        self.flags = cursor.read_u16::<LittleEndian>()?;
+        self.unk_01 = cursor.read_u16::<LittleEndian>()?;
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The ways and external links of a world, per-way flags are in the same order as the list's way references
+#[derive(Default, Debug)]
+pub struct WayList {
+    pub way_flags: Vec<u32>,
+    /// set when the way count doesn't match the payload, the ways still load through the references
+    pub decode_error: Option<String>,
+}
+
+impl ArchetypeImpl for WayList {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, crate::bigfile::YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        let num_ways = cursor.read_u32::<LittleEndian>()? as usize;
+        if num_ways * 4 != buf.len() - 4 {
+            self.decode_error = Some(format!("way list count {} doesn't match payload size {}", num_ways, buf.len()));
+            return Ok(0);
+        }
+
+        for _ in 0..num_ways {
+            self.way_flags.push(cursor.read_u32::<LittleEndian>()?);
+        }
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default();
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WayNode {
+    pub way: YKey,
+    pub point: u16,
+    pub gao: YKey,
+    pub position: Vec3,
+    pub width: f32,
+    pub flags: WayPointFlags,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WayEdge {
+    pub from: usize,
+    pub to: usize,
+    /// true when the edge comes from a wel rather than the way's own point links
+    pub external: bool,
+}
+
+/// Graph of way points across every way in a network, nodes are addressed by their index in `nodes`
+#[derive(Debug, Default)]
+pub struct WayNetwork {
+    pub nodes: Vec<WayNode>,
+    pub edges: Vec<WayEdge>,
+    /// (way, point index) to node index
+    lookup: HashMap<(YKey, u16), usize>,
+    /// edges by (lower, higher) node index, links are usually stored on both points
+    edge_set: HashSet<(usize, usize)>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl WayNetwork {
+    /// Builds the network of a way list, using its way references and any external links it references
+    pub fn from_way_list(bf: &Bigfile, wal: YKey) -> Self {
+        let refs = &bf.object_table[&wal].references;
+        let of_type = |object_type: ObjectType| -> Vec<YKey> {
+            refs.iter()
+                .filter(|key| bf.is_key_valid(**key) && bf.file_table[*key].object_type == object_type)
+                .copied()
+                .collect()
+        };
+        Self::build(bf, &of_type(ObjectType::way), &of_type(ObjectType::wel))
+    }
+
+    pub fn build(bf: &Bigfile, ways: &[YKey], links: &[YKey]) -> Self {
+        let mut network = Self::default();
+
+        for way_key in ways.iter() {
+            let Some(way) = bf.object_table.get(way_key).and_then(|obj| obj.archetype.as_way()) else { continue; };
+            let gaos = &bf.object_table[way_key].references;
+
+            for (idx, point) in way.points.iter().enumerate() {
+                let Some(gao_key) = gaos.get(idx).filter(|gao| bf.is_key_valid(**gao)) else { continue; };
+                let Some(gao) = bf.object_table[gao_key].archetype.as_game_object() else { continue; };
+                network.lookup.insert((*way_key, idx as u16), network.nodes.len());
+                network.nodes.push(WayNode {
+                    way: *way_key,
+                    point: idx as u16,
+                    gao: *gao_key,
+                    position: gao.position(),
+                    width: point.width,
+                    flags: point.flags,
+                });
+            }
+
+            for (idx, point) in way.points.iter().enumerate() {
+                for link in point.links.iter().flatten() {
+                    network.connect((*way_key, idx as u16), (*way_key, *link), false);
+                }
+            }
+        }
+
+        for link_key in links.iter() {
+            let Some(link) = bf.object_table.get(link_key).and_then(|obj| obj.archetype.as_way_external_link()) else { continue; };
+            let refs = &bf.object_table[link_key].references;
+            let (Some(from_way), Some(to_way)) = (refs.first(), refs.get(1)) else { continue; };
+            network.connect((*from_way, link.from_point), (*to_way, link.to_point), true);
+        }
+
+        network.build_adjacency();
+        network
+    }
+
+    fn build_adjacency(&mut self) {
+        self.adjacency = vec![Vec::new(); self.nodes.len()];
+        for edge in self.edges.iter() {
+            self.adjacency[edge.from].push(edge.to);
+            self.adjacency[edge.to].push(edge.from);
+        }
+    }
+
+    fn connect(&mut self, from: (YKey, u16), to: (YKey, u16), external: bool) {
+        let (Some(from), Some(to)) = (self.lookup.get(&from).copied(), self.lookup.get(&to).copied()) else { return; };
+        if from == to || !self.edge_set.insert((from.min(to), from.max(to))) {
+            return;
+        }
+        self.edges.push(WayEdge { from, to, external });
+    }
+
+    pub fn neighbours(&self, node: usize) -> &[usize] {
+        &self.adjacency[node]
+    }
+
+    /// Shortest path by distance between two nodes, including both ends
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if from >= self.nodes.len() || to >= self.nodes.len() {
+            return None;
+        }
+
+        let mut dist = vec![f32::INFINITY; self.nodes.len()];
+        let mut prev = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        dist[from] = 0.0;
+        // f32 isn't Ord, distances are never negative so their bits sort the same way
+        queue.push(Reverse((0.0f32.to_bits(), from)));
+
+        while let Some(Reverse((d, node))) = queue.pop() {
+            let d = f32::from_bits(d);
+            if node == to {
+                break;
+            }
+            if d > dist[node] {
+                continue;
+            }
+            for next in self.neighbours(node).iter().copied() {
+                let nd = d + self.nodes[node].position.distance(self.nodes[next].position);
+                if nd < dist[next] {
+                    dist[next] = nd;
+                    prev[next] = Some(node);
+                    queue.push(Reverse((nd.to_bits(), next)));
+                }
+            }
+        }
+
+        if !dist[to].is_finite() {
+            return None;
+        }
+
+        let mut path = vec![to];
+        while let Some(node) = prev[*path.last().unwrap()] {
+            path.push(node);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Groups nodes into connected components
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut components = Vec::new();
+        for start in 0..self.nodes.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for next in self.neighbours(node).iter().copied() {
+                    if !seen[next] {
+                        seen[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAY: u32 = 0x1000;
+
+    /// A network of points on one way, connected by point links
+    fn network(positions: &[Vec3], links: &[(u16, u16)]) -> WayNetwork {
+        let mut network = WayNetwork::default();
+        for (idx, position) in positions.iter().enumerate() {
+            network.lookup.insert((YKey::from(WAY), idx as u16), idx);
+            network.nodes.push(WayNode {
+                way: YKey::from(WAY),
+                point: idx as u16,
+                gao: YKey::from(WAY + 1 + idx as u32),
+                position: *position,
+                width: 1.0,
+                flags: WayPointFlags::empty(),
+            });
+        }
+        for (from, to) in links.iter() {
+            network.connect((YKey::from(WAY), *from), (YKey::from(WAY), *to), false);
+        }
+        network.build_adjacency();
+        network
+    }
+
+    fn way_buf(points: &[(u16, u16, u16, f32)]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(WayFlags::CLOSED | WayFlags::NAVIGATION).bits().to_le_bytes());
+        buf.push(3);
+        buf.extend_from_slice(&2.5f32.to_le_bytes());
+        buf.extend_from_slice(&0xDEADBEEFu32.to_le_bytes());
+        buf.extend_from_slice(&(points.len() as u16).to_le_bytes());
+        for (prev, next, flags, width) in points {
+            buf.extend_from_slice(&prev.to_le_bytes());
+            buf.extend_from_slice(&next.to_le_bytes());
+            buf.extend_from_slice(&flags.to_le_bytes());
+            buf.extend_from_slice(&width.to_le_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn load_way() {
+        let buf = way_buf(&[(0xFFFF, 1, 1, 1.0), (0, 2, 0, 1.5), (1, 0xFFFF, 2, 2.0)]);
+        let mut way = Way::default();
+        assert_eq!(way.load_from_buf(&buf).unwrap(), buf.len());
+        assert!(way.decode_error.is_none());
+
+        assert_eq!(way.flags, WayFlags::CLOSED | WayFlags::NAVIGATION);
+        assert_eq!(way.way_type, 3);
+        assert_eq!(way.width, 2.5);
+        assert_eq!(way.unk_01, 0xDEADBEEF);
+        assert_eq!(way.raw_header[..], buf[..WAY_HEADER_SIZE]);
+
+        assert_eq!(way.points.len(), 3);
+        assert_eq!(way.points[0].links, [None, Some(1)]);
+        assert_eq!(way.points[1].links, [Some(0), Some(2)]);
+        assert_eq!(way.points[2].flags, WayPointFlags::END);
+        assert_eq!(way.points[1].width, 1.5);
+        assert_eq!(way.points[2].raw[..], buf[WAY_HEADER_SIZE + 2 + WAY_POINT_SIZE * 2..]);
+    }
+
+    #[test]
+    fn load_way_link_out_of_range() {
+        let buf = way_buf(&[(0xFFFF, 1, 0, 1.0), (0, 7, 0, 1.0)]);
+        let mut way = Way::default();
+        assert_eq!(way.load_from_buf(&buf).unwrap(), buf.len());
+        assert!(way.decode_error.is_some());
+    }
+
+    #[test]
+    fn connect_keeps_one_edge_per_pair() {
+        let network = network(&[Vec3::ZERO, Vec3::X], &[(0, 1), (1, 0), (0, 1), (1, 1), (0, 5)]);
+        assert_eq!(network.edges.len(), 1);
+        assert_eq!(network.neighbours(0), &[1]);
+        assert_eq!(network.neighbours(1), &[0]);
+    }
+
+    #[test]
+    fn components() {
+        let network = network(&[Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z], &[(0, 1), (2, 3)]);
+        let mut components: Vec<Vec<usize>> = network.components().into_iter()
+            .map(|mut component| { component.sort_unstable(); component })
+            .collect();
+        components.sort_unstable();
+        assert_eq!(components, vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn shortest_path_takes_the_shorter_route() {
+        // 0 -> 1 -> 3 is 2 long, 0 -> 2 -> 3 goes out of the way
+        let network = network(
+            &[Vec3::ZERO, Vec3::X, Vec3::new(1.0, 10.0, 0.0), Vec3::X * 2.0],
+            &[(0, 1), (1, 3), (0, 2), (2, 3)]
+        );
+        assert_eq!(network.shortest_path(0, 3), Some(vec![0, 1, 3]));
+        assert_eq!(network.shortest_path(3, 0), Some(vec![3, 1, 0]));
+        assert_eq!(network.shortest_path(2, 2), Some(vec![2]));
+    }
+
+    #[test]
+    fn shortest_path_without_route() {
+        let network = network(&[Vec3::ZERO, Vec3::X, Vec3::Y], &[(0, 1)]);
+        assert_eq!(network.shortest_path(0, 2), None);
+        assert_eq!(network.shortest_path(0, 3), None);
+        assert_eq!(network.shortest_path(7, 0), None);
+    }
+}
@@ -1,19 +1,155 @@
 use std::io::Read;
+use glam::{Vec3, Vec4};
+use strum::{Display, FromRepr};
+use crate::util::read_nul_term_string;
+use crate::util::load_util::{read_vec3, read_vec4};
 
 use super::*;
 
+#[derive(FromRepr, Display, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum WorldType {
+    #[default]
+    Main = 0,
+    Subworld = 1,
+    Streamed = 2,
+    /// only used as a source for other worlds, never instanced on its own
+    Template = 3,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WorldSettings {
+    pub ambient_color: Vec4,
+    pub fog_color: Vec4,
+    pub fog_near: f32,
+    pub fog_far: f32,
+    pub unk_01: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WorldLinks {
+    pub woc: YKey,
+    pub gol: YKey,
+    pub wal: YKey,
+    pub wil: YKey,
+    pub nav: YKey,
+    pub top: YKey,
+}
+
+impl WorldLinks {
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, YKey)> {
+        [("woc", self.woc), ("gol", self.gol), ("wal", self.wal), ("wil", self.wil), ("nav", self.nav), ("top", self.top)].into_iter()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StreamingLayer {
+    pub name: String,
+    pub unk_01: u32,
+    pub gol: YKey,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WorldData {
+    pub unk_01: u32,
+    pub world_type: WorldType,
+    pub flags: u8,
+    pub unk_02: u16,
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    pub settings: WorldSettings,
+    pub links: WorldLinks,
+    pub layers: Vec<StreamingLayer>,
+}
+
+#[derive(Default, EnumAsInner)]
+pub enum WorldDataState {
+    #[default]
+    None,
+    Decoded(WorldData),
+    /// holds the decode error, links and layers are then only reachable through the references
+    Unknown(String),
+}
+
 #[derive(Default)]
 pub struct World {
+    pub data: WorldDataState,
+}
+
+impl World {
+    fn read_data(cursor: &mut Cursor<&[u8]>) -> Result<WorldData, YetiIOError> {
+        let mut data = WorldData {
+            unk_01: cursor.read_u32::<LittleEndian>()?,
+            ..Default::default()
+        };
+
+        let type_id = cursor.read_u8()?;
+        data.world_type = WorldType::from_repr(type_id).ok_or(format!("unknown world type {:#04X}", type_id))?;
+        data.flags = cursor.read_u8()?;
+        data.unk_02 = cursor.read_u16::<LittleEndian>()?;
+
+        data.bounds_min = read_vec3(cursor)?;
+        data.bounds_max = read_vec3(cursor)?;
+        if !data.bounds_min.is_finite() || !data.bounds_max.is_finite() || data.bounds_min.cmpgt(data.bounds_max).any() {
+            return Err(format!("bad world bounds {} {}", data.bounds_min, data.bounds_max).into());
+        }
+
+        data.settings = WorldSettings {
+            ambient_color: read_vec4(cursor)?,
+            fog_color: read_vec4(cursor)?,
+            fog_near: cursor.read_f32::<LittleEndian>()?,
+            fog_far: cursor.read_f32::<LittleEndian>()?,
+            unk_01: cursor.read_u32::<LittleEndian>()?,
+        };
+
+        data.links = WorldLinks {
+            woc: cursor.read_u32::<LittleEndian>()?.into(),
+            gol: cursor.read_u32::<LittleEndian>()?.into(),
+            wal: cursor.read_u32::<LittleEndian>()?.into(),
+            wil: cursor.read_u32::<LittleEndian>()?.into(),
+            nav: cursor.read_u32::<LittleEndian>()?.into(),
+            top: cursor.read_u32::<LittleEndian>()?.into(),
+        };
+
+        let num_layers = cursor.read_u16::<LittleEndian>()?;
+        for _ in 0..num_layers {
+            data.layers.push(StreamingLayer {
+                name: read_nul_term_string(cursor)?,
+                unk_01: cursor.read_u32::<LittleEndian>()?,
+                gol: cursor.read_u32::<LittleEndian>()?.into(),
+            });
+        }
+
+        Ok(data)
+    }
 
+    /// Whether the world should be instanced on its own (e.g. when exporting)
+    pub fn is_instanced(&self) -> bool {
+        !matches!(&self.data, WorldDataState::Decoded(data) if data.world_type == WorldType::Template)
+    }
 }
 
 impl ArchetypeImpl for World {
-    fn load_from_buf(&mut self, _buf: &[u8]) -> Result<(), YetiIOError> {
-        Ok(())
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        match Self::read_data(&mut cursor) {
+            Ok(data) => {
+                self.data = WorldDataState::Decoded(data);
+                Ok(cursor.position() as usize)
+            },
+            Err(error) => {
+                log::debug!("couldn't decode world: {}", error);
+                self.data = WorldDataState::Unknown(error.to_string());
+                Ok(0)
+            }
+        }
     }
 
     fn unload(&mut self) {
-        
+        *self = Default::default();
+    }
+    fn decode_error(&self) -> Option<String> {
+        self.data.as_unknown().cloned()
     }
 }
 
@@ -27,7 +163,7 @@ pub struct RsfEntry {
 }
 
 impl ArchetypeImpl for Rsf {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
 
         let num = cursor.read_u32::<LittleEndian>()?;
@@ -56,7 +192,7 @@ impl ArchetypeImpl for Rsf {
 
         self.entries = entries;
 
-        Ok(())
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
@@ -8,9 +8,9 @@ pub struct YetiScript {
 }
 
 impl ArchetypeImpl for YetiScript {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         self.buffer = buf.to_vec();
-        Ok(())
+        Ok(buf.len())
     }
 
     fn unload(&mut self) {
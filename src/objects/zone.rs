@@ -1,35 +1,75 @@
 use std::io::Cursor;
-use byteorder::{LittleEndian, ReadBytesExt};
-use glam::{Mat4, Vec3};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use glam::{Mat4, Quat, Vec3, Vec4};
 use log::*;
 use strum::FromRepr;
-use crate::util::load_util::read_mat4;
+use crate::bigfile::Bigfile;
+use crate::metadata::{ObjectType, YKey};
+use crate::util::load_util::{read_mat4, read_vec3, read_vec4, write_mat4, write_vec3};
 
 use super::{ArchetypeImpl, YetiIOError};
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub struct Zone {
     pub unk_01: u8,
     pub unk_02: u8,
     pub zone_type: ZoneType,
+    /// type byte as read, saved as is while the shape isn't decoded so unknown types survive
+    pub type_id: u8,
     pub unk_04: u8,
     pub unk_05: u8,
-    pub unk_06: u8
+    pub unk_06: u8,
+    /// None when the zone has no modifier block or it couldn't be decoded
+    pub modifiers: Option<Vec<ZoneModifier>>,
+    /// set when the shape doesn't fit the payload, only the header is decoded and saved then
+    pub decode_error: Option<String>,
 }
 
+const ZONE_HEADER_SIZE: usize = 6;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub enum ZoneType {
+    /// no payload, the zone sits at its gao's position
     #[default]
     Point,
-    Ray,
+    Ray(ZoneRay),
     Sphere(ZoneSphere),
-    AABox,
+    AABox(ZoneAABox),
     OBox(ZoneOBox),
-    Capsule
+    Capsule(ZoneCapsule)
+}
+
+impl ZoneType {
+    /// where the shape sits relative to its owning gao
+    pub fn local_matrix(&self) -> Mat4 {
+        match self {
+            ZoneType::Point => Mat4::IDENTITY,
+            ZoneType::Ray(ray) => Mat4::from_rotation_translation(Quat::from_rotation_arc(Vec3::Z, ray.direction.normalize_or(Vec3::Z)), ray.origin),
+            ZoneType::Sphere(sphere) => Mat4::from_translation(Vec3::new(sphere.x, sphere.y, sphere.z)),
+            ZoneType::AABox(aabox) => Mat4::from_translation((aabox.min + aabox.max) / 2.0),
+            ZoneType::OBox(obox) => obox.matrix,
+            ZoneType::Capsule(capsule) => Mat4::from_rotation_translation(
+                Quat::from_rotation_arc(Vec3::Z, (capsule.end - capsule.start).normalize_or(Vec3::Z)),
+                (capsule.start + capsule.end) / 2.0
+            ),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ZoneType::Point => "point",
+            ZoneType::Ray(_) => "ray",
+            ZoneType::Sphere(_) => "sphere",
+            ZoneType::AABox(_) => "aabox",
+            ZoneType::OBox(_) => "obox",
+            ZoneType::Capsule(_) => "capsule",
+        }
+    }
 }
 
 pub trait ZoneTypeTrait {
     fn load_from_buf(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), YetiIOError>;
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError>;
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -49,6 +89,81 @@ impl ZoneTypeTrait for ZoneSphere {
 
         Ok(())
     }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_f32::<LittleEndian>(self.x)?;
+        buf.write_f32::<LittleEndian>(self.y)?;
+        buf.write_f32::<LittleEndian>(self.z)?;
+        buf.write_f32::<LittleEndian>(self.radius)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ZoneRay {
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub length: f32
+}
+
+impl ZoneTypeTrait for ZoneRay {
+    fn load_from_buf(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), YetiIOError> {
+        self.origin = read_vec3(cursor)?;
+        self.direction = read_vec3(cursor)?;
+        self.length = cursor.read_f32::<LittleEndian>()?;
+        Ok(())
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        write_vec3(buf, &self.origin)?;
+        write_vec3(buf, &self.direction)?;
+        buf.write_f32::<LittleEndian>(self.length)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ZoneAABox {
+    pub min: Vec3,
+    pub max: Vec3
+}
+
+impl ZoneTypeTrait for ZoneAABox {
+    fn load_from_buf(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), YetiIOError> {
+        self.min = read_vec3(cursor)?;
+        self.max = read_vec3(cursor)?;
+        Ok(())
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        write_vec3(buf, &self.min)?;
+        write_vec3(buf, &self.max)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ZoneCapsule {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub radius: f32
+}
+
+impl ZoneTypeTrait for ZoneCapsule {
+    fn load_from_buf(&mut self, cursor: &mut Cursor<&[u8]>) -> Result<(), YetiIOError> {
+        self.start = read_vec3(cursor)?;
+        self.end = read_vec3(cursor)?;
+        self.radius = cursor.read_f32::<LittleEndian>()?;
+        Ok(())
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        write_vec3(buf, &self.start)?;
+        write_vec3(buf, &self.end)?;
+        buf.write_f32::<LittleEndian>(self.radius)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -67,6 +182,14 @@ impl ZoneTypeTrait for ZoneOBox {
         );
         Ok(())
     }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        write_mat4(buf, &self.matrix)?;
+        buf.write_f32::<LittleEndian>(self.extents.x)?;
+        buf.write_f32::<LittleEndian>(self.extents.y)?;
+        buf.write_f32::<LittleEndian>(self.extents.z)?;
+        Ok(())
+    }
 }
 
 #[derive(FromRepr, Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -82,55 +205,331 @@ pub enum ZoneModType {
     CharacterDiffuseMultiplier = 6
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoneModifier {
+    Ambient { color: Vec4, intensity: f32 },
+    Fog { color: Vec4, start: f32, end: f32, density: f32 },
+    PostFx { post_fx: YKey },
+    SunLight { direction: Vec3, color: Vec4, intensity: f32, unk_01: f32 },
+    CharacterDiffuseMultiplier { multiplier: f32 },
+}
+
+impl ZoneModifier {
+    pub fn mod_type(&self) -> ZoneModType {
+        match self {
+            Self::Ambient { .. } => ZoneModType::Ambient,
+            Self::Fog { .. } => ZoneModType::Fog,
+            Self::PostFx { .. } => ZoneModType::PostFx,
+            Self::SunLight { .. } => ZoneModType::SunLight,
+            Self::CharacterDiffuseMultiplier { .. } => ZoneModType::CharacterDiffuseMultiplier,
+        }
+    }
+
+    fn load_from_buf(cursor: &mut Cursor<&[u8]>) -> Result<Self, YetiIOError> {
+        let id = cursor.read_u8()?;
+        let mod_type = ZoneModType::from_repr(id).ok_or(format!("invalid zone mod type {:#04X}", id))?;
+        Ok(match mod_type {
+            ZoneModType::Ambient => Self::Ambient {
+                color: read_vec4(cursor)?,
+                intensity: cursor.read_f32::<LittleEndian>()?
+            },
+            ZoneModType::Fog => Self::Fog {
+                color: read_vec4(cursor)?,
+                start: cursor.read_f32::<LittleEndian>()?,
+                end: cursor.read_f32::<LittleEndian>()?,
+                density: cursor.read_f32::<LittleEndian>()?
+            },
+            ZoneModType::PostFx => Self::PostFx {
+                post_fx: cursor.read_u32::<LittleEndian>()?.into()
+            },
+            ZoneModType::SunLight => Self::SunLight {
+                direction: read_vec3(cursor)?,
+                color: read_vec4(cursor)?,
+                intensity: cursor.read_f32::<LittleEndian>()?,
+                unk_01: cursor.read_f32::<LittleEndian>()?
+            },
+            ZoneModType::CharacterDiffuseMultiplier => Self::CharacterDiffuseMultiplier {
+                multiplier: cursor.read_f32::<LittleEndian>()?
+            },
+            // nothing is known about these, so we can't tell how big they are
+            ZoneModType::Sector | ZoneModType::Invalid => return Err(format!("can't read zone mod {:?}", mod_type).into())
+        })
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u8(self.mod_type() as u8)?;
+        match self {
+            Self::Ambient { color, intensity } => {
+                write_vec3(buf, &color.truncate())?;
+                buf.write_f32::<LittleEndian>(color.w)?;
+                buf.write_f32::<LittleEndian>(*intensity)?;
+            },
+            Self::Fog { color, start, end, density } => {
+                write_vec3(buf, &color.truncate())?;
+                buf.write_f32::<LittleEndian>(color.w)?;
+                buf.write_f32::<LittleEndian>(*start)?;
+                buf.write_f32::<LittleEndian>(*end)?;
+                buf.write_f32::<LittleEndian>(*density)?;
+            },
+            Self::PostFx { post_fx } => buf.write_u32::<LittleEndian>((*post_fx).into())?,
+            Self::SunLight { direction, color, intensity, unk_01 } => {
+                write_vec3(buf, direction)?;
+                write_vec3(buf, &color.truncate())?;
+                buf.write_f32::<LittleEndian>(color.w)?;
+                buf.write_f32::<LittleEndian>(*intensity)?;
+                buf.write_f32::<LittleEndian>(*unk_01)?;
+            },
+            Self::CharacterDiffuseMultiplier { multiplier } => buf.write_f32::<LittleEndian>(*multiplier)?,
+        }
+        Ok(())
+    }
+}
+
+impl Zone {
+    fn read_modifiers(cursor: &mut Cursor<&[u8]>) -> Result<Vec<ZoneModifier>, YetiIOError> {
+        let num_mods = cursor.read_u8()?;
+        let mut modifiers = Vec::with_capacity(num_mods as usize);
+        for _ in 0..num_mods {
+            modifiers.push(ZoneModifier::load_from_buf(cursor)?);
+        }
+        Ok(modifiers)
+    }
+}
+
 impl ArchetypeImpl for Zone {
-    fn load_from_buf(&mut self, buf: &[u8]) -> Result<(), YetiIOError> {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
         let mut cursor = Cursor::new(buf);
         self.unk_01 = cursor.read_u8()?;
         self.unk_02 = match cursor.read_u8()? {
             0x80 => 0x80,
             _ => return Err(format!("unk_02 not 0x80 {:#04X}", self.unk_02).into())
         };
-        self.zone_type = zone_type_from_id(cursor.read_u8()?);
+        self.type_id = cursor.read_u8()?;
         self.unk_04 = cursor.read_u8()?;
         self.unk_05 = cursor.read_u8()?;
         self.unk_06 = cursor.read_u8()?;
 
-        match &mut self.zone_type {
-            ZoneType::Sphere(sphere) => sphere.load_from_buf(&mut cursor)?,
-            ZoneType::OBox(obox) => obox.load_from_buf(&mut cursor)?,
-            _ => ()
+        let Some(zone_type) = zone_type_from_id(self.type_id) else {
+            self.decode_error = Some(format!("unknown zone type {:#04X}", self.type_id));
+            return Ok(ZONE_HEADER_SIZE);
+        };
+        self.zone_type = zone_type;
+
+        let shape = match &mut self.zone_type {
+            ZoneType::Point => Ok(()),
+            ZoneType::Sphere(sphere) => sphere.load_from_buf(&mut cursor),
+            ZoneType::OBox(obox) => obox.load_from_buf(&mut cursor),
+            ZoneType::Ray(ray) => ray.load_from_buf(&mut cursor),
+            ZoneType::AABox(aabox) => aabox.load_from_buf(&mut cursor),
+            ZoneType::Capsule(capsule) => capsule.load_from_buf(&mut cursor),
+        };
+        if let Err(error) = shape {
+            self.decode_error = Some(format!("couldn't read {} shape: {}", self.zone_type.name(), error));
+            return Ok(ZONE_HEADER_SIZE);
         }
-        
-        Ok(())
+
+        // anything after the shape is the modifier list, left as unparsed data if we can't make sense of it
+        if (cursor.position() as usize) < buf.len() {
+            let start = cursor.position();
+            match Self::read_modifiers(&mut cursor) {
+                Ok(modifiers) => self.modifiers = Some(modifiers),
+                Err(error) => {
+                    debug!("couldn't read zone modifiers: {}", error);
+                    cursor.set_position(start);
+                }
+            }
+        }
+
+        Ok(cursor.position() as usize)
     }
 
     fn unload(&mut self) {
         *self = Self::default()
     }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+
+    fn save_to_buf(&self, buf: &mut Vec<u8>) -> Result<(), YetiIOError> {
+        buf.write_u8(self.unk_01)?;
+        buf.write_u8(self.unk_02)?;
+        buf.write_u8(match self.decode_error {
+            Some(_) => self.type_id,
+            None => zone_type_to_id(self.zone_type)
+        })?;
+        buf.write_u8(self.unk_04)?;
+        buf.write_u8(self.unk_05)?;
+        buf.write_u8(self.unk_06)?;
+
+        // the shape and modifiers are still in the unparsed data
+        if self.decode_error.is_some() {
+            return Ok(());
+        }
+
+        match &self.zone_type {
+            ZoneType::Point => (),
+            ZoneType::Ray(ray) => ray.save_to_buf(buf)?,
+            ZoneType::Sphere(sphere) => sphere.save_to_buf(buf)?,
+            ZoneType::AABox(aabox) => aabox.save_to_buf(buf)?,
+            ZoneType::OBox(obox) => obox.save_to_buf(buf)?,
+            ZoneType::Capsule(capsule) => capsule.save_to_buf(buf)?,
+        }
+
+        if let Some(modifiers) = &self.modifiers {
+            buf.write_u8(modifiers.len() as u8)?;
+            for modifier in modifiers.iter() {
+                modifier.save_to_buf(buf)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub fn zone_type_to_id(zt: ZoneType) -> u8 {
     match zt {
         ZoneType::Point => 0,
-        ZoneType::Ray => 1,
+        ZoneType::Ray(_) => 1,
         ZoneType::Sphere(_) => 2,
-        ZoneType::AABox => 3,
+        ZoneType::AABox(_) => 3,
         ZoneType::OBox(_) => 4,
-        ZoneType::Capsule => 5
+        ZoneType::Capsule(_) => 5
     }
 }
 
-fn zone_type_from_id(byte: u8) -> ZoneType {
+fn zone_type_from_id(byte: u8) -> Option<ZoneType> {
     match byte {
-        0 => ZoneType::Point,
-        1 => ZoneType::Ray,
-        2 => ZoneType::Sphere(ZoneSphere::default()),
-        3 => ZoneType::AABox,
-        4 => ZoneType::OBox(ZoneOBox::default()),
-        5 => ZoneType::Capsule,
+        0 => Some(ZoneType::Point),
+        1 => Some(ZoneType::Ray(ZoneRay::default())),
+        2 => Some(ZoneType::Sphere(ZoneSphere::default())),
+        3 => Some(ZoneType::AABox(ZoneAABox::default())),
+        4 => Some(ZoneType::OBox(ZoneOBox::default())),
+        5 => Some(ZoneType::Capsule(ZoneCapsule::default())),
         _ => {
             error!("invalid zone type! {:#04X}", byte);
-            ZoneType::Point
+            None
         }
     }
-}
\ No newline at end of file
+}
+
+/// per-zone settings of a zone array, in the same order as the array's zon references
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ZoneArrayEntry {
+    pub flags: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct ZoneArray {
+    pub entries: Vec<ZoneArrayEntry>,
+    /// set when the entry count doesn't match the payload, the zones still load through the references
+    pub decode_error: Option<String>,
+}
+
+impl ArchetypeImpl for ZoneArray {
+    fn load_from_buf(&mut self, buf: &[u8]) -> Result<usize, YetiIOError> {
+        let mut cursor = Cursor::new(buf);
+        let num_entries = cursor.read_u32::<LittleEndian>()? as usize;
+        if num_entries * 4 != buf.len() - 4 {
+            self.decode_error = Some(format!("zone array count {} doesn't match payload size {}", num_entries, buf.len()));
+            return Ok(0);
+        }
+
+        for _ in 0..num_entries {
+            self.entries.push(ZoneArrayEntry {
+                flags: cursor.read_u32::<LittleEndian>()?,
+            });
+        }
+
+        Ok(cursor.position() as usize)
+    }
+
+    fn unload(&mut self) {
+        *self = Self::default()
+    }
+
+    fn decode_error(&self) -> Option<String> {
+        self.decode_error.clone()
+    }
+}
+
+/// A zone owned by a gao, `zone_array` is set when the zone was reached through a zar
+#[derive(Debug, Clone, Copy)]
+pub struct GaoZone {
+    pub zone: YKey,
+    pub zone_array: Option<YKey>,
+}
+
+/// Collects the zones a gao owns, either referenced directly or through its zone arrays
+pub fn find_gao_zones(bf: &Bigfile, key: YKey) -> Vec<GaoZone> {
+    let mut zones = Vec::new();
+    for subkey in bf.object_table[&key].references.iter().filter(|subkey| bf.is_key_valid(**subkey)) {
+        match bf.file_table[subkey].object_type {
+            ObjectType::zon => zones.push(GaoZone { zone: *subkey, zone_array: None }),
+            ObjectType::zar => {
+                for zon in bf.object_table[subkey].references.iter()
+                    .filter(|zon| bf.is_key_valid(**zon) && bf.file_table[*zon].object_type == ObjectType::zon) {
+                    zones.push(GaoZone { zone: *zon, zone_array: Some(*subkey) });
+                }
+            },
+            _ => { }
+        }
+    }
+    zones
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::test_util::assert_round_trip;
+
+    fn sphere_zone() -> Vec<u8> {
+        let mut buf = vec![0x01, 0x80, 2, 0x00, 0x10, 0x20];
+        for v in [1.0, 2.0, 3.0, 4.5] {
+            buf.write_f32::<LittleEndian>(v).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn round_trip_without_modifiers() {
+        let mut zone = Zone::default();
+        assert_round_trip(&mut zone, &sphere_zone());
+        assert!(zone.modifiers.is_none());
+    }
+
+    #[test]
+    fn round_trip_with_modifiers() {
+        let mut buf = sphere_zone();
+        buf.write_u8(3).unwrap();
+
+        buf.write_u8(ZoneModType::Ambient as u8).unwrap();
+        for v in [0.1, 0.2, 0.3, 1.0, 2.0] {
+            buf.write_f32::<LittleEndian>(v).unwrap();
+        }
+
+        buf.write_u8(ZoneModType::PostFx as u8).unwrap();
+        buf.write_u32::<LittleEndian>(0x12345678).unwrap();
+
+        buf.write_u8(ZoneModType::CharacterDiffuseMultiplier as u8).unwrap();
+        buf.write_f32::<LittleEndian>(0.75).unwrap();
+
+        let mut zone = Zone::default();
+        assert_round_trip(&mut zone, &buf);
+        assert_eq!(zone.modifiers.map(|modifiers| modifiers.len()), Some(3));
+    }
+
+    #[test]
+    fn header_only_fallback() {
+        // a truncated sphere and an unknown type both keep just the header, the rest stays unparsed
+        for buf in [sphere_zone()[..12].to_vec(), vec![0x01, 0x80, 9, 0x00, 0x10, 0x20, 0xAA]] {
+            let mut zone = Zone::default();
+            assert_eq!(zone.load_from_buf(&buf).unwrap(), ZONE_HEADER_SIZE);
+            assert!(zone.decode_error.is_some());
+
+            let mut saved = Vec::new();
+            zone.save_to_buf(&mut saved).unwrap();
+            assert_eq!(saved, buf[..ZONE_HEADER_SIZE]);
+        }
+    }
+}
@@ -1,57 +1,213 @@
-use crate::{objects::{ActionType, AnimEventData}, ui::{editors::EditorImpl, util::format_bytes_to_hex_wrapped}};
+use crate::{objects::{ActionType, AnimEvent, AnimEventData}, ui::{editors::EditorImpl, util::format_bytes_to_hex_wrapped}};
+use crate::bigfile::Bigfile;
 use super::*;
 
 
 pub struct AnimEventEditor;
 
+impl AnimEventEditor {
+    fn event_color(data: &AnimEventData) -> egui::Color32 {
+        match data {
+            AnimEventData::None => egui::Color32::GRAY,
+            AnimEventData::Sound(_) => egui::Color32::LIGHT_BLUE,
+            AnimEventData::Effect(_) => egui::Color32::from_rgb(255, 160, 60),
+            AnimEventData::Trigger(_) => egui::Color32::LIGHT_GREEN,
+        }
+    }
+
+    fn draw_timeline(ui: &mut egui::Ui, events: &[AnimEvent], last_frame: f32) {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 40.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        painter.hline(rect.x_range(), rect.center().y, ui.visuals().widgets.noninteractive.fg_stroke);
+
+        let frame_to_x = |frame: f32| rect.left() + 4.0 + (rect.width() - 8.0) * (frame / last_frame.max(1.0));
+        let mut hovered = Vec::new();
+        for (idx, event) in events.iter().enumerate() {
+            let Some(frame) = event.data.frame() else { continue; };
+            let x = frame_to_x(frame);
+            painter.vline(x, rect.y_range().shrink(6.0), egui::Stroke::new(2.0, Self::event_color(&event.data)));
+            if response.hover_pos().is_some_and(|pos| (pos.x - x).abs() < 3.0) {
+                hovered.push(format!("{} {} @ frame {}", idx, event.data, frame));
+            }
+        }
+
+        if !hovered.is_empty() {
+            response.on_hover_text(hovered.join("\n"));
+        }
+    }
+}
+
 impl EditorImpl for AnimEventEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
-        let Some(aev) = ectx.bf.object_table.get(&key) else { return; };
+        let bf = &ectx.bf;
+        let Some(aev) = bf.object_table.get(&key) else { return; };
         let ObjectArchetype::AnimEvent(aev) = &aev.archetype else { return; };
 
         ui.label(format!("num events: {}", aev.events.len()));
-        for (idx, event) in aev.events.iter().enumerate() {
-            ui.collapsing(format!("event index {} type {}", idx, event.data), |ui| {
-                ui.label(format!("event type {}", event.data));
-                ui.label(format!("flags {:08b}", event.flags));
-                ui.label(match &event.data {
-                    AnimEventData::None => String::from("None"),
-                    AnimEventData::Type01(buf) => format_bytes_to_hex_wrapped(buf),
-                    AnimEventData::Type02(buf) => format_bytes_to_hex_wrapped(buf),
-                    AnimEventData::Type03(buf) => format_bytes_to_hex_wrapped(buf)
-                })
+        let last_frame = aev.events.iter().filter_map(|event| event.data.frame()).fold(0.0, f32::max);
+        Self::draw_timeline(ui, &aev.events, last_frame);
+        ui.separator();
+
+        let mut order: Vec<usize> = (0..aev.events.len()).collect();
+        order.sort_by(|a, b| aev.events[*a].data.frame().unwrap_or(0.0).total_cmp(&aev.events[*b].data.frame().unwrap_or(0.0)));
+
+        let mut open_tab = None;
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            egui::Grid::new("aev_events").striped(true).show(ui, |ui| {
+                ui.label("frame");
+                ui.label("type");
+                ui.label("flags");
+                ui.label("fires");
+                ui.label("params");
+                ui.end_row();
+
+                for idx in order {
+                    let event = &aev.events[idx];
+                    ui.label(event.data.frame().map(|frame| format!("{:.1}", frame)).unwrap_or_default());
+                    ui.colored_label(Self::event_color(&event.data), format!("{}", event.data));
+                    ui.label(format!("{:08b}", event.flags));
+                    match event.data.referenced_key() {
+                        Some(subkey) if bf.is_key_valid(subkey) => ref_button(ui, bf, Some(subkey), &mut open_tab),
+                        Some(subkey) => { ui.label(format!("{:#010X} (missing)", subkey)); },
+                        None => { ui.label("-"); }
+                    }
+                    ui.label(match &event.data {
+                        AnimEventData::None => String::new(),
+                        AnimEventData::Sound(sound) => format!("until {:.1} vol {:.2} pitch {:.2} bone {} unk {}",
+                            sound.end_frame, sound.volume, sound.pitch, sound.bone, format_bytes_to_hex_wrapped(&sound.unk_01)),
+                        AnimEventData::Effect(effect) => format!("bone {} scale {:.2} unk {:#010X}", effect.bone, effect.scale, effect.unk_01),
+                        AnimEventData::Trigger(trigger) => format!("id {:#010X} params {:?} unk {:#06X}", trigger.event_id, trigger.params, trigger.unk_01),
+                    });
+                    ui.end_row();
+                }
             });
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
         }
     }
 }
 
+/// Resolves a reference index stored in an object's payload to the key it points to
+fn resolve_ref(bf: &Bigfile, key: YKey, ref_index: u16) -> Option<YKey> {
+    bf.object_table[&key].references.get(ref_index as usize)
+        .copied()
+        .filter(|subkey| bf.is_key_valid(*subkey))
+}
+
+fn ref_button(ui: &mut egui::Ui, bf: &Bigfile, subkey: Option<YKey>, open_tab: &mut Option<YKey>) {
+    match subkey {
+        Some(subkey) => {
+            if ui.selectable_label(false, format!("{:#010X} {}", subkey, bf.file_table[&subkey].get_name_ext())).clicked() {
+                *open_tab = Some(subkey);
+            }
+        },
+        None => { ui.label("-"); }
+    }
+}
+
 pub struct ListActionBankEditor;
 
 impl EditorImpl for ListActionBankEditor {
-    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, tctx: &EditorTabContext) {
-        let ObjectArchetype::ListActionBank(lab) = &ectx.bf.object_table.get(&key).unwrap().archetype else { return; };
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let ObjectArchetype::ListActionBank(lab) = &bf.object_table.get(&key).unwrap().archetype else { return; };
 
         ui.label(format!("version: {}", lab.version));
         ui.label(format!("num actions: {}", lab.num_actions));
+        ui.separator();
+
+        let mut open_tab = None;
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            egui::Grid::new("lab_actions").striped(true).show(ui, |ui| {
+                ui.label("id");
+                ui.label("flags");
+                ui.label("bank");
+                ui.label("action");
+                ui.label("animation");
+                ui.label("events");
+                ui.end_row();
+
+                for entry in lab.entries.iter() {
+                    ui.label(format!("{}", entry.action_id));
+                    ui.label(format!("{:08b}", entry.flags));
+
+                    let acb_key = resolve_ref(bf, key, entry.bank_ref as u16);
+                    ref_button(ui, bf, acb_key, &mut open_tab);
+
+                    // follow the bank down to the action and its animation
+                    let act_key = acb_key.and_then(|acb_key| {
+                        let acb = bf.object_table[&acb_key].archetype.as_action_bank()?;
+                        let act_ref = acb.find_action(entry.action_id)?.action_ref?;
+                        resolve_ref(bf, acb_key, act_ref)
+                    });
+                    ref_button(ui, bf, act_key, &mut open_tab);
+
+                    let anim = act_key.and_then(|act_key| {
+                        bf.object_table[&act_key].archetype.as_action()?.animation.map(|anim| (act_key, anim))
+                    });
+                    ref_button(ui, bf, anim.and_then(|(act_key, anim)| resolve_ref(bf, act_key, anim.anim_ref?)), &mut open_tab);
+                    ref_button(ui, bf, anim.and_then(|(act_key, anim)| resolve_ref(bf, act_key, anim.event_ref?)), &mut open_tab);
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
     }
 }
 
 pub struct ActionBankEditor;
 
 impl EditorImpl for ActionBankEditor {
-    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, tctx: &EditorTabContext) {
-        let ObjectArchetype::ActionBank(acb) = &ectx.bf.object_table.get(&key).unwrap().archetype else { return; };
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let ObjectArchetype::ActionBank(acb) = &bf.object_table.get(&key).unwrap().archetype else { return; };
 
         ui.label(format!("version: {}", acb.version));
         ui.label(format_bytes_to_hex_wrapped(&acb.unk_dat01));
+        ui.label(format!("actions: {}", acb.actions.len()));
+        ui.separator();
+
+        let mut open_tab = None;
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            for entry in acb.actions.iter() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("action {}", entry.action_id));
+                    ref_button(ui, bf, entry.action_ref.and_then(|idx| resolve_ref(bf, key, idx)), &mut open_tab);
+                });
+                if entry.transitions.is_empty() {
+                    continue;
+                }
+                ui.collapsing(format!("{} transitions##{}", entry.transitions.len(), entry.action_id), |ui| {
+                    egui::Grid::new(format!("acb_transitions_{}", entry.action_id)).striped(true).show(ui, |ui| {
+                        for transition in entry.transitions.iter() {
+                            ui.label(format!("to {}", transition.to_action));
+                            ui.label(format!("blend {:.3}s", transition.blend_time));
+                            ui.label(format!("flags {:#06X}", transition.flags));
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
     }
 }
 
 pub struct ActionEditor;
 
 impl EditorImpl for ActionEditor {
-    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, tctx: &EditorTabContext) {
-        let ObjectArchetype::Action(act) = &ectx.bf.object_table.get(&key).unwrap().archetype else { return; };
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let ObjectArchetype::Action(act) = &bf.object_table.get(&key).unwrap().archetype else { return; };
 
         ui.label(format!("unk_01: {:#06X}", act.unk_01));
         match act.action_type {
@@ -62,7 +218,34 @@ impl EditorImpl for ActionEditor {
                 ui.label(format!("f0: {}", data[0]));
                 ui.label(format!("f1: {}", data[1]));
                 ui.label(format!("f2: {}", data[2]));
-            }
+            },
+            ActionType::Unknown(action_type) => { ui.label(format!("unknown action type {:#010X}", action_type)); }
+        }
+
+        let Some(anim) = act.animation else { return; };
+        ui.separator();
+
+        let mut open_tab = None;
+        egui::Grid::new("action_animation").show(ui, |ui| {
+            ui.label("animation");
+            ref_button(ui, bf, anim.anim_ref.and_then(|idx| resolve_ref(bf, key, idx)), &mut open_tab);
+            ui.end_row();
+            ui.label("events");
+            ref_button(ui, bf, anim.event_ref.and_then(|idx| resolve_ref(bf, key, idx)), &mut open_tab);
+            ui.end_row();
+            ui.label("speed");
+            ui.label(format!("{}", anim.speed));
+            ui.end_row();
+            ui.label("flags");
+            ui.label(format!("{:08b}", anim.flags));
+            ui.end_row();
+            ui.label("blend in/out");
+            ui.label(format!("{:.3}s / {:.3}s", anim.blend.blend_in, anim.blend.blend_out));
+            ui.end_row();
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
         }
     }
-}
\ No newline at end of file
+}
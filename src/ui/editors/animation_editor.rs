@@ -0,0 +1,40 @@
+use super::*;
+
+#[derive(Default)]
+pub struct AnimationEditor {
+    frame: f32,
+}
+
+impl EditorImpl for AnimationEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let ObjectArchetype::Animation(ani) = &ectx.bf.object_table.get(&key).unwrap().archetype else { return; };
+
+        ui.label(format!("version: {:#010X}", ani.version));
+        ui.label(format!("unk_01: {:#010X}", ani.unk_01));
+        ui.label(format!("frames: {} @ {} fps ({:.2}s)", ani.num_frames, ani.frame_rate, ani.duration()));
+        ui.label(format!("tracks: {}", ani.tracks.len()));
+
+        let max_frame = ani.num_frames.saturating_sub(1) as f32;
+        self.frame = self.frame.min(max_frame);
+        ui.add(egui::Slider::new(&mut self.frame, 0.0..=max_frame).text("frame"));
+        ui.separator();
+
+        let poses = ani.sample(self.frame);
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            for (track, pose) in ani.tracks.iter().zip(poses.iter()) {
+                ui.collapsing(format!("bone {} - {:?}", track.bone, track.flags), |ui| {
+                    ui.label(format!("rot keys: {}  pos keys: {}  scl keys: {}", track.rotation.len(), track.translation.len(), track.scale.len()));
+                    if let Some(rot) = pose.rotation {
+                        ui.label(format!("rot: {}", rot));
+                    }
+                    if let Some(pos) = pose.translation {
+                        ui.label(format!("pos: {}", pos));
+                    }
+                    if let Some(scl) = pose.scale {
+                        ui.label(format!("scl: {}", scl));
+                    }
+                });
+            }
+        });
+    }
+}
@@ -1,12 +1,234 @@
 use super::*;
+use crate::objects::{CollisionObjectTable, GameMaterialList, ObjectArchetype};
+use super::game_material_editor::game_material_color;
+use crate::ui::util::format_bytes_to_hex_wrapped;
+use crate::ui::util::AppUiUtil;
+use glam::Vec3;
+
+#[derive(Debug, Default, strum_macros::Display, strum::EnumIter, PartialEq, Clone, Copy)]
+pub enum ShapeView {
+    #[default]
+    Top,
+    Front,
+    Side,
+}
+
+impl ShapeView {
+    fn project(&self, pos: Vec3) -> egui::Vec2 {
+        // yeti is z up, screen y goes down
+        match self {
+            Self::Top => egui::vec2(pos.x, -pos.y),
+            Self::Front => egui::vec2(pos.x, -pos.z),
+            Self::Side => egui::vec2(pos.y, -pos.z),
+        }
+    }
+}
+
+/// Color for a collision material index when it doesn't resolve to a game material
+pub fn collision_material_color(material: u16) -> egui::Color32 {
+    egui::ecolor::Hsva::new((material as f32 * 0.618_034).fract(), 0.7, 0.9, 1.0).into()
+}
+
+/// Draws triangles flattened along the view axis, fitted to the available width
+pub fn draw_collision_shape(ui: &mut egui::Ui, view: &mut ShapeView, positions: &[Vec3], faces: &[([u16; 3], egui::Color32)]) {
+    ui.horizontal(|ui| {
+        ui.label("view: ");
+        ui.enum_selector(view);
+    });
+
+    let size = egui::vec2(ui.available_width(), ui.available_width().min(400.0));
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+    let projected: Vec<egui::Vec2> = positions.iter().map(|pos| view.project(*pos)).collect();
+    let Some((min, max)) = projected.iter().fold(None, |acc: Option<(egui::Vec2, egui::Vec2)>, p| match acc {
+        Some((min, max)) => Some((min.min(*p), max.max(*p))),
+        None => Some((*p, *p))
+    }) else { return; };
+
+    let extent = (max - min).max(egui::vec2(0.001, 0.001));
+    let scale = f32::min((rect.width() - 20.0) / extent.x, (rect.height() - 20.0) / extent.y);
+    let offset = rect.center() - (min + extent / 2.0) * scale;
+    let to_screen = |idx: u16| projected.get(idx as usize).map(|p| offset + *p * scale);
+
+    for (indices, color) in faces {
+        let Some(points) = indices.iter().map(|idx| to_screen(*idx)).collect::<Option<Vec<_>>>() else { continue; };
+        painter.add(egui::Shape::convex_polygon(points.clone(), color.gamma_multiply(0.2), egui::Stroke::NONE));
+        painter.add(egui::Shape::closed_line(points, egui::Stroke::new(1.0, *color)));
+    }
+}
 
 #[derive(Default)]
-pub struct CollisionObjectEditor;
+pub struct CollisionObjectEditor {
+    view: ShapeView,
+    /// game material list of the collision it was found for
+    gml: Option<(YKey, Option<YKey>)>,
+}
 
 impl EditorImpl for CollisionObjectEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
         if ui.button("Export to .glb...").clicked() {
             ectx.respond(EditorResponse::GltfExport(key));
         }
+
+        let bf = &ectx.bf;
+        let Some(col) = bf.object_table.get(&key).and_then(|obj| obj.archetype.as_collision_object()) else { return; };
+        ui.label(format!("positions: {}", col.positions.len()));
+        ui.label(format!("faces: {}", col.faces.len()));
+        let num_invalid = col.faces.iter().filter(|face| !col.is_face_valid(face)).count();
+        if num_invalid > 0 {
+            ui.label(format!("invalid faces: {}", num_invalid));
+        }
+
+        let gml = match self.gml {
+            Some((cached, gml)) if cached == key => gml,
+            _ => {
+                let gml = GameMaterialList::find_for_collision(bf, &GameMaterialList::referenced_by(bf), key);
+                self.gml = Some((key, gml));
+                gml
+            }
+        };
+        let mut open_tab = None;
+        match gml {
+            Some(gml) => {
+                ui.horizontal(|ui| {
+                    ui.label("game materials: ");
+                    if ui.selectable_label(false, format!("{:#010X} {}", gml, bf.file_table[&gml].get_name_ext())).clicked() {
+                        open_tab = Some(gml);
+                    }
+                });
+            },
+            None => {
+                ui.label("no game material list found");
+            }
+        }
+
+        let materials: Vec<_> = col.materials().into_iter().map(|material| {
+            let resolved = gml.and_then(|gml| GameMaterialList::resolve(bf, gml, material));
+            let color = resolved.and_then(|(_, props)| props).map(|props| game_material_color(props.color()))
+                .unwrap_or_else(|| collision_material_color(material));
+            (material, resolved.map(|(gmt, _)| gmt), color)
+        }).collect();
+
+        ui.collapsing(format!("materials {}", materials.len()), |ui| {
+            for (material, gmt, color) in materials.iter() {
+                let count = col.faces.iter().filter(|face| face.material == *material).count();
+                ui.horizontal(|ui| {
+                    ui.colored_label(*color, format!("material {}: {} faces", material, count));
+                    if let Some(gmt) = gmt {
+                        if ui.selectable_label(false, format!("{:#010X} {}", gmt, bf.file_table[gmt].get_name_ext())).clicked() {
+                            open_tab = Some(*gmt);
+                        }
+                    }
+                });
+            }
+        });
+
+        let faces: Vec<_> = col.faces.iter()
+            .map(|face| (face.indices, materials.iter().find(|(material, _, _)| *material == face.material).map(|(_, _, color)| *color)
+                .unwrap_or_else(|| collision_material_color(face.material))))
+            .collect();
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            draw_collision_shape(ui, &mut self.view, &col.positions, &faces);
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CookedCollisionMeshEditor {
+    view: ShapeView,
+}
+
+impl EditorImpl for CookedCollisionMeshEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        if ui.button("Export to .glb...").clicked() {
+            ectx.respond(EditorResponse::GltfExport(key));
+        }
+
+        let Some(ccm) = ectx.bf.object_table.get(&key).and_then(|obj| obj.archetype.as_cooked_collision_mesh()) else { return; };
+        ui.label(format!("mesh type: {}", if ccm.mesh_tag.is_empty() { "unknown" } else { &ccm.mesh_tag }));
+
+        let Some(hull) = &ccm.hull else {
+            ui.label("no convex hull decoded");
+            return;
+        };
+
+        ui.label(format!("hull version: {}", hull.version));
+        ui.label(format!("vertices: {}", hull.vertices.len()));
+        ui.label(format!("edges: {}", hull.num_edges));
+        ui.label(format!("polygons: {}", hull.polygons.len()));
+
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            ui.collapsing("polygons", |ui| {
+                for (idx, poly) in hull.polygons.iter().enumerate() {
+                    ui.label(format!("{}: plane {} vertices {:?}", idx, poly.plane, poly.indices));
+                }
+            });
+
+            let color = ui.visuals().strong_text_color();
+            let faces: Vec<_> = hull.triangles().into_iter().map(|indices| (indices, color)).collect();
+            draw_collision_shape(ui, &mut self.view, &hull.vertices, &faces);
+        });
+    }
+}
+
+pub struct PhysicsStructureEditor;
+
+impl EditorImpl for PhysicsStructureEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let Some(phs) = ectx.bf.object_table.get(&key).and_then(|obj| obj.archetype.as_physics_structure()) else { return; };
+        let Some(body) = &phs.body else {
+            ui.label("physics body doesn't decode");
+            return;
+        };
+
+        ui.label(format!("body type: {:?}", body.body_type));
+        ui.label(format!("mass: {}", body.mass));
+        ui.label(format!("linear damping: {}", body.linear_damping));
+        ui.label(format!("angular damping: {}", body.angular_damping));
+        ui.label(format!("friction: {}", body.friction));
+        ui.label(format!("restitution: {}", body.restitution));
+        ui.label(format!("center of mass: {}", body.center_of_mass));
+        ui.label(format!("flags: {:#010X}", body.flags));
+    }
+}
+
+pub struct CollisionObjectTableEditor;
+
+impl EditorImpl for CollisionObjectTableEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        if ui.button("Export to .glb...").clicked() {
+            ectx.respond(EditorResponse::GltfExport(key));
+        }
+
+        let bf = &ectx.bf;
+        let Some(ObjectArchetype::CollisionObjectTable(cot)) = bf.object_table.get(&key).map(|obj| &obj.archetype) else { return; };
+        ui.label(format!("num collisions: {}", cot.num_collisions));
+
+        for (idx, entry) in cot.entries.iter().enumerate() {
+            ui.collapsing(format!("entry {} flags {:#010X}", idx, entry.flags), |ui| {
+                ui.label(format_bytes_to_hex_wrapped(&entry.data));
+            });
+        }
+
+        ui.separator();
+
+        let mut open_tab = None;
+        for object_type in [ObjectType::col, ObjectType::ccm, ObjectType::phs] {
+            for ref_key in CollisionObjectTable::find_references(bf, key, object_type) {
+                if ui.selectable_label(false, format!("{:#010X} {}", ref_key, bf.file_table[&ref_key].get_name_ext())).clicked() {
+                    open_tab = Some(ref_key);
+                }
+            }
+        }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
     }
-}
\ No newline at end of file
+}
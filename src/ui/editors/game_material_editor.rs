@@ -0,0 +1,91 @@
+use super::*;
+use crate::bigfile::Bigfile;
+use crate::objects::GameMaterialList;
+
+/// Surface color of a game material for drawing
+pub fn game_material_color(color: glam::Vec4) -> egui::Color32 {
+    egui::Rgba::from_rgba_unmultiplied(color.x, color.y, color.z, color.w).into()
+}
+
+/// Ids that happen to be keys in the bigfile are linked, anything else is just shown
+fn id_link(ui: &mut egui::Ui, bf: &Bigfile, label: &str, id: u32, open_tab: &mut Option<YKey>) {
+    ui.horizontal(|ui| {
+        ui.label(format!("{}: ", label));
+        let key = YKey::from(id);
+        if bf.is_key_valid(key) {
+            if ui.selectable_label(false, format!("{:#010X} {}", key, bf.file_table[&key].get_name_ext())).clicked() {
+                *open_tab = Some(key);
+            }
+        } else {
+            ui.label(format!("{:#010X}", id));
+        }
+    });
+}
+
+pub struct GameMaterialEditor;
+
+impl EditorImpl for GameMaterialEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let Some(mut props) = ectx.bf.object_table.get(&key).and_then(|obj| obj.archetype.as_game_material()).and_then(|gmt| gmt.props) else {
+            ui.label("game material doesn't decode");
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("surface type: ");
+            ui.add(egui::DragValue::new(&mut props.surface_type));
+            ui.colored_label(game_material_color(props.color()), "■");
+        });
+        ui.horizontal(|ui| {
+            ui.label("friction: ");
+            ui.add(egui::DragValue::new(&mut props.friction).speed(0.01).range(0.0..=16.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("restitution: ");
+            ui.add(egui::DragValue::new(&mut props.restitution).speed(0.01).range(0.0..=1.0));
+        });
+
+        let mut open_tab = None;
+        id_link(ui, ectx.bf, "sound id", props.sound_id, &mut open_tab);
+        id_link(ui, ectx.bf, "fx id", props.fx_id, &mut open_tab);
+
+        if let Some(gmt) = ectx.bf.object_table.get_mut(&key).and_then(|obj| obj.archetype.as_game_material_mut()) {
+            gmt.props = Some(props);
+        }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
+
+pub struct GameMaterialListEditor;
+
+impl EditorImpl for GameMaterialListEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let Some(gml) = bf.object_table.get(&key).and_then(|obj| obj.archetype.as_game_material_list()) else { return; };
+        ui.label(format!("num materials: {}", gml.num_materials));
+
+        let mut open_tab = None;
+        for (idx, gmt_key) in GameMaterialList::materials(bf, key).into_iter().enumerate() {
+            let props = bf.object_table.get(&gmt_key).and_then(|obj| obj.archetype.as_game_material()).and_then(|gmt| gmt.props);
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", idx));
+                if let Some(id) = gml.ids.get(idx) {
+                    ui.label(format!("id {:#010X}", id));
+                }
+                if let Some(props) = props {
+                    ui.colored_label(game_material_color(props.color()), format!("surface {}", props.surface_type));
+                }
+                if ui.selectable_label(false, format!("{:#010X} {}", gmt_key, bf.file_table[&gmt_key].get_name_ext())).clicked() {
+                    open_tab = Some(gmt_key);
+                }
+            });
+        }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
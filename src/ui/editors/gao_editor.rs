@@ -1,11 +1,63 @@
 use super::*;
-use crate::objects::ObjectArchetype;
+use crate::ui::util::format_bytes_to_hex_wrapped;
+use crate::objects::{find_gao_zones, GaoBlock, Light, ObjectArchetype};
 
 #[derive(Default)]
 pub struct GameobjectEditor {
     
 }
 
+impl GameobjectEditor {
+    fn ref_index_button(ui: &mut egui::Ui, ectx: &EditorContext, key: YKey, ref_index: u32, open_tab: &mut Option<YKey>) {
+        match ectx.bf.object_table[&key].references.get(ref_index as usize) {
+            Some(subkey) if ectx.bf.is_key_valid(*subkey) => {
+                if ui.selectable_label(false, format!("{:#010X} {}", subkey, ectx.bf.file_table[subkey].get_name_ext())).clicked() {
+                    *open_tab = Some(*subkey);
+                }
+            },
+            _ => { ui.label(format!("bad ref index {}", ref_index)); }
+        }
+    }
+
+    fn draw_block(ui: &mut egui::Ui, ectx: &EditorContext, key: YKey, block: &GaoBlock, open_tab: &mut Option<YKey>) {
+        match block {
+            GaoBlock::Attachments(attachments) => {
+                for attachment in attachments.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("bone {}", attachment.bone));
+                        Self::ref_index_button(ui, ectx, key, attachment.ref_index, open_tab);
+                    });
+                    ui.label(format!("pos: {}", attachment.matrix.w_axis.truncate()));
+                }
+            },
+            GaoBlock::InitialPos(matrix) => {
+                let (scl, rot, pos) = matrix.to_scale_rotation_translation();
+                ui.label(format!("pos: {}", pos));
+                ui.label(format!("rot: {}", rot));
+                ui.label(format!("scl: {}", scl));
+            },
+            GaoBlock::AABox { min, max } => {
+                ui.label(format!("min: {}", min));
+                ui.label(format!("max: {}", max));
+            },
+            GaoBlock::ZoneArray(indices) | GaoBlock::CollisionList(indices) => {
+                for idx in indices.iter() {
+                    Self::ref_index_button(ui, ectx, key, *idx, open_tab);
+                }
+            },
+            GaoBlock::OrientedBox { center, half_extents, rotation } => {
+                ui.label(format!("center: {}", center));
+                ui.label(format!("half extents: {}", half_extents));
+                ui.label(format!("rotation: {}", rotation));
+            },
+            GaoBlock::Raw(_, data) => {
+                ui.label(format!("{} bytes", data.len()));
+                ui.label(egui::RichText::new(format_bytes_to_hex_wrapped(data)).monospace());
+            }
+        }
+    }
+}
+
 impl EditorImpl for GameobjectEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
         if ui.button("Export to .glb...").clicked() {
@@ -28,7 +80,46 @@ impl EditorImpl for GameobjectEditor {
             ui.label(format!("mat z: {}", gao.matrix.z_axis));
             ui.label(format!("mat w: {}", gao.matrix.w_axis));
             ui.add_space(15.0);
-            ui.label(format!("light: {:?}", gao.light));
+            match gao.light {
+                Light::None => { ui.label("light: none"); },
+                _ => {
+                    ui.collapsing("light", |ui| {
+                        ui.label(format!("{:#?}", gao.light));
+                    });
+                }
+            }
+            ui.separator();
+
+            let mut open_tab = None;
+            let zones = find_gao_zones(ectx.bf, key);
+            if !zones.is_empty() {
+                ui.collapsing(format!("zones: {}", zones.len()), |ui| {
+                    for zone in zones.iter() {
+                        ui.horizontal(|ui| {
+                            zone_list_row(ui, ectx.bf, zone.zone, &mut open_tab);
+                            if let Some(zar) = zone.zone_array {
+                                if ui.selectable_label(false, format!("via {:#010X}", zar)).clicked() {
+                                    open_tab = Some(zar);
+                                }
+                            }
+                        });
+                    }
+                });
+                ui.separator();
+            }
+
+            ui.label(format!("blocks: {}", gao.blocks.len()));
+            egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                for (idx, block) in gao.blocks.iter().enumerate() {
+                    egui::CollapsingHeader::new(block.name()).id_salt(idx).show(ui, |ui| {
+                        Self::draw_block(ui, ectx, key, block, &mut open_tab);
+                    });
+                }
+            });
+
+            if let Some(key) = open_tab {
+                ectx.respond(EditorResponse::OpenNewTab(key));
+            }
         }
     }
 }
@@ -43,5 +134,26 @@ impl EditorImpl for GraphicObjectTableEditor {
         if ui.button("Export to .glb...").clicked() {
             ectx.respond(EditorResponse::GltfExport(key));
         }
+
+        let bf = &ectx.bf;
+        let mut open_tab = None;
+        if let Some(got) = bf.object_table.get(&key).and_then(|obj| obj.archetype.as_graphic_object_table()) {
+            if !got.lod_distances.is_empty() {
+                ui.label(format!("lod distances: {:?}", got.lod_distances));
+            }
+
+            let lods = got.mesh_lods(bf, key);
+            let has_lods = lods.iter().any(|(_, level)| *level > 0);
+            ui.label(if has_lods { "meshes:" } else { "meshes (no lods found):" });
+            for (mesh_key, level) in lods {
+                if ui.selectable_label(false, format!("lod {} {:#010X} {}", level, mesh_key, bf.file_table[&mesh_key].get_name_ext())).clicked() {
+                    open_tab = Some(mesh_key);
+                }
+            }
+        }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
     }
 }
\ No newline at end of file
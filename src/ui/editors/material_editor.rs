@@ -0,0 +1,77 @@
+use super::*;
+use crate::objects::MaterialData;
+use glam::Vec4;
+
+pub struct MaterialEditor;
+
+fn color_label(ui: &mut egui::Ui, name: &str, col: Vec4) {
+    ui.horizontal(|ui| {
+        let c = col.clamp(Vec4::ZERO, Vec4::ONE) * 255.0;
+        let (rect, _) = ui.allocate_exact_size([16.0, 16.0].into(), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, egui::Color32::from_rgb(c.x as u8, c.y as u8, c.z as u8));
+        ui.label(format!("{}: {}", name, col));
+    });
+}
+
+impl EditorImpl for MaterialEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let obj = ectx.bf.object_table.get(&key).unwrap();
+        let mat = match &obj.archetype {
+            ObjectArchetype::Material(mat) => mat,
+            _ => { return; }
+        };
+
+        let props = match &mat.data {
+            MaterialData::Decoded(props) => props,
+            MaterialData::Unknown(error) => {
+                ui.label(format!("couldn't decode material: {}", error));
+                return;
+            },
+            MaterialData::None => { return; }
+        };
+
+        ui.label(format!("unk_01: {:#010X}", props.unk_01));
+        ui.label(format!("flags: {:?}", props.flags));
+        ui.label(format!("blend mode: {}", props.blend_mode));
+        ui.label(format!("alpha ref: {} ({:.3})", props.alpha_ref, props.alpha_cutoff()));
+        ui.label(format!("unk_02: {:#06X}", props.unk_02));
+        ui.separator();
+        color_label(ui, "diffuse", props.diffuse);
+        color_label(ui, "specular", props.specular);
+        color_label(ui, "emissive", props.emissive);
+        ui.separator();
+
+        let mut open_tab = None;
+        ui.label(format!("textures: {}", props.textures.len()));
+        egui::Grid::new("material_textures").striped(true).show(ui, |ui| {
+            for tex in props.textures.iter() {
+                ui.label(format!("{}", tex.slot));
+                match obj.references.get(tex.ref_index as usize) {
+                    Some(tex_key) if ectx.bf.is_key_valid(*tex_key) => {
+                        if ui.selectable_label(false, format!("{:#010X} {}", tex_key, ectx.bf.file_table[tex_key].get_name_ext())).clicked() {
+                            open_tab = Some(*tex_key);
+                        }
+                    },
+                    _ => { ui.label(format!("bad ref index {}", tex.ref_index)); }
+                }
+                ui.label(format!("uv {}", tex.uv_set));
+                ui.label(format!("unk {:#04X}", tex.unk_01));
+                ui.end_row();
+            }
+        });
+        ui.separator();
+
+        ui.label(format!("params: {}", props.params.len()));
+        egui::Grid::new("material_params").striped(true).show(ui, |ui| {
+            for param in props.params.iter() {
+                ui.label(format!("{:#010X}", param.id));
+                ui.label(format!("{}", param.value));
+                ui.end_row();
+            }
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
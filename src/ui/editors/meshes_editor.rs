@@ -14,21 +14,49 @@ impl EditorImpl for MeshMetadataEditor {
         } else { None };
 
         let do_export = ui.button("Export to .glb").clicked();
+        let mut open_tab = None;
 
         if let ObjectArchetype::MeshMetadata(ref msh) = &ectx.bf.object_table.get(&key).unwrap().archetype {
+            let bf = &ectx.bf;
             ui.label(format!("num submeshes: {}", msh.num_submeshes));
             ui.label(format!("version: {}", msh.version));
+            ui.label(format!("layout: {:?}", msh.layout));
+
+            // LODs are either separate meshes of a got or repeated submeshes
+            let got_lod = bf.object_table.iter()
+                .filter(|(_, obj)| obj.references.contains(&key))
+                .find_map(|(got_key, obj)| obj.archetype.as_graphic_object_table()
+                    .and_then(|got| got.mesh_lods(bf, *got_key).into_iter().find(|(mesh_key, _)| *mesh_key == key))
+                    .map(|(_, level)| (*got_key, level)));
+            if let Some((got_key, level)) = got_lod {
+                if ui.selectable_label(false, format!("lod {} of got {:#010X} {}", level, got_key, bf.file_table[&got_key].get_name_ext())).clicked() {
+                    open_tab = Some(got_key);
+                }
+            }
+
+            let lod_levels = msd.and_then(|msd_key| bf.object_table.get(&msd_key))
+                .and_then(|obj| obj.archetype.as_mesh_data())
+                .map(|msd| msd.submesh_lod_levels(&msh.submeshes))
+                .unwrap_or_else(|| vec![0; msh.submeshes.len()]);
+            let num_lods = lod_levels.iter().max().map_or(1, |level| *level as usize + 1);
+            if num_lods > 1 {
+                ui.label(format!("submesh lods: {}", num_lods));
+            }
+
             ui.label(format!("unk_dat01: {}", msh.unk_dat01.iter().map(|b| format!("{:02X}", b)).collect::<Vec<String>>().join(" ")));
             ui.label(format!("unk_dat02: {}", msh.unk_dat02.iter().map(|b| format!("{:02X}", b)).collect::<Vec<String>>().join(" ")));
-            for idx in 0..msh.submeshes.len() {
-                let sb = &msh.submeshes[idx];
-                ui.collapsing(format!("submesh {}", idx), |ui| {
+            for (idx, (sb, lod_level)) in msh.submeshes.iter().zip(&lod_levels).enumerate() {
+                let header = match num_lods {
+                    1 => format!("submesh {}", idx),
+                    _ => format!("submesh {} (lod {})", idx, lod_level)
+                };
+                ui.collapsing(header, |ui| {
                     ui.label(format!("vtx_start: {}", sb.vtx_start));
                     ui.label(format!("vtx_num: {}", sb.vtx_num));
-                    ui.label(format!("  -calc vtx end: {}", sb.vtx_start + sb.vtx_num));
+                    ui.label(format!("  -calc vtx end: {}", sb.vtx_start as u32 + sb.vtx_num as u32));
                     ui.label(format!("face_start: {0} {0:#06X}", sb.face_start));
                     ui.label(format!("face_num: {0} {0:#06X}", sb.face_num));
-                    ui.label(format!("  -calc face end: {}", sb.face_start + sb.face_num * 3));
+                    ui.label(format!("  -calc face end: {}", sb.face_start as u32 + sb.face_num as u32 * 3));
                     ui.label(format_bytes_to_hex_wrapped(&sb.unk_dat01));
                     ui.label(format!("material index: {}", sb.material_index));
                     ui.label(format!("bone palette {}: {}", sb.bone_palette.len(), format_bytes_to_hex_wrapped(&sb.bone_palette)));
@@ -41,8 +69,8 @@ impl EditorImpl for MeshMetadataEditor {
                         let mut passed = true;
                         for idx in 0..msh.submeshes.len() {
                             let submesh = &msh.submeshes[idx];
-                            for vertex_index in submesh.vtx_start..(submesh.vtx_start + submesh.vtx_num) {
-                                let weights = &msd.vertex_data.weights[vertex_index as usize];
+                            for vertex_index in (submesh.vtx_start as usize)..(submesh.vtx_start as usize + submesh.vtx_num as usize) {
+                                let Some(weights) = msd.vertex_data.weights.get(vertex_index) else { continue; };
                                 for weight in weights {
                                     if weight.weight > 0.0 {
                                         let valid_bone = (weight.bone as usize) < submesh.bone_palette.len();
@@ -63,6 +91,10 @@ impl EditorImpl for MeshMetadataEditor {
         if do_export {
             ectx.respond(EditorResponse::GltfExport(key));
         }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
     }
 }
 
@@ -86,6 +118,17 @@ impl EditorImpl for MeshDataEditor {
             ui.label(format!("num submeshes: {}", msd.num_submeshes));
             ui.label(format!("pivot offset: {}", msd.pivot_offset));
             ui.label(format!("uniform scale: {}", msd.uniform_scale));
+            ui.label(format!("vertex layout: {:?}, stride {}", msd.layout, msd.layout.stride()));
+            ui.label(format!("index size: {}", msd.index_size));
+            ui.label(format!("decoded: {} vertices, {} faces", msd.vertex_data.pos.len(), msd.faces.len()));
+
+            if !msd.old_submeshes.is_empty() {
+                ui.collapsing(format!("old submeshes {}", msd.old_submeshes.len()), |ui| {
+                    for (idx, sb) in msd.old_submeshes.iter().enumerate() {
+                        ui.label(format!("submesh {}: vtx {} +{} face {} +{} material {}", idx, sb.vtx_start, sb.vtx_num, sb.face_start, sb.face_num, sb.material_index));
+                    }
+                });
+            }
 
             if ui.button("Export to .obj...").clicked() {
                 if let Some(path) = pick_exp_path(&obj, ".obj") {
@@ -97,20 +140,23 @@ impl EditorImpl for MeshDataEditor {
 
             if self.show_vertices {
                 egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
-                    for v in 0..msd.num_vertices as usize {
+                    let vd = &msd.vertex_data;
+                    for v in 0..vd.pos.len() {
                         ui.collapsing(format!("vertex {}", v), |ui| {
-                            ui.label(format_bytes_to_hex_wrapped(&msd.vertex_data.bufs[v]));
-                            ui.label(format!("pos: {}", msd.vertex_data.pos[v]));
-                            ui.label(format!("uv0: {}", msd.vertex_data.uv0[v]));
-                            ui.label(format!("uv1: {}", msd.vertex_data.uv1[v]));
-                            ui.label(format!("tan: {}", msd.vertex_data.tangents[v]));
-                            ui.label(format!("nrm: {}", msd.vertex_data.normals[v]));
+                            ui.label(format_bytes_to_hex_wrapped(vd.vertex_bytes(v)));
+                            ui.label(format!("pos: {}", vd.pos[v]));
+                            if let Some(uv0) = vd.uv0.get(v) { ui.label(format!("uv0: {}", uv0)); }
+                            if let Some(uv1) = vd.uv1.get(v) { ui.label(format!("uv1: {}", uv1)); }
+                            if let Some(tan) = vd.tangents.get(v) { ui.label(format!("tan: {}", tan)); }
+                            if let Some(nrm) = vd.normals.get(v) { ui.label(format!("nrm: {}", nrm)); }
+                            if let Some(col) = vd.colors.get(v) { ui.label(format!("col: {}", col)); }
                             
-                            ui.label("bones: ");
-                            let bone = &msd.vertex_data.weights[v];
-                            for b in 0..bone.len() {
-                                ui.label(format!("  bone{}: {:?}", &b, bone[b]));
-                            };
+                            if let Some(bone) = vd.weights.get(v) {
+                                ui.label("bones: ");
+                                for (b, weight) in bone.iter().enumerate() {
+                                    ui.label(format!("  bone{}: {:?}", b, weight));
+                                }
+                            }
                         });
                     }
                 });
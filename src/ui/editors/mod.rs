@@ -19,8 +19,12 @@ mod dtb_editor; use dtb_editor::*;
 mod vxc_editor; use vxc_editor::*;
 mod world_editor; use world_editor::*;
 mod collision_editor; use collision_editor::*;
+mod game_material_editor; use game_material_editor::*;
+mod nav_editor; use nav_editor::*;
 mod anim_other_editor; use anim_other_editor::*;
 mod way_editor; use way_editor::*;
+mod material_editor; use material_editor::*;
+mod animation_editor; use animation_editor::*;
 
 pub use crate::egui as egui;
 
@@ -48,10 +52,12 @@ pub fn create_editor_for_type(obj_type: &ObjectType) -> Box<dyn EditorImpl> {
         ObjectType::tga => Box::new(TextureMetadataEditor::default()),
         ObjectType::txd => Box::new(TextureDataEditor { }),
         ObjectType::snk => Box::new(SnkEditor { }),
-        ObjectType::shd => Box::new(ShaderGraphEditor { }),
+        ObjectType::shd => Box::new(ShaderGraphEditor::default()),
         ObjectType::ske => Box::new(SkeletonEditor::default()),
+        ObjectType::skp => Box::new(SkeletonProceduralEditor { }),
         ObjectType::eps => Box::new(EditableParamStructEditor { }),
         ObjectType::zon => Box::new(ZoneEditor { }),
+        ObjectType::zar => Box::new(ZoneArrayEditor { }),
         ObjectType::dbr => Box::new(DbrEditor { }),
         ObjectType::epl => Box::new(EditableParamsListEditor { }),
         ObjectType::dtb => Box::new(DataTableEditor { }),
@@ -60,12 +66,22 @@ pub fn create_editor_for_type(obj_type: &ObjectType) -> Box<dyn EditorImpl> {
         ObjectType::got => Box::new(GraphicObjectTableEditor::default()),
         ObjectType::wor => Box::new(WorldEditor::default()),
         ObjectType::col => Box::new(CollisionObjectEditor::default()),
+        ObjectType::cot => Box::new(CollisionObjectTableEditor { }),
+        ObjectType::ccm => Box::new(CookedCollisionMeshEditor::default()),
+        ObjectType::phs => Box::new(PhysicsStructureEditor { }),
+        ObjectType::gmt => Box::new(GameMaterialEditor { }),
+        ObjectType::gml => Box::new(GameMaterialListEditor { }),
+        ObjectType::nav => Box::new(NavDataEditor::default()),
         ObjectType::aev => Box::new(AnimEventEditor { }),
         ObjectType::lab => Box::new(ListActionBankEditor { }),
         ObjectType::acb => Box::new(ActionBankEditor { }),
         ObjectType::act => Box::new(ActionEditor { }),
         ObjectType::way => Box::new(WayEditor { }),
+        ObjectType::wel => Box::new(WayExternalLinkEditor { }),
+        ObjectType::wal => Box::new(WayListEditor::default()),
         ObjectType::rsf => Box::new(RsfEditor::default()),
+        ObjectType::mat => Box::new(MaterialEditor { }),
+        ObjectType::ani => Box::new(AnimationEditor::default()),
         _               => Box::new(BlankEditor { }),
     }
 }
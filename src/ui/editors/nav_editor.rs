@@ -0,0 +1,76 @@
+use super::*;
+use super::game_material_editor::game_material_color;
+use crate::objects::{surface_color, NavAreaFlags, NavData};
+
+fn area_color(area: NavAreaFlags) -> egui::Color32 {
+    game_material_color(surface_color(area.bits() as u64))
+}
+
+#[derive(Default)]
+pub struct NavDataEditor {
+    view: ShapeView,
+    /// worlds linking to the nav data they were found for
+    worlds: Option<(YKey, Vec<YKey>)>,
+}
+
+impl EditorImpl for NavDataEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        if ui.button("Export to .glb...").clicked() {
+            ectx.respond(EditorResponse::GltfExport(key));
+        }
+
+        let bf = &ectx.bf;
+        let mut open_tab = None;
+        if self.worlds.as_ref().is_none_or(|(cached, _)| *cached != key) {
+            self.worlds = Some((key, NavData::find_worlds(bf, key)));
+        }
+        let worlds = self.worlds.as_ref().map(|(_, worlds)| worlds.as_slice()).unwrap_or_default();
+        if worlds.is_empty() {
+            ui.label("no world links to this navmesh");
+        }
+        for wor in worlds.iter().copied() {
+            ui.horizontal(|ui| {
+                ui.label("world: ");
+                if ui.selectable_label(false, format!("{:#010X} {}", wor, bf.file_table[&wor].get_name_ext())).clicked() {
+                    open_tab = Some(wor);
+                }
+            });
+        }
+
+        if let Some(navmesh) = bf.object_table.get(&key).and_then(|obj| obj.archetype.as_nav_data()).and_then(|nav| nav.navmesh.as_ref()) {
+            ui.label(format!("version: {}", navmesh.version));
+            ui.label(format!("bounds: {} - {}", navmesh.bounds_min, navmesh.bounds_max));
+            ui.label(format!("vertices: {}", navmesh.vertices.len()));
+            ui.label(format!("polygons: {}", navmesh.polygons.len()));
+
+            egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                ui.collapsing(format!("areas {}", navmesh.areas().len()), |ui| {
+                    for area in navmesh.areas() {
+                        let count = navmesh.polygons.iter().filter(|poly| poly.area_flags == area).count();
+                        ui.colored_label(area_color(area), format!("{:?}: {} polygons", area, count));
+                    }
+                });
+
+                ui.collapsing("polygons", |ui| {
+                    for (idx, poly) in navmesh.polygons.iter().enumerate() {
+                        let neighbours: Vec<String> = poly.neighbours.iter()
+                            .map(|neighbour| neighbour.map_or("-".to_string(), |n| n.to_string()))
+                            .collect();
+                        ui.label(format!("{}: {:?} vertices {:?} neighbours [{}]", idx, poly.area_flags, poly.indices, neighbours.join(", ")));
+                    }
+                });
+
+                let faces: Vec<_> = navmesh.triangles().into_iter()
+                    .map(|(poly, indices)| (indices, area_color(navmesh.polygons[poly].area_flags)))
+                    .collect();
+                draw_collision_shape(ui, &mut self.view, &navmesh.vertices, &faces);
+            });
+        } else {
+            ui.label("navmesh doesn't decode");
+        }
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
@@ -1,42 +1,287 @@
 use super::*;
+use crate::ui::util::format_bytes_to_hex_wrapped;
 use crate::objects::{*, node_ids::ShaderNodeId, node_ids::ShaderNodeId::*};
 
 mod node_id_editors;
 use node_id_editors::*;
 
-pub struct ShaderGraphEditor;
+const NODE_WIDTH: f32 = 150.0;
+const NODE_HEADER: f32 = 22.0;
+const PIN_SPACING: f32 = 16.0;
+const COLUMN_SPACING: f32 = 220.0;
+const ROW_SPACING: f32 = 24.0;
+
+#[derive(Default)]
+pub struct ShaderGraphEditor {
+    graph: usize,
+    pan: egui::Vec2,
+    selected: Option<usize>,
+    show_glsl: bool,
+    compile_result: Option<Result<(), String>>,
+}
+
+impl ShaderGraphEditor {
+    fn node_height(graph: &ShaderGraph, node: usize) -> f32 {
+        let pins = graph.nodes[node].inputs.len().max(graph.num_outputs(node) as usize);
+        NODE_HEADER + pins as f32 * PIN_SPACING + 4.0
+    }
+
+    /// Places nodes in columns by their distance from the last node they feed, so outputs end up on the right
+    fn layout(graph: &ShaderGraph) -> Vec<egui::Pos2> {
+        let num_nodes = graph.nodes.len();
+        let consumers: Vec<Vec<usize>> = (0..num_nodes).map(|node| graph.consumers(node)).collect();
+        let mut depth = vec![0usize; num_nodes];
+        // longest path relaxation, capped so a cycle can't loop forever
+        for _ in 0..num_nodes {
+            let mut changed = false;
+            for node in 0..num_nodes {
+                let d = consumers[node].iter().map(|c| depth[*c] + 1).max().unwrap_or(0).min(num_nodes);
+                if d != depth[node] {
+                    depth[node] = d;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut column_height: Vec<f32> = Vec::new();
+        (0..num_nodes).map(|node| {
+            if column_height.len() <= depth[node] {
+                column_height.resize(depth[node] + 1, 0.0);
+            }
+            let y = column_height[depth[node]];
+            column_height[depth[node]] += Self::node_height(graph, node) + ROW_SPACING;
+            egui::pos2(-(depth[node] as f32) * COLUMN_SPACING, y)
+        }).collect()
+    }
+
+    fn draw_diagram(&mut self, ui: &mut egui::Ui, graph: &ShaderGraph) {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+        if response.dragged() {
+            self.pan += response.drag_delta();
+        }
+
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+
+        let origin = egui::pos2(rect.right() - NODE_WIDTH - 20.0, rect.top() + 20.0) + self.pan;
+        let positions: Vec<egui::Pos2> = Self::layout(graph).into_iter().map(|pos| origin + pos.to_vec2()).collect();
+        let node_rect = |node: usize| egui::Rect::from_min_size(positions[node], egui::vec2(NODE_WIDTH, Self::node_height(graph, node)));
+        let input_pos = |pin: ShaderPin| node_rect(pin.node).left_top() + egui::vec2(0.0, NODE_HEADER + (pin.pin as f32 + 0.5) * PIN_SPACING);
+        let output_pos = |pin: ShaderPin| node_rect(pin.node).right_top() + egui::vec2(0.0, NODE_HEADER + (pin.pin as f32 + 0.5) * PIN_SPACING);
+
+        let mut feeds_output = vec![false; graph.nodes.len()];
+        if let Some(main) = graph.main_output() {
+            feeds_output[main] = true;
+            for node in graph.upstream(main) {
+                feeds_output[node] = true;
+            }
+        }
+
+        let active = visuals.selection.stroke.color;
+        let inactive = visuals.widgets.noninteractive.fg_stroke.color.gamma_multiply(0.4);
+
+        for edge in graph.edges() {
+            let from = output_pos(edge.from);
+            let to = input_pos(edge.to);
+            let bend = ((to.x - from.x).abs() * 0.5).max(40.0);
+            let color = if feeds_output[edge.from.node] && feeds_output[edge.to.node] { active } else { inactive };
+            painter.add(egui::epaint::CubicBezierShape::from_points_stroke(
+                [from, from + egui::vec2(bend, 0.0), to - egui::vec2(bend, 0.0), to],
+                false,
+                egui::Color32::TRANSPARENT,
+                egui::Stroke::new(1.5, color)
+            ));
+        }
+
+        let mut hovered = None;
+        for (idx, node) in graph.nodes.iter().enumerate() {
+            let node_rect = node_rect(idx);
+            if !rect.intersects(node_rect) {
+                continue;
+            }
+            if response.hover_pos().is_some_and(|pos| node_rect.contains(pos)) {
+                hovered = Some(idx);
+            }
+
+            let stroke = if self.selected == Some(idx) {
+                egui::Stroke::new(2.0, visuals.strong_text_color())
+            } else if feeds_output[idx] {
+                egui::Stroke::new(1.0, active)
+            } else {
+                egui::Stroke::new(1.0, inactive)
+            };
+            painter.rect_filled(node_rect, 4.0, visuals.widgets.noninteractive.bg_fill);
+            painter.rect_stroke(node_rect, 4.0, stroke, egui::StrokeKind::Inside);
+            painter.text(
+                node_rect.left_top() + egui::vec2(6.0, NODE_HEADER * 0.5),
+                egui::Align2::LEFT_CENTER,
+                format!("{} {}", idx, node.get_id().trim_start_matches("eSID_")),
+                egui::FontId::proportional(12.0),
+                visuals.text_color()
+            );
+
+            for (pin, input) in node.inputs.iter().enumerate() {
+                let pos = input_pos(ShaderPin { node: idx, pin: pin as u32 });
+                if input.is_some() {
+                    painter.circle_filled(pos, 4.0, stroke.color);
+                } else {
+                    painter.circle_stroke(pos, 4.0, egui::Stroke::new(1.0, inactive));
+                }
+            }
+            for pin in 0..graph.num_outputs(idx) {
+                painter.circle_filled(output_pos(ShaderPin { node: idx, pin }), 4.0, stroke.color);
+            }
+        }
+
+        if response.clicked() {
+            self.selected = hovered;
+        }
+        if let Some(idx) = hovered {
+            let node = &graph.nodes[idx];
+            response.on_hover_text(format!("{} {}\n{} inputs, {} connected", idx, node.get_id(), node.inputs.len(), node.inputs.iter().flatten().count()));
+        }
+    }
+
+    fn draw_node(ui: &mut egui::Ui, node: &mut ShaderNode, ctx: &egui::Context) {
+        ui.label(format!("unk_01: {:#010X} {:#034b}", node.unk_01, node.unk_01));
+        ui.label(format!("payload size: {:#X}", node.payload_size));
+        if node.recovered {
+            ui.label("payload size didn't match, node was found by seeking for its id");
+        }
+        for (pin, input) in node.inputs.iter().enumerate() {
+            match input {
+                Some(from) => ui.label(format!("in {} <- node {} out {}", pin, from.node, from.pin)),
+                None => ui.label(format!("in {} <- not connected", pin))
+            };
+        }
+        if !node.extra.is_empty() {
+            ui.collapsing(format!("{} unparsed bytes", node.extra.len()), |ui| {
+                ui.label(egui::RichText::new(format_bytes_to_hex_wrapped(&node.extra)).monospace());
+            });
+        }
+        let old_node = std::mem::replace(&mut node.node, ShaderNodeId::Invalid);
+        node.node = draw_node_id_editor(old_node, ui, ctx);
+    }
+}
 
 impl EditorImpl for ShaderGraphEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
-        if let ObjectArchetype::ShaderGraph(shd) = &mut ectx.bf.object_table.get_mut(&key).unwrap().archetype {
+        let Some(ObjectArchetype::ShaderGraph(shd)) = ectx.bf.object_table.get_mut(&key).map(|obj| &mut obj.archetype) else { return; };
+
+        ui.horizontal(|ui| {
             ui.label(format!("version: {:#06X}", shd.version));
             ui.label(format!("flags: {:#06X} {:#018b}", shd.flags, shd.flags));
+        });
+        if shd.graphs.is_empty() {
+            ui.label("no graphs");
+            return;
+        }
 
-            let mut i = 0;
-            egui::ScrollArea::new([false, true]).auto_shrink([false, false]).show(ui, |ui| {
-                for graph in &mut shd.graphs {
-                    ui.collapsing(format!("{}", i), |ui| {
-                        ui.label(format!("unk_01: {:#010X} {:#034b}", graph.unk_01, graph.unk_01));
-                        ui.label(format!("unk_02: {:#010X} {:#034b}", graph.unk_02, graph.unk_02));
-                        ui.label(format!("unk_03: {:#010X} {:#034b}", graph.unk_03, graph.unk_03));
-                        ui.label(format!("unk_04: {:#010X} {:#034b}", graph.unk_04, graph.unk_04));
-                        ui.label(format!("num_nodes: {}", graph.num_nodes));
-                        ui.label(format!("unk_06: {:#010X}", graph.unk_06));
-                        
-                        let mut j = 0;
-                        for node in &mut graph.nodes {
-                            ui.collapsing(format!("{:#010X} {}", j, node.get_id()), |ui| {
-                                ui.label(format!("unk_01: {:#010X} {:#034b}", node.unk_01, node.unk_01));
-                                let old_node = std::mem::replace(&mut node.node, ShaderNodeId::Invalid);
-                                node.node = draw_node_id_editor(old_node, ui, ectx.ctx);
-                            });
-                            j += 1;
-                        }
-                    });
-                    i += 1;
+        ui.horizontal(|ui| {
+            ui.label("graph");
+            for i in 0..shd.graphs.len() {
+                if ui.selectable_label(self.graph == i, format!("{}", i)).clicked() && self.graph != i {
+                    self.graph = i;
+                    self.selected = None;
+                    self.compile_result = None;
                 }
+            }
+            if ui.button("reset view").clicked() {
+                self.pan = egui::Vec2::ZERO;
+            }
+            ui.toggle_value(&mut self.show_glsl, "GLSL");
+        });
+        self.graph = self.graph.min(shd.graphs.len() - 1);
+        if self.selected.is_some_and(|idx| idx >= shd.graphs[self.graph].nodes.len()) {
+            self.selected = None;
+        }
+
+        let classification = shd.classify();
+        let graph = &mut shd.graphs[self.graph];
+
+        if self.show_glsl {
+            egui::TopBottomPanel::bottom("shader_graph_glsl").resizable(true).show_inside(ui, |ui| {
+                let Some(mut source) = graph.to_glsl() else {
+                    ui.label("graph has no main output");
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    if ui.button("compile").clicked() {
+                        self.compile_result = Some(ectx.shader_cache.compile_source(&format!("shd_{:#010X}_{}", key, self.graph), &source));
+                    }
+                    if ui.button("copy").clicked() {
+                        ui.ctx().copy_text(source.clone());
+                    }
+                    match &self.compile_result {
+                        Some(Ok(())) => { ui.label("compiled"); },
+                        Some(Err(error)) => { ui.colored_label(ui.visuals().error_fg_color, error); },
+                        None => { }
+                    }
+                });
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    ui.add(egui::TextEdit::multiline(&mut source).code_editor().desired_width(f32::INFINITY).interactive(false));
+                });
             });
         }
+
+        egui::SidePanel::right("shader_graph_side").resizable(true).show_inside(ui, |ui| {
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                match &classification {
+                    Some(class) => {
+                        ui.collapsing(format!("material classification (graph {})", class.graph), |ui| {
+                            ui.label(format!("alpha: {:?}", class.alpha));
+                            ui.label(format!("unlit: {}  emissive: {}  normal mapped: {}", class.unlit, class.emissive, class.normal_mapped));
+                            for tex in class.textures.iter() {
+                                let uses: Vec<&str> = [(tex.diffuse, "diffuse"), (tex.alpha, "alpha"), (tex.normal, "normal"), (tex.specular, "specular"), (tex.emissive, "emissive")]
+                                    .into_iter().filter(|u| u.0).map(|u| u.1).collect();
+                                let label = format!("node {}: slot {} -> {}", tex.node, tex.texture_slot, uses.join(", "));
+                                if ui.selectable_label(self.graph == class.graph && self.selected == Some(tex.node), label).clicked() {
+                                    self.graph = class.graph;
+                                    self.selected = Some(tex.node);
+                                }
+                            }
+                        });
+                    },
+                    None => { ui.label("no main output to classify"); }
+                }
+                ui.separator();
+                ui.label(format!("unk_01: {:#010X} {:#034b}", graph.unk_01, graph.unk_01));
+                ui.label(format!("unk_02: {:#010X} {:#034b}", graph.unk_02, graph.unk_02));
+                ui.label(format!("unk_03: {:#010X} {:#034b}", graph.unk_03, graph.unk_03));
+                ui.label(format!("unk_04: {:#010X} {:#034b}", graph.unk_04, graph.unk_04));
+                ui.label(format!("num_nodes: {}", graph.num_nodes));
+                ui.label(format!("unk_06: {:#010X}", graph.unk_06));
+                match graph.main_output() {
+                    Some(main) => ui.label(format!("main output: node {}, fed by {} nodes", main, graph.upstream(main).len())),
+                    None => ui.label("no main output")
+                };
+                ui.separator();
+
+                // the selection can point into another graph until the next frame
+                if let Some((idx, node)) = self.selected.and_then(|idx| graph.nodes.get_mut(idx).map(|node| (idx, node))) {
+                    ui.label(format!("{} {}", idx, node.get_id()));
+                    ui.push_id("selected", |ui| Self::draw_node(ui, node, ectx.ctx));
+                    ui.separator();
+                }
+
+                for (j, node) in graph.nodes.iter_mut().enumerate() {
+                    let title = if node.recovered {
+                        format!("{:#010X} {} (recovered)", j, node.get_id())
+                    } else {
+                        format!("{:#010X} {}", j, node.get_id())
+                    };
+                    ui.collapsing(title, |ui| {
+                        Self::draw_node(ui, node, ectx.ctx);
+                    });
+                }
+            });
+        });
+
+        self.draw_diagram(ui, graph);
     }
 }
 
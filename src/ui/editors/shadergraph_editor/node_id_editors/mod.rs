@@ -3,7 +3,6 @@
 use crate::objects::node_ids::*;
 use crate::egui as egui;
 
-
 pub fn draw_eSID_ADD(mut node:eSID_ADD, ui: &mut egui::Ui, _ctx: &egui::Context) -> ShaderNodeId {
 
     ShaderNodeId::eSID_ADD(node)
@@ -565,4 +564,4 @@ pub fn draw_eSID_WorldPosition(mut node:eSID_WorldPosition, ui: &mut egui::Ui, _
 pub fn draw_eSID_WorldSinusFX_VC(mut node:eSID_WorldSinusFX_VC, ui: &mut egui::Ui, _ctx: &egui::Context) -> ShaderNodeId {
 
     ShaderNodeId::eSID_WorldSinusFX_VC(node)
-}
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
 use super::*;
-use crate::objects::{Bone, ObjectArchetype};
+use crate::bigfile::Bigfile;
+use crate::objects::{Bone, BoneConstraintType, ObjectArchetype, ProceduralBone, ProceduralBoneKind, Skeleton};
+use crate::ui::util::format_bytes_to_hex_wrapped;
 
 pub struct SkeletonEditor {
     hierarchy_view: bool,
@@ -13,38 +15,88 @@ impl Default for SkeletonEditor {
     }
 }
 
+fn bone_name(bones: &[Bone], idx: Option<u8>) -> String {
+    match idx {
+        Some(idx) => match bones.get(idx as usize) {
+            Some(bone) => format!("{} {}", idx, bone.get_name()),
+            None => format!("{} (missing)", idx)
+        },
+        None => String::from("-")
+    }
+}
+
+fn draw_bone_data(ui: &mut egui::Ui, bones: &[Bone], bone: &Bone) {
+    ui.label(format!("parent: {}", bone_name(bones, bone.parent)));
+    ui.label(format!("flags: {:?}", bone.flags));
+    ui.label(format!("lod mask: {:#010b}", bone.lod_mask));
+
+    let source = if bone.local_decoded { "" } else { " (from mesh space)" };
+    ui.label(format!("local pos{}: {}", source, bone.local.translation));
+    ui.label(format!("local rot{}: {}", source, bone.local.rotation));
+    ui.label(format!("local scl{}: {}", source, bone.local.scale));
+
+    let constraint = &bone.constraint;
+    if constraint.constraint_type != BoneConstraintType::None {
+        ui.label(format!("constraint: {:?} target {} weight {} limits {:?}",
+            constraint.constraint_type, bone_name(bones, constraint.target), constraint.weight, constraint.limits));
+    }
+
+    let (scl, rot, pos) = bone.mesh_space_matrix.to_scale_rotation_translation();
+    ui.label(format!("bind pos: {}", pos));
+    ui.label(format!("bind rot: {}", rot));
+    ui.label(format!("bind scl: {}", scl));
+    let (scl, rot, pos) = bone.inv_bind_matrix.to_scale_rotation_translation();
+    ui.label(format!("inv bind pos: {}", pos));
+    ui.label(format!("inv bind rot: {}", rot));
+    ui.label(format!("inv bind scl: {}", scl));
+
+    ui.label(format!("unk_02: {}", format_bytes_to_hex_wrapped(&bone.unk_02)));
+    ui.collapsing("raw data", |ui| {
+        ui.label(format_bytes_to_hex_wrapped(&bone.data));
+    });
+}
+
+fn draw_procedural_bone(ui: &mut egui::Ui, bones: &[Bone], bone: &ProceduralBone) {
+    ui.label(format!("parent: {}", bone_name(bones, bone.parent)));
+    ui.label(format!("local pos: {}", bone.translation));
+    ui.label(format!("local rot: {}", bone.rotation));
+    match bone.kind {
+        ProceduralBoneKind::Twist { source, axis, factor } => {
+            ui.label(format!("twist from {} around {} by {}", bone_name(bones, Some(source)), axis, factor));
+        },
+        ProceduralBoneKind::Jiggle { stiffness, damping, gravity } => {
+            ui.label(format!("jiggle stiffness {} damping {} gravity {}", stiffness, damping, gravity));
+        },
+        ProceduralBoneKind::LookAt { target } => {
+            ui.label(format!("look at {}", bone_name(bones, Some(target))));
+        },
+        ProceduralBoneKind::CopyTransform { source, weight } => {
+            ui.label(format!("copy transform of {} weight {}", bone_name(bones, Some(source)), weight));
+        },
+    }
+}
+
+fn draw_procedural_bones(ui: &mut egui::Ui, bf: &Bigfile, skp_key: YKey, bones: &[Bone]) {
+    let Some(ObjectArchetype::SkeletonProcedural(skp)) = bf.object_table.get(&skp_key).map(|obj| &obj.archetype) else { return; };
+    if skp.bones.is_empty() {
+        ui.label(format!("procedural data {:#010X} has no decoded bones", skp_key));
+        return;
+    }
+
+    for (idx, bone) in skp.bones.iter().enumerate() {
+        ui.collapsing(format!("{} {} ({})", idx, bone.get_name(), bone.kind.name()), |ui| {
+            draw_procedural_bone(ui, bones, bone);
+        });
+    }
+}
+
 impl SkeletonEditor{
     fn draw_bone_hierarchy(&self, ui: &mut egui::Ui, bones: &Vec<Bone>, idx: u8) {
         let bone = &bones[idx as usize];
 
         ui.collapsing(format!("{} {}", idx, bone.get_name()), |ui| {
             ui.collapsing("     -data", |ui| {
-                
-                let (scl, rot, pos) = bone.mesh_space_matrix.to_scale_rotation_translation();
-                ui.label(format!("bind pos: {}", pos));
-                ui.label(format!("bind rot: {}", rot));
-                ui.label(format!("bind scl: {}", scl));
-                let (scl, rot, pos) = bone.inv_bind_matrix.to_scale_rotation_translation();
-                ui.label(format!("inv bind pos: {}", pos));
-                ui.label(format!("inv bind rot: {}", rot));
-                ui.label(format!("inv bind scl: {}", scl));
-
-                let parent_byte: u8 = bone.parent.unwrap_or(0xFF);
-
-                let formatted_str = bone.unk_01.iter().copied()
-                    .chain(std::iter::once(parent_byte))
-                    .chain(bone.data.iter().copied())
-                    .collect::<Vec<u8>>()
-                    .chunks_exact(8)
-                    .map(|c| {
-                        format!("{:02X} {:02X} {:02X} {:02X}  {:02X} {:02X} {:02X} {:02X}",
-                            c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]
-                        )
-                    })
-                    .collect::<Vec<String>>()
-                    .join("\r\n");
-
-                ui.label(formatted_str);
+                draw_bone_data(ui, bones, bone);
             });
 
             for child in &bone.children {
@@ -56,47 +108,70 @@ impl SkeletonEditor{
 
 impl super::EditorImpl for SkeletonEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
-        if let ObjectArchetype::Skeleton(ske) = &ectx.bf.object_table.get(&key).unwrap().archetype {
+        let bf = &ectx.bf;
+        if let ObjectArchetype::Skeleton(ske) = &bf.object_table.get(&key).unwrap().archetype {
             ui.label(format!("version: {:#04X}", ske.version));
             ui.label(format!("num_bones: {}", ske.num_bones));
             ui.label(format!("unk_01: {:#04X}", ske.unk_01));
             ui.checkbox(&mut self.hierarchy_view, "hierarchy view");
 
-            if ske.num_bones == 0 { 
+            if ske.num_bones == 0 {
                 ui.label("no bones :( i'm just a sack of meat");
-                return; 
+                return;
             }
 
-            if self.hierarchy_view {
-                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+            egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                if let Some(skp_key) = Skeleton::find_procedural(bf, key) {
+                    ui.collapsing(format!("procedural bones {:#010X}", skp_key), |ui| {
+                        draw_procedural_bones(ui, bf, skp_key, &ske.bones);
+                    });
+                }
+
+                if self.hierarchy_view {
                     self.draw_bone_hierarchy(ui, &ske.bones, 0);
-                });
-            } else {
-                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                } else {
                     for (idx, bone) in ske.bones.iter().enumerate() {
                         ui.collapsing(format!("{} {}", idx, bone.get_name()), |ui| {
-                            ui.label(format!("parent: {:?}", bone.parent));
-
-                            let parent_byte: u8 = bone.parent.unwrap_or(0xFF);
-
-                            let formatted_str = bone.unk_01.iter().copied()
-                                .chain(std::iter::once(parent_byte))
-                                .chain(bone.data.iter().copied())
-                                .collect::<Vec<u8>>()
-                                .chunks_exact(8)
-                                .map(|c| {
-                                    format!("{:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X}",
-                                        c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]
-                                    )
-                                })
-                                .collect::<Vec<String>>()
-                                .join("\r\n");
-            
-                            ui.label(formatted_str);
+                            draw_bone_data(ui, &ske.bones, bone);
                         });
                     }
-                });
-            }
+                }
+            });
         }
     }
-}
\ No newline at end of file
+}
+
+pub struct SkeletonProceduralEditor;
+
+impl super::EditorImpl for SkeletonProceduralEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let Some(ObjectArchetype::SkeletonProcedural(skp)) = bf.object_table.get(&key).map(|obj| &obj.archetype) else { return; };
+
+        // bone indices point into the skeleton that references this
+        let skeleton = bf.object_table.iter()
+            .filter(|(_, obj)| obj.references.contains(&key))
+            .find_map(|(ske_key, obj)| obj.archetype.as_skeleton().map(|ske| (*ske_key, ske)));
+
+        ui.label(format!("version: {:#04X}", skp.version));
+        ui.label(format!("num bones: {}", skp.bones.len()));
+        let mut open_tab = None;
+        match skeleton {
+            Some((ske_key, _)) => {
+                if ui.selectable_label(false, format!("skeleton: {:#010X} {}", ske_key, bf.file_table[&ske_key].get_name_ext())).clicked() {
+                    open_tab = Some(ske_key);
+                }
+            },
+            None => { ui.label("skeleton not loaded, bone indices can't be resolved"); }
+        }
+        ui.separator();
+
+        egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+            draw_procedural_bones(ui, bf, key, skeleton.map(|(_, ske)| ske.bones.as_slice()).unwrap_or(&[]));
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
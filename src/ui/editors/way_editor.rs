@@ -1,21 +1,36 @@
 use glam::Vec3;
 
-use crate::ui::util::format_bytes_to_hex;
+use crate::bigfile::Bigfile;
+use crate::objects::WayNetwork;
+use crate::ui::util::format_bytes_to_hex_wrapped;
 
 use super::*;
 
+fn key_label(ui: &mut egui::Ui, bf: &Bigfile, key: YKey, open_tab: &mut Option<YKey>) {
+    if !bf.is_key_valid(key) {
+        ui.label(format!("{:#010X} (missing)", key));
+    } else if ui.selectable_label(false, format!("{:#010X} {}", key, bf.file_table[&key].get_name_ext())).clicked() {
+        *open_tab = Some(key);
+    }
+}
+
+fn link_text(link: Option<u16>) -> String {
+    link.map(|l| l.to_string()).unwrap_or(String::from("-"))
+}
+
 pub struct WayEditor;
 
 impl EditorImpl for WayEditor {
-    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, tctx: &EditorTabContext) {
-        let Some(object) = &ectx.bf.object_table.get(&key) else { return; };
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let Some(object) = bf.object_table.get(&key) else { return; };
         let ObjectArchetype::Way(ref way) = object.archetype else { return; };
 
         let mut min = Vec3::INFINITY;
         let mut max = Vec3::NEG_INFINITY;
-        
+
         for key in &object.references {
-            let ObjectArchetype::GameObject(gao) = &ectx.bf.object_table.get(key).unwrap().archetype else { continue; };
+            let ObjectArchetype::GameObject(gao) = &bf.object_table.get(key).unwrap().archetype else { continue; };
 
             min = min.min(gao.position());
             max = max.max(gao.position());
@@ -24,11 +39,156 @@ impl EditorImpl for WayEditor {
         ui.label(format!("bounds min: {}", min));
         ui.label(format!("bounds max: {}", max));
 
-        ui.label(format_bytes_to_hex(&way.unk_dat01));
+        ui.label(format!("flags: {:?}", way.flags));
+        ui.label(format!("type: {}", way.way_type));
+        ui.label(format!("width: {}", way.width));
+        ui.label(format!("unk_01: {:#010X}", way.unk_01));
+        ui.label(egui::RichText::new(format!("header: {}", format_bytes_to_hex_wrapped(&way.raw_header))).monospace());
         ui.label(format!("num way gaos: {}", way.num_way_gaos));
+        ui.separator();
+
+        let mut open_tab = None;
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            egui::Grid::new("way_points").striped(true).show(ui, |ui| {
+                ui.label("point");
+                ui.label("gao");
+                ui.label("links");
+                ui.label("flags");
+                ui.label("width");
+                ui.label("raw");
+                ui.end_row();
+
+                for (idx, point) in way.points.iter().enumerate() {
+                    ui.label(format!("{}", idx));
+                    match object.references.get(idx) {
+                        Some(gao) => key_label(ui, bf, *gao, &mut open_tab),
+                        None => { ui.label("-"); }
+                    }
+                    ui.label(format!("{} / {}", link_text(point.links[0]), link_text(point.links[1])));
+                    ui.label(format!("{:?}", point.flags));
+                    ui.label(format!("{}", point.width));
+                    ui.label(egui::RichText::new(format_bytes_to_hex_wrapped(&point.raw)).monospace());
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
+
+pub struct WayExternalLinkEditor;
+
+impl EditorImpl for WayExternalLinkEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let Some(object) = bf.object_table.get(&key) else { return; };
+        let ObjectArchetype::WayExternalLink(ref link) = object.archetype else { return; };
+
+        let mut open_tab = None;
+        egui::Grid::new("wel").show(ui, |ui| {
+            ui.label("from");
+            if let Some(way) = object.references.first() {
+                key_label(ui, bf, *way, &mut open_tab);
+            }
+            ui.label(format!("point {}", link.from_point));
+            ui.end_row();
+            ui.label("to");
+            if let Some(way) = object.references.get(1) {
+                key_label(ui, bf, *way, &mut open_tab);
+            }
+            ui.label(format!("point {}", link.to_point));
+            ui.end_row();
+        });
+        ui.label(format!("flags: {:#06X}", link.flags));
+        ui.label(format!("unk_01: {:#06X}", link.unk_01));
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct WayListEditor {
+    path_from: usize,
+    path_to: usize,
+    /// network of the way list it was built for, with its number of connected groups
+    network: Option<(YKey, WayNetwork, usize)>,
+    /// (from, to) points the path was found for
+    path_endpoints: Option<(usize, usize)>,
+    path: Option<Vec<usize>>,
+}
+
+impl EditorImpl for WayListEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        let bf = &ectx.bf;
+        let Some(object) = bf.object_table.get(&key) else { return; };
+        let ObjectArchetype::WayList(ref wal) = object.archetype else { return; };
+
+        if self.network.as_ref().is_none_or(|(cached, ..)| *cached != key) {
+            let network = WayNetwork::from_way_list(bf, key);
+            let num_components = network.components().len();
+            self.network = Some((key, network, num_components));
+            self.path_endpoints = None;
+        }
+        let Some((_, network, num_components)) = &self.network else { return; };
+        ui.label(format!("network: {} points, {} edges ({} external), {} connected groups",
+            network.nodes.len(), network.edges.len(), network.edges.iter().filter(|e| e.external).count(), num_components));
+
+        let mut open_tab = None;
+        if !network.nodes.is_empty() {
+            let last = network.nodes.len() - 1;
+            ui.horizontal(|ui| {
+                ui.label("path from");
+                ui.add(egui::DragValue::new(&mut self.path_from).range(0..=last));
+                ui.label("to");
+                ui.add(egui::DragValue::new(&mut self.path_to).range(0..=last));
+            });
+            self.path_from = self.path_from.min(last);
+            self.path_to = self.path_to.min(last);
+
+            if self.path_endpoints != Some((self.path_from, self.path_to)) {
+                self.path_endpoints = Some((self.path_from, self.path_to));
+                self.path = network.shortest_path(self.path_from, self.path_to);
+            }
+
+            match &self.path {
+                Some(path) => {
+                    ui.collapsing(format!("path: {} points", path.len()), |ui| {
+                        for node in path.iter().map(|idx| &network.nodes[*idx]) {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("point {} of", node.point));
+                                key_label(ui, bf, node.way, &mut open_tab);
+                                ui.label(format!("at {} width {}", node.position, node.width));
+                            });
+                        }
+                    });
+                },
+                None => { ui.label("no path"); }
+            }
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            let mut way_idx = 0;
+            for subkey in object.references.iter() {
+                ui.horizontal(|ui| {
+                    if bf.is_key_valid(*subkey) && bf.file_table[subkey].object_type == ObjectType::way {
+                        if let Some(flags) = wal.way_flags.get(way_idx) {
+                            ui.label(format!("{:#010X}", flags));
+                        }
+                        way_idx += 1;
+                    }
+                    key_label(ui, bf, *subkey, &mut open_tab);
+                });
+            }
+        });
 
-        for (idx, data) in way.way_datas.iter().enumerate() {
-            ui.label(format!("index {}: {}", idx, format_bytes_to_hex(data)));
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
         }
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use crate::bigfile::Bigfile;
+use crate::objects::{WorldData, WorldDataState};
 
 use super::*;
 
@@ -31,6 +33,20 @@ impl EditorImpl for WorldEditor {
         }
 
         egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            let mut open_tab = None;
+            if let ObjectArchetype::World(wor) = &ectx.bf.object_table[&key].archetype {
+                match &wor.data {
+                    WorldDataState::Decoded(data) => Self::draw_world_data(data, ui, ectx.bf, &mut open_tab),
+                    WorldDataState::Unknown(error) => { ui.label(format!("couldn't decode world: {}", error)); },
+                    WorldDataState::None => { }
+                }
+            }
+            if let Some(key) = open_tab {
+                ectx.respond(EditorResponse::OpenNewTab(key));
+            }
+
+            ui.separator();
+            ui.label("shaders");
             for shd in &self.display_order {
                 ui.collapsing(format!("{:#010X} {}", shd, ectx.bf.file_table[shd].get_name_ext()), |ui| {
                     if ui.button("Open shader...").clicked() {
@@ -48,6 +64,48 @@ impl EditorImpl for WorldEditor {
     }
 }
 
+impl WorldEditor {
+    fn key_button(ui: &mut egui::Ui, bf: &Bigfile, label: &str, key: YKey, open_tab: &mut Option<YKey>) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", label));
+            if bf.is_key_valid(key) {
+                if ui.selectable_label(false, format!("{:#010X} {}", key, bf.file_table[&key].get_name_ext())).clicked() {
+                    *open_tab = Some(key);
+                }
+            } else {
+                ui.label(format!("{:#010X}", key));
+            }
+        });
+    }
+
+    fn draw_world_data(data: &WorldData, ui: &mut egui::Ui, bf: &Bigfile, open_tab: &mut Option<YKey>) {
+        ui.label(format!("unk_01: {:#010X}", data.unk_01));
+        ui.label(format!("type: {}", data.world_type));
+        ui.label(format!("flags: {:#04X}", data.flags));
+        ui.label(format!("unk_02: {:#06X}", data.unk_02));
+        ui.label(format!("bounds: {} - {}", data.bounds_min, data.bounds_max));
+
+        ui.collapsing("settings", |ui| {
+            ui.label(format!("ambient: {}", data.settings.ambient_color));
+            ui.label(format!("fog color: {}", data.settings.fog_color));
+            ui.label(format!("fog range: {} - {}", data.settings.fog_near, data.settings.fog_far));
+            ui.label(format!("unk_01: {:#010X}", data.settings.unk_01));
+        });
+
+        ui.collapsing("links", |ui| {
+            for (name, key) in data.links.iter() {
+                Self::key_button(ui, bf, name, key, open_tab);
+            }
+        });
+
+        ui.collapsing(format!("streaming layers: {}", data.layers.len()), |ui| {
+            for layer in data.layers.iter() {
+                Self::key_button(ui, bf, &format!("{} ({:#010X})", layer.name, layer.unk_01), layer.gol, open_tab);
+            }
+        });
+    }
+}
+
 #[derive(Default)]
 pub struct RsfEditor;
 
@@ -1,16 +1,151 @@
 use super::*;
+use crate::objects::{ZoneModifier, ZoneType};
+use crate::bigfile::Bigfile;
 
 pub struct ZoneEditor;
 
+/// one line per zone: a button to open it and its shape
+pub fn zone_list_row(ui: &mut egui::Ui, bf: &Bigfile, zon: YKey, open_tab: &mut Option<YKey>) {
+    ui.horizontal(|ui| {
+        if ui.selectable_label(false, format!("{:#010X} {}", zon, bf.file_table[&zon].get_name_ext())).clicked() {
+            *open_tab = Some(zon);
+        }
+        match bf.object_table[&zon].archetype.as_zone() {
+            Some(zone) => {
+                ui.label(zone.zone_type.name());
+                ui.label(format!("{} modifiers", zone.modifiers.as_ref().map_or(0, |m| m.len())));
+            },
+            None => { ui.label("not loaded"); }
+        }
+    });
+}
+
+impl ZoneEditor {
+    fn draw_shape(ui: &mut egui::Ui, zone_type: &ZoneType) {
+        match zone_type {
+            ZoneType::Point => { ui.label("point"); },
+            ZoneType::Ray(ray) => {
+                ui.label("ray");
+                ui.label(format!("origin: {}", ray.origin));
+                ui.label(format!("direction: {}", ray.direction));
+                ui.label(format!("length: {}", ray.length));
+            },
+            ZoneType::Sphere(sphere) => {
+                ui.label("sphere");
+                ui.label(format!("center: [{}, {}, {}]", sphere.x, sphere.y, sphere.z));
+                ui.label(format!("radius: {}", sphere.radius));
+            },
+            ZoneType::AABox(aabox) => {
+                ui.label("aabox");
+                ui.label(format!("min: {}", aabox.min));
+                ui.label(format!("max: {}", aabox.max));
+            },
+            ZoneType::OBox(obox) => {
+                ui.label("obox");
+                let (_, rot, pos) = obox.matrix.to_scale_rotation_translation();
+                ui.label(format!("pos: {}", pos));
+                ui.label(format!("rot: {}", rot));
+                ui.label(format!("extents: {}", obox.extents));
+            },
+            ZoneType::Capsule(capsule) => {
+                ui.label("capsule");
+                ui.label(format!("start: {}", capsule.start));
+                ui.label(format!("end: {}", capsule.end));
+                ui.label(format!("radius: {}", capsule.radius));
+            }
+        }
+    }
+
+    fn draw_modifier(ui: &mut egui::Ui, modifier: &ZoneModifier) {
+        match modifier {
+            ZoneModifier::Ambient { color, intensity } => {
+                ui.label(format!("color: {}", color));
+                ui.label(format!("intensity: {}", intensity));
+            },
+            ZoneModifier::Fog { color, start, end, density } => {
+                ui.label(format!("color: {}", color));
+                ui.label(format!("start: {}  end: {}", start, end));
+                ui.label(format!("density: {}", density));
+            },
+            ZoneModifier::PostFx { post_fx } => { ui.label(format!("post fx: {:#010X}", post_fx)); },
+            ZoneModifier::SunLight { direction, color, intensity, unk_01 } => {
+                ui.label(format!("direction: {}", direction));
+                ui.label(format!("color: {}", color));
+                ui.label(format!("intensity: {}", intensity));
+                ui.label(format!("unk_01: {}", unk_01));
+            },
+            ZoneModifier::CharacterDiffuseMultiplier { multiplier } => { ui.label(format!("multiplier: {}", multiplier)); }
+        }
+    }
+}
+
 impl EditorImpl for ZoneEditor {
     fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        if ui.button("Export to .glb...").clicked() {
+            ectx.respond(EditorResponse::GltfExport(key));
+        }
+
         if let ObjectArchetype::Zone(zone) = &ectx.bf.object_table.get(&key).unwrap().archetype {
             ui.label(format!("unk_01: {:#04X}", zone.unk_01));
             ui.label(format!("unk_02: {:#04X}", zone.unk_02));
-            ui.label(format!("{:?}", zone.zone_type));
             ui.label(format!("unk_04: {:#04X}", zone.unk_04));
             ui.label(format!("unk_05: {:#04X}", zone.unk_05));
             ui.label(format!("unk_06: {:#04X}", zone.unk_06));
+            ui.separator();
+            Self::draw_shape(ui, &zone.zone_type);
+            ui.separator();
+
+            match &zone.modifiers {
+                Some(modifiers) => {
+                    ui.label(format!("modifiers: {}", modifiers.len()));
+                    for (idx, modifier) in modifiers.iter().enumerate() {
+                        egui::CollapsingHeader::new(format!("{:?}", modifier.mod_type())).id_salt(idx).default_open(true).show(ui, |ui| {
+                            Self::draw_modifier(ui, modifier);
+                        });
+                    }
+                },
+                None => { ui.label("no modifiers"); }
+            }
+        }
+    }
+}
+
+pub struct ZoneArrayEditor;
+
+impl EditorImpl for ZoneArrayEditor {
+    fn draw(&mut self, key: YKey, ui: &mut egui::Ui, ectx: &mut EditorContext, _tctx: &EditorTabContext) {
+        if ui.button("Export to .glb...").clicked() {
+            ectx.respond(EditorResponse::GltfExport(key));
+        }
+
+        let bf = &ectx.bf;
+        let obj = bf.object_table.get(&key).unwrap();
+        let ObjectArchetype::ZoneArray(zar) = &obj.archetype else { return; };
+
+        let zones: Vec<YKey> = obj.references.iter()
+            .filter(|zon| bf.is_key_valid(**zon) && bf.file_table[*zon].object_type == ObjectType::zon)
+            .copied()
+            .collect();
+        ui.label(format!("zones: {}", zones.len()));
+        if !zar.entries.is_empty() && zar.entries.len() != zones.len() {
+            ui.colored_label(egui::Color32::YELLOW, format!("{} entries but {} zone references", zar.entries.len(), zones.len()));
+        }
+        ui.separator();
+
+        let mut open_tab = None;
+        egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+            for (idx, zon) in zones.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if let Some(entry) = zar.entries.get(idx) {
+                        ui.label(format!("{:#010X}", entry.flags));
+                    }
+                    zone_list_row(ui, bf, *zon, &mut open_tab);
+                });
+            }
+        });
+
+        if let Some(key) = open_tab {
+            ectx.respond(EditorResponse::OpenNewTab(key));
         }
     }
-}
\ No newline at end of file
+}
@@ -18,6 +18,15 @@ pub fn format_bytes_to_hex(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<String>>().join(" ")
 }
 
+/// 16 bytes per line, each prefixed with its offset (starting at `base_offset`)
+pub fn format_bytes_to_hex_dump(bytes: &[u8], base_offset: usize) -> String {
+    bytes.chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| format!("{:08X}  {}", base_offset + i * 16, format_bytes_to_hex(chunk)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub trait AppUiUtil {
     fn enum_selector<T: strum::IntoEnumIterator + Clone + Display + PartialEq>(&mut self, value: &mut T);
     fn number_field(&mut self, num: &mut f32, storage_string: &mut String);
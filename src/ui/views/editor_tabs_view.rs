@@ -168,6 +168,17 @@ impl FileEditorTabs {
         egui::CentralPanel::default().show(ectx.ctx, |ui| {
             for tab in self.editor_tabs.iter_mut() {
                 if tab.key == key && tab.loaded {
+                    let obj = &ectx.bf.object_table[&key];
+                    if let Some(warning) = &obj.load_warning {
+                        ui.colored_label(egui::Color32::YELLOW, warning);
+                        egui::CollapsingHeader::new("unparsed data").id_salt(key).show(ui, |ui| {
+                            egui::ScrollArea::vertical().id_salt("unparsed_data").max_height(200.0).show(ui, |ui| {
+                                ui.monospace(crate::ui::util::format_bytes_to_hex_dump(&obj.unparsed_data, obj.consumed));
+                            });
+                        });
+                        ui.separator();
+                    }
+
                     let tctx = EditorTabContext {
                         load_set: &tab.load
                     };
@@ -1,5 +1,5 @@
-use crate::{bigfile::{metadata::ObjectType, Bigfile}, metadata::YKey, objects::ObjectArchetype, ui::AppContext};
-use std::{collections::HashSet, fs::{self, *}, io::{self, Write}};
+use crate::{bigfile::{io::parse_and_remove_refs, metadata::ObjectType, Bigfile}, metadata::YKey, objects::{ObjectArchetype, YetiObject}, ui::AppContext};
+use std::{collections::{BTreeMap, HashSet}, fs::{self, *}, io::{self, Write}, any::Any, panic::{self, AssertUnwindSafe, PanicHookInfo}};
 use log::*;
 use crate::egui as egui;
 
@@ -27,6 +27,12 @@ impl super::View for ToolsView {
             if ui.button("Export node hierarchy for .glb file").clicked() {
                 export_node_hierarchy();
             }
+            if ui.button("Export Parse Report").clicked() {
+                export_parse_report(bf);
+            }
+            if ui.button("Export Round Trip Report").clicked() {
+                export_round_trip_report(bf);
+            }
         }
     }
 }
@@ -104,7 +110,7 @@ fn export_zones(bf: &mut Bigfile) {
         for key in &keys {
             if let Ok(_) = bf.load_file(*key) {
                 if let ObjectArchetype::Zone(zon) = &bf.object_table[&key].archetype {
-                    writeln!(file, "{:#010X} - {} - {:?}", key, &bf.file_table[&key].get_name_ext(), zon).unwrap();
+                    writeln!(file, "{:#010X} - {} - {:?}", key, &bf.file_table[key].get_name_ext(), zon).unwrap();
                 }
             }
             bf.unload_file(*key).unwrap();
@@ -164,4 +170,282 @@ fn export_shader_node_ids(bf: &mut Bigfile) {
             error!("{}", err.to_string());
         }
     };
-}
\ No newline at end of file
+}
+
+struct ParseReportEntry {
+    key: YKey,
+    obj_type: ObjectType,
+    payload_len: usize,
+    result: Result<usize, String>,
+    /// set when the archetype loaded without decoding its payload
+    decode_error: Option<String>,
+}
+
+#[derive(Default)]
+struct ParseReportSummary {
+    total: usize,
+    failed: usize,
+    undecoded: usize,
+    fully_consumed: usize,
+    partially_consumed: usize,
+    payload_bytes: usize,
+    consumed_bytes: usize,
+}
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo) + Sync + Send + 'static>;
+
+/// Silences the panic hook while parsers are run under `catch_unwind`, putting the old hook back when dropped
+struct SilencePanics(Option<PanicHook>);
+
+impl SilencePanics {
+    fn new() -> Self {
+        let old_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| { }));
+        Self(Some(old_hook))
+    }
+}
+
+impl Drop for SilencePanics {
+    fn drop(&mut self) {
+        // the hook can't be swapped while this thread is panicking
+        if let Some(old_hook) = self.0.take().filter(|_| !std::thread::panicking()) {
+            panic::set_hook(old_hook);
+        }
+    }
+}
+
+/// Payload size after the reference list, split the same way as `parse_and_remove_refs` but without panicking on short files
+fn payload_len(bytes: &[u8]) -> usize {
+    let num_refs = bytes.get(..4).map_or(0, |num| u32::from_le_bytes([num[0], num[1], num[2], num[3]]) as usize);
+    bytes.len().saturating_sub(4 + 4 * num_refs)
+}
+
+fn try_parse_file(obj_type: &ObjectType, bytes: &[u8]) -> (usize, Result<usize, String>, Option<String>) {
+    // some parsers index into the buffer directly, so a bad file can panic instead of returning an error
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (_, payload) = parse_and_remove_refs(bytes);
+        let mut archetype = YetiObject::type_to_archetype(obj_type);
+        let result = archetype.load_from_buf(payload);
+        let decode_error = archetype.decode_error();
+        archetype.unload();
+        (payload.len(), result, decode_error)
+    }));
+
+    match result {
+        Ok((payload_len, Ok(consumed), decode_error)) => (payload_len, Ok(consumed), decode_error),
+        Ok((payload_len, Err(error), _)) => (payload_len, Err(error.to_string()), None),
+        Err(panic) => (payload_len(bytes), Err(format!("panicked: {}", panic_message(&panic))), None)
+    }
+}
+
+fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    panic.downcast_ref::<String>().map(|s| s.as_str())
+        .or_else(|| panic.downcast_ref::<&str>().copied())
+        .unwrap_or("unknown panic")
+}
+
+fn export_parse_report(bf: &mut Bigfile) {
+    let (summary_path, details_path) = match (make_tool_output_file_path("parse_report_summary.txt"), make_tool_output_file_path("parse_report_details.txt")) {
+        (Ok(s), Ok(d)) => (s, d),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    info!("exporting parse report to {} and {}", summary_path, details_path);
+
+    let mut keys: Vec<YKey> = bf.file_table.iter()
+        .filter(|ent| bf.is_key_valid(*ent.0))
+        .filter(|ent| !YetiObject::type_to_archetype(&ent.1.object_type).is_no_impl())
+        .map(|ent| *ent.0)
+        .collect();
+    keys.sort_by_key(|key| (bf.file_table[key].object_type as u16, u32::from(*key)));
+
+    let silence = SilencePanics::new();
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        let entry = &bf.file_table[&key];
+        let (payload_len, result, decode_error) = match bf.io.read_file(&bf.segment_header, &bf.bigfile_header, entry) {
+            Ok(bytes) => try_parse_file(&entry.object_type, &bytes),
+            Err(error) => (0, Err(format!("read error: {}", error)), None)
+        };
+
+        entries.push(ParseReportEntry {
+            key,
+            obj_type: entry.object_type,
+            payload_len,
+            result,
+            decode_error
+        });
+    }
+    drop(silence);
+
+    let mut summaries: BTreeMap<u16, (ObjectType, ParseReportSummary)> = BTreeMap::new();
+    for entry in &entries {
+        let (_, summary) = summaries.entry(entry.obj_type as u16).or_insert((entry.obj_type, ParseReportSummary::default()));
+        summary.total += 1;
+        summary.payload_bytes += entry.payload_len;
+        if entry.decode_error.is_some() {
+            summary.undecoded += 1;
+        }
+        match entry.result {
+            Ok(consumed) => {
+                summary.consumed_bytes += consumed.min(entry.payload_len);
+                if consumed >= entry.payload_len {
+                    summary.fully_consumed += 1;
+                } else {
+                    summary.partially_consumed += 1;
+                }
+            },
+            Err(_) => summary.failed += 1
+        }
+    }
+
+    if let Err(err) = File::create(&summary_path).and_then(|mut file| write_parse_report_summary(&mut file, &summaries)) {
+        error!("{}", err);
+    }
+
+    if let Err(err) = File::create(&details_path).and_then(|mut file| write_parse_report_details(&mut file, bf, &entries)) {
+        error!("{}", err);
+    }
+
+    info!("parse report finished, {} files parsed", entries.len());
+}
+
+fn write_parse_report_summary(file: &mut File, summaries: &BTreeMap<u16, (ObjectType, ParseReportSummary)>) -> io::Result<()> {
+    writeln!(file, "{:<6} {:>8} {:>8} {:>9} {:>8} {:>8} {:>14} {:>14} {:>9}", "type", "total", "failed", "undecoded", "full", "partial", "consumed", "payload", "coverage")?;
+    for (obj_type, summary) in summaries.values() {
+        let coverage = match summary.payload_bytes {
+            0 => 100.0,
+            n => summary.consumed_bytes as f64 / n as f64 * 100.0
+        };
+        writeln!(file, "{:<6} {:>8} {:>8} {:>9} {:>8} {:>8} {:>14} {:>14} {:>8.2}%",
+            format!("{:?}", obj_type), summary.total, summary.failed, summary.undecoded, summary.fully_consumed, summary.partially_consumed,
+            summary.consumed_bytes, summary.payload_bytes, coverage)?;
+    }
+    Ok(())
+}
+
+fn write_parse_report_details(file: &mut File, bf: &Bigfile, entries: &[ParseReportEntry]) -> io::Result<()> {
+    for entry in entries {
+        let name = bf.file_table[&entry.key].get_name_ext();
+        match (&entry.result, &entry.decode_error) {
+            (Ok(consumed), None) => writeln!(file, "{:#010X} {:<6} OK   {:>10} / {:<10} {}", entry.key, format!("{:?}", entry.obj_type), consumed, entry.payload_len, name)?,
+            (Ok(consumed), Some(error)) => writeln!(file, "{:#010X} {:<6} WARN {:>10} / {:<10} {} - {}", entry.key, format!("{:?}", entry.obj_type), consumed, entry.payload_len, name, error)?,
+            (Err(error), _) => writeln!(file, "{:#010X} {:<6} FAIL {:>10} / {:<10} {} - {}", entry.key, format!("{:?}", entry.obj_type), "-", entry.payload_len, name, error)?
+        }
+    }
+    Ok(())
+}
+
+enum RoundTripResult {
+    Identical,
+    /// identical, but only because this many unparsed trailing bytes were copied back as-is
+    Unparsed(usize),
+    Mismatch { offset: usize, original_len: usize, saved_len: usize },
+    Failed(String),
+}
+
+fn try_round_trip_file(obj_type: &ObjectType, bytes: &[u8]) -> RoundTripResult {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut obj = YetiObject::default();
+        obj.archetype = YetiObject::type_to_archetype(obj_type);
+        obj.load_from_buf(bytes)?;
+        obj.save_to_buf().map(|saved| (saved, obj.unparsed_data.len()))
+    }));
+
+    match result {
+        Ok(Ok((saved, unparsed_len))) => {
+            match bytes.iter().zip(saved.iter()).position(|(a, b)| a != b) {
+                None if bytes.len() == saved.len() && unparsed_len != 0 => RoundTripResult::Unparsed(unparsed_len),
+                None if bytes.len() == saved.len() => RoundTripResult::Identical,
+                offset => RoundTripResult::Mismatch {
+                    offset: offset.unwrap_or(bytes.len().min(saved.len())),
+                    original_len: bytes.len(),
+                    saved_len: saved.len()
+                }
+            }
+        },
+        Ok(Err(error)) => RoundTripResult::Failed(error.to_string()),
+        Err(panic) => RoundTripResult::Failed(format!("panicked: {}", panic_message(&panic)))
+    }
+}
+
+/// Loads and re-saves every file with a saveable archetype, checking that the output is byte-identical
+fn export_round_trip_report(bf: &mut Bigfile) {
+    let path = match make_tool_output_file_path("round_trip_report.txt") {
+        Ok(path) => path,
+        Err(error) => {
+            error!("{}", error);
+            return;
+        }
+    };
+
+    info!("exporting round trip report to {}", path);
+
+    let mut keys: Vec<YKey> = bf.file_table.iter()
+        .filter(|ent| bf.is_key_valid(*ent.0))
+        .filter(|ent| YetiObject::type_to_archetype(&ent.1.object_type).can_save())
+        .map(|ent| *ent.0)
+        .collect();
+    keys.sort_by_key(|key| (bf.file_table[key].object_type as u16, u32::from(*key)));
+
+    let silence = SilencePanics::new();
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        let entry = &bf.file_table[&key];
+        let result = match bf.io.read_file(&bf.segment_header, &bf.bigfile_header, entry) {
+            Ok(bytes) => try_round_trip_file(&entry.object_type, &bytes),
+            Err(error) => RoundTripResult::Failed(format!("read error: {}", error))
+        };
+        entries.push((key, entry.object_type, result));
+    }
+    drop(silence);
+
+    // (total, identical, unparsed, mismatched, failed)
+    let mut summaries: BTreeMap<u16, (ObjectType, [usize; 5])> = BTreeMap::new();
+    for (_, obj_type, result) in &entries {
+        let (_, counts) = summaries.entry(*obj_type as u16).or_insert((*obj_type, [0; 5]));
+        counts[0] += 1;
+        match result {
+            RoundTripResult::Identical => counts[1] += 1,
+            RoundTripResult::Unparsed(_) => counts[2] += 1,
+            RoundTripResult::Mismatch { .. } => counts[3] += 1,
+            RoundTripResult::Failed(_) => counts[4] += 1,
+        }
+    }
+
+    if let Err(err) = File::create(&path).and_then(|mut file| write_round_trip_report(&mut file, bf, &summaries, &entries)) {
+        error!("{}", err);
+    }
+
+    let identical = entries.iter().filter(|(_, _, result)| matches!(result, RoundTripResult::Identical)).count();
+    let unparsed = entries.iter().filter(|(_, _, result)| matches!(result, RoundTripResult::Unparsed(_))).count();
+    info!("round trip report finished, {} / {} files identical, {} more only through unparsed data", identical, entries.len(), unparsed);
+}
+
+fn write_round_trip_report(file: &mut File, bf: &Bigfile, summaries: &BTreeMap<u16, (ObjectType, [usize; 5])>, entries: &[(YKey, ObjectType, RoundTripResult)]) -> io::Result<()> {
+    writeln!(file, "{:<6} {:>8} {:>10} {:>10} {:>10} {:>8}", "type", "total", "identical", "unparsed", "mismatch", "failed")?;
+    for (obj_type, counts) in summaries.values() {
+        writeln!(file, "{:<6} {:>8} {:>10} {:>10} {:>10} {:>8}", format!("{:?}", obj_type), counts[0], counts[1], counts[2], counts[3], counts[4])?;
+    }
+    writeln!(file)?;
+
+    for (key, obj_type, result) in entries {
+        let name = bf.file_table[key].get_name_ext();
+        match result {
+            RoundTripResult::Identical => { },
+            RoundTripResult::Unparsed(unparsed_len) => {
+                writeln!(file, "{:#010X} {:<6} UNPARSED {} bytes copied back {}", key, format!("{:?}", obj_type), unparsed_len, name)?;
+            },
+            RoundTripResult::Mismatch { offset, original_len, saved_len } => {
+                writeln!(file, "{:#010X} {:<6} MISMATCH at {:#X} (original {} bytes, saved {} bytes) {}", key, format!("{:?}", obj_type), offset, original_len, saved_len, name)?;
+            },
+            RoundTripResult::Failed(error) => {
+                writeln!(file, "{:#010X} {:<6} FAIL {} - {}", key, format!("{:?}", obj_type), name, error)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
-use std::io::Cursor;
-use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read, Write};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use glam::*;
 use crate::YetiIOError;
 
@@ -27,4 +27,44 @@ pub fn read_mat4(cursor: &mut Cursor<&[u8]>) -> Result<Mat4, YetiIOError> {
             cursor.read_f32::<LittleEndian>()?,
         ),
     })
-}
\ No newline at end of file
+}
+
+pub fn read_vec3(cursor: &mut Cursor<&[u8]>) -> Result<Vec3, YetiIOError> {
+    Ok(Vec3::new(
+        cursor.read_f32::<LittleEndian>()?,
+        cursor.read_f32::<LittleEndian>()?,
+        cursor.read_f32::<LittleEndian>()?,
+    ))
+}
+
+pub fn read_vec4(rdr: &mut impl Read) -> Result<Vec4, YetiIOError> {
+    Ok(Vec4::new(
+        rdr.read_f32::<LittleEndian>()?,
+        rdr.read_f32::<LittleEndian>()?,
+        rdr.read_f32::<LittleEndian>()?,
+        rdr.read_f32::<LittleEndian>()?,
+    ))
+}
+
+pub fn read_quat(cursor: &mut Cursor<&[u8]>) -> Result<Quat, YetiIOError> {
+    Ok(Quat::from_xyzw(
+        cursor.read_f32::<LittleEndian>()?,
+        cursor.read_f32::<LittleEndian>()?,
+        cursor.read_f32::<LittleEndian>()?,
+        cursor.read_f32::<LittleEndian>()?,
+    ))
+}
+
+pub fn write_vec3(wtr: &mut impl Write, v: &Vec3) -> Result<(), YetiIOError> {
+    for f in v.to_array() {
+        wtr.write_f32::<LittleEndian>(f)?;
+    }
+    Ok(())
+}
+
+pub fn write_mat4(wtr: &mut impl Write, mat: &Mat4) -> Result<(), YetiIOError> {
+    for f in mat.to_cols_array() {
+        wtr.write_f32::<LittleEndian>(f)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
-use std::io::Read;
-use byteorder::ReadBytesExt;
+use std::io::{Read, Write};
+use byteorder::{ReadBytesExt, WriteBytesExt};
 use glam::{Mat4, Vec4};
 
 pub mod dds_header;
@@ -20,6 +20,14 @@ pub fn read_nul_term_string(rdr: &mut impl Read) -> std::io::Result<String> {
     Ok(s)
 }
 
+pub fn write_nul_term_string(wtr: &mut impl Write, s: &str) -> std::io::Result<()> {
+    // inverse of read_nul_term_string, which maps each byte straight to a char
+    for c in s.chars() {
+        wtr.write_u8(c as u8)?;
+    }
+    wtr.write_u8(0)
+}
+
 pub fn transform_yeti_matrix(mat: &Mat4) -> Mat4 {
     // https://stackoverflow.com/questions/1263072/changing-a-matrix-from-right-handed-to-left-handed-coordinate-system
     const TOGGLE_MATRIX: glam::Mat4 = Mat4 {